@@ -16,6 +16,7 @@
 #include "closure.h"
 
 #include <deque>
+#include <vector>
 #include <sstream>
 #include <algorithm>
 
@@ -26,7 +27,7 @@ namespace lalr1 {
 
 
 // global closure id counter
-t_state_id Closure::g_id = 0;
+std::atomic<t_state_id> Closure::g_id{0};
 
 
 Closure::Closure() : std::enable_shared_from_this<Closure>{}, m_id{g_id++}
@@ -215,6 +216,152 @@ void Closure::ResolveLookaheads()
 }
 
 
+/**
+ * add the resolved lookaheads from another closure with the same core
+ * @return true if new lookaheads were added
+ */
+bool Closure::AddLookaheads(const ClosurePtr& closure)
+{
+	bool added = false;
+
+	for(const ElementPtr& elem : m_elems)
+	{
+		t_hash elem_hash = elem->hash(true);
+
+		// find the element whose core has the same hash
+		if(auto iter = std::find_if(closure->m_elems.begin(), closure->m_elems.end(),
+			[elem_hash](const ElementPtr& closure_elem) -> bool
+			{
+				return closure_elem->hash(true) == elem_hash;
+			}); iter != closure->m_elems.end())
+		{
+			if(elem->AddLookaheads((*iter)->GetLookaheads()))
+				added = true;
+		}
+	}
+
+	if(added)
+		m_hash = std::nullopt;
+	return added;
+}
+
+
+/**
+ * tests if the closure can be merged with another one having the same core
+ * without introducing new reduce/reduce conflicts (weak compatibility),
+ * only the kernel elements, whose cursor has been moved by a transition, are compared
+ * @see https://doi.org/10.1007/BF00290336
+ */
+bool Closure::IsWeaklyCompatible(const Closure& closure) const
+{
+	// lookaheads of the kernel elements with the same core in both closures
+	std::vector<std::pair<const Terminal::t_terminalset*,
+		const Terminal::t_terminalset*>> lookaheads;
+
+	for(const ElementPtr& elem : m_elems)
+	{
+		// the lookaheads of the other elements follow from the kernel
+		if(elem->GetCursor() == 0)
+			continue;
+
+		t_hash elem_hash = elem->hash(true);
+
+		auto iter = std::find_if(closure.m_elems.begin(), closure.m_elems.end(),
+			[elem_hash](const ElementPtr& closure_elem) -> bool
+			{
+				return closure_elem->hash(true) == elem_hash;
+			});
+		if(iter == closure.m_elems.end())
+			return false;
+
+		lookaheads.emplace_back(std::make_pair(
+			&elem->GetLookaheads(), &(*iter)->GetLookaheads()));
+	}
+
+	auto intersects = [](const Terminal::t_terminalset* las1,
+		const Terminal::t_terminalset* las2) -> bool
+	{
+		for(const TerminalPtr& la : *las1)
+		{
+			if(las2->contains(la))
+				return true;
+		}
+		return false;
+	};
+
+	for(std::size_t idx1=0; idx1<lookaheads.size(); ++idx1)
+	{
+		for(std::size_t idx2=idx1+1; idx2<lookaheads.size(); ++idx2)
+		{
+			const auto& [las1_this, las1_other] = lookaheads[idx1];
+			const auto& [las2_this, las2_other] = lookaheads[idx2];
+
+			// merging does not create new common lookaheads
+			if(!intersects(las1_this, las2_other) && !intersects(las1_other, las2_this))
+				continue;
+
+			// common lookaheads already exist in one of the closures
+			if(intersects(las1_this, las2_this) || intersects(las1_other, las2_other))
+				continue;
+
+			return false;
+		}
+	}
+
+	return true;
+}
+
+
+/**
+ * tests if the kernel elements of both closures have the same lookaheads
+ * among the given ones, e.g. among the lookaheads of reduce/reduce conflicts
+ */
+bool Closure::HasSameKernelLookaheads(const Closure& closure,
+	const Terminal::t_terminalset& lookaheads) const
+{
+	// lookaheads of an element that are among the given ones
+	auto get_relevant = [&lookaheads](const ElementPtr& elem) -> Terminal::t_terminalset
+	{
+		Terminal::t_terminalset relevant;
+		for(const TerminalPtr& la : elem->GetLookaheads())
+		{
+			if(lookaheads.contains(la))
+				relevant.insert(la);
+		}
+		return relevant;
+	};
+
+	for(const ElementPtr& elem : m_elems)
+	{
+		// the lookaheads of the other elements follow from the kernel
+		if(elem->GetCursor() == 0)
+			continue;
+
+		t_hash elem_hash = elem->hash(true);
+
+		auto iter = std::find_if(closure.m_elems.begin(), closure.m_elems.end(),
+			[elem_hash](const ElementPtr& closure_elem) -> bool
+			{
+				return closure_elem->hash(true) == elem_hash;
+			});
+		if(iter == closure.m_elems.end())
+			return false;
+
+		const Terminal::t_terminalset las_this = get_relevant(elem);
+		const Terminal::t_terminalset las_other = get_relevant(*iter);
+		if(las_this.size() != las_other.size())
+			return false;
+		for(const TerminalPtr& la : las_this)
+		{
+			if(!las_other.contains(la))
+				return false;
+		}
+	}
+
+	return true;
+}
+
+
 /**
  * perform a transition and get the corresponding lalr(1) closure
  */
@@ -303,6 +450,32 @@ bool Closure::HasReduceConflict() const
 }
 
 
+/**
+ * get the lookaheads that are shared by different finished elements of the closure
+ */
+Terminal::t_terminalset Closure::GetReduceConflictLookaheads() const
+{
+	Terminal::t_terminalset seen_lookaheads, conflict_lookaheads;
+
+	for(const ElementPtr& elem : m_elems)
+	{
+		// only consider finished rules that are reduced
+		if(!elem->IsCursorAtEnd())
+			continue;
+
+		for(const TerminalPtr& lookahead : elem->GetLookaheads())
+		{
+			if(seen_lookaheads.contains(lookahead))
+				conflict_lookaheads.insert(lookahead);
+			else
+				seen_lookaheads.insert(lookahead);
+		}
+	}
+
+	return conflict_lookaheads;
+}
+
+
 /**
  * calculates a unique hash for the closure (with or without lookaheads)
  */
@@ -20,6 +20,7 @@
 
 #include <deque>
 #include <list>
+#include <atomic>
 #include <memory>
 #include <functional>
 #include <iostream>
@@ -66,10 +67,21 @@ public:
 
 	void AddLookaheadDependencies(const ClosurePtr& closure);
 	void ResolveLookaheads();
+	bool AddLookaheads(const ClosurePtr& closure);
+
+	// tests if the closure can be merged with another one having the same core
+	bool IsWeaklyCompatible(const Closure& closure) const;
+
+	// tests if the kernel elements have the same lookaheads among the given ones
+	bool HasSameKernelLookaheads(const Closure& closure,
+		const Terminal::t_terminalset& lookaheads) const;
 
 	// tests if the closure has a reduce/reduce conflict
 	bool HasReduceConflict() const;
 
+	// get the lookaheads of the reduce/reduce conflicts
+	Terminal::t_terminalset GetReduceConflictLookaheads() const;
+
 	t_hash hash(bool only_core = false) const;
 
 	friend std::ostream& operator<<(std::ostream& ostr, const Closure& coll);
@@ -79,7 +91,7 @@ private:
 	t_elements m_elems{};     // lalr(1) elements in the closure
 	t_state_id m_id{0};       // closure id
 
-	static t_state_id g_id;   // global closure id counter
+	static std::atomic<t_state_id> g_id;   // global closure id counter
 
 	// cached hash values
 	mutable std::optional<t_hash> m_hash{ std::nullopt };
@@ -20,7 +20,11 @@
 #include <sstream>
 #include <fstream>
 #include <deque>
+#include <vector>
 #include <algorithm>
+#include <atomic>
+#include <thread>
+#include <functional>
 
 #include <boost/functional/hash.hpp>
 
@@ -48,11 +52,14 @@ t_hash Collection::HashTransition::operator()(
 
 /**
  * compare two transition elements for equality
+ * (closures with the same core are not necessarily the same in lr(1) collections)
  */
 bool Collection::CompareTransitionsEqual::operator()(
 	const t_transition& tr1, const t_transition& tr2) const
 {
-	return HashTransition{}(tr1) == HashTransition{}(tr2);
+	return std::get<0>(tr1) == std::get<0>(tr2) &&
+		std::get<1>(tr1) == std::get<1>(tr2) &&
+		std::get<2>(tr1)->hash() == std::get<2>(tr2)->hash();
 }
 
 
@@ -105,7 +112,11 @@ const Collection& Collection::operator=(const Collection& coll)
 	this->m_closure_cache = coll.m_closure_cache;
 	this->m_seen_closures = coll.m_seen_closures;
 	this->m_stopOnConflicts = coll.m_stopOnConflicts;
+	this->m_num_threads = coll.m_num_threads;
+	this->m_algo = coll.m_algo;
+	this->m_la_algo = coll.m_la_algo;
 	this->m_progress_observer = coll.m_progress_observer;
+	this->m_conflict_solver = coll.m_conflict_solver;
 
 	return *this;
 }
@@ -147,7 +158,7 @@ Collection::t_transitions Collection::GetTransitions(
 			continue;
 
 		// only consider transitions from the given closure
-		if(closure_from->hash() != closure->hash())
+		if(closure_from != closure)
 			continue;
 
 		if(sym->IsTerminal() == term)
@@ -181,7 +192,7 @@ Terminal::t_terminalset Collection::_GetLookbackTerminals(
 		const SymbolPtr& sym = std::get<2>(transition);
 
 		// only consider transitions to the given closure
-		if(closure_to->hash() != closure->hash())
+		if(closure_to != closure)
 			continue;
 
 		if(sym->IsTerminal())
@@ -273,20 +284,482 @@ void Collection::DoTransitions(const ClosurePtr& closure_from)
 }
 
 
-void Collection::DoTransitions()
+/**
+ * calculate the hashes of all symbols and rules reachable from the given closure,
+ * these are cached lazily and must not be calculated concurrently
+ */
+static void precalc_hashes(const ClosurePtr& closure)
 {
-	m_closure_cache = nullptr;
-	DoTransitions(*m_collection.begin());
-	ReportProgress("Calculated transitions.", true);
+	std::unordered_set<t_symbol_id> seen_nonterms{};
+	std::deque<NonTerminalPtr> nonterms{};
+
+	for(const ElementPtr& elem : closure->GetElements())
+		nonterms.push_back(elem->GetLhs());
+
+	while(nonterms.size())
+	{
+		NonTerminalPtr nonterm = nonterms.front();
+		nonterms.pop_front();
+
+		if(seen_nonterms.contains(nonterm->GetId()))
+			continue;
+		seen_nonterms.insert(nonterm->GetId());
+		nonterm->hash();
+
+		for(t_index rule_idx=0; rule_idx<nonterm->NumRules(); ++rule_idx)
+		{
+			const WordPtr& rule = nonterm->GetRule(rule_idx);
+			rule->hash();
+
+			for(t_index sym_idx=0; sym_idx<rule->size(); ++sym_idx)
+			{
+				const SymbolPtr& sym = (*rule)[sym_idx];
+				sym->hash();
+
+				if(!sym->IsTerminal())
+					nonterms.push_back(std::dynamic_pointer_cast<NonTerminal>(sym));
+			}
+		}
+	}
+}
+
+
+/**
+ * perform all possible lalr(1) transitions from all closures,
+ * the closures of each breadth-first level are calculated in parallel,
+ * afterwards the closures are numbered like in the sequential construction
+ */
+void Collection::DoTransitionsParallel(const ClosurePtr& closure_start)
+{
+	precalc_hashes(closure_start);
+
+	m_closure_cache = std::make_shared<
+		std::unordered_map<t_hash, ClosurePtr>>();
+	m_closure_cache->emplace(std::make_pair(
+		closure_start->hash(true), closure_start));
+
+	// closures are created concurrently, so assign their ids in a well-defined order
+	t_state_id next_id = closure_start->GetId();
+	std::vector<ClosurePtr> closures_level{ closure_start };
+
+	while(closures_level.size())
+	{
+		// calculate the transitions of all closures in this level
+		std::vector<const Closure::t_transitions*> transitions_level(
+			closures_level.size(), nullptr);
+		std::atomic<std::size_t> next_closure{0};
+
+		auto calc_transitions = [&closures_level, &transitions_level, &next_closure]()
+		{
+			for(std::size_t idx = next_closure++; idx < closures_level.size(); idx = next_closure++)
+				transitions_level[idx] = &closures_level[idx]->DoTransitions();
+		};
+
+		std::vector<std::thread> threads;
+		std::size_t num_threads = std::min(m_num_threads, closures_level.size());
+		for(std::size_t thread_idx=1; thread_idx<num_threads; ++thread_idx)
+			threads.emplace_back(calc_transitions);
+		calc_transitions();
+		for(std::thread& thread : threads)
+			thread.join();
+
+		// merge the new closures into the collection
+		std::vector<ClosurePtr> closures_next_level;
+
+		for(std::size_t idx=0; idx<closures_level.size(); ++idx)
+		{
+			const ClosurePtr& closure_from = closures_level[idx];
+
+			for(const Closure::t_transition& tup : *transitions_level[idx])
+			{
+				const SymbolPtr& trans_sym = std::get<0>(tup);
+				const ClosurePtr& closure_to = std::get<1>(tup);
+				const Closure::t_elements& elems_from = std::get<2>(tup);
+
+				t_hash hash_to = closure_to->hash(true);
+				auto cacheIter = m_closure_cache->find(hash_to);
+				bool new_closure = (cacheIter == m_closure_cache->end());
+
+				std::ostringstream ostrMsg;
+				ostrMsg << "Calculating " << (new_closure ? "new " : "") <<  "transition "
+					<< closure_from->GetId() << " " << g_options.GetArrowChar() << " "
+					<< (new_closure ? next_id + 1 : cacheIter->second->GetId())
+					<< ". Total closures: " << m_collection.size()
+					<< ", total transitions: " << m_transitions.size()
+					<< ".";
+				ReportProgress(ostrMsg.str(), false);
+
+				if(new_closure)
+				{
+					// new unique closure
+					closure_to->SetId(++next_id);
+					m_closure_cache->emplace(std::make_pair(hash_to, closure_to));
+					m_collection.push_back(closure_to);
+					m_transitions.emplace(std::make_tuple(
+						closure_from, closure_to, trans_sym, elems_from));
+
+					closures_next_level.push_back(closure_to);
+				}
+				else
+				{
+					// reuse closure with the same core that has already been seen
+					const ClosurePtr& closure_to_existing = cacheIter->second;
+
+					// unite lookaheads
+					closure_to_existing->AddLookaheadDependencies(closure_to);
+
+					// add the transition from the closure
+					m_transitions.emplace(std::make_tuple(
+						closure_from, closure_to_existing, trans_sym, elems_from));
+				}
+			}
+		}
+
+		closures_level = std::move(closures_next_level);
+	}
+
+	RenumberDepthFirst(closure_start);
+}
+
+
+/**
+ * number the closures in the order the sequential construction creates them:
+ * all successors of a closure are created when it is reached depth-first,
+ * but only those whose core has not been reached before are kept
+ */
+void Collection::RenumberDepthFirst(const ClosurePtr& closure_start)
+{
+	std::unordered_set<const Closure*> seen_closures{ closure_start.get() };
+	t_state_id next_id = 0;
+	closure_start->SetId(next_id++);
+
+	std::function<void(const ClosurePtr&)> visit;
+	visit = [this, &seen_closures, &next_id, &visit](const ClosurePtr& closure_from)
+	{
+		const Closure::t_transitions& transitions = closure_from->DoTransitions();
+
+		// the successors get their ids before any of them is visited
+		t_state_id first_id = next_id;
+		next_id += transitions.size();
+
+		for(std::size_t idx=0; idx<transitions.size(); ++idx)
+		{
+			const ClosurePtr& closure_to = (*m_closure_cache)[
+				std::get<1>(transitions[idx])->hash(true)];
+			if(seen_closures.contains(closure_to.get()))
+				continue;
+
+			seen_closures.insert(closure_to.get());
+			closure_to->SetId(first_id + idx);
+			visit(closure_to);
+		}
+	};
+
+	visit(closure_start);
+}
+
+
+/**
+ * perform all possible lr(1) transitions from all closures,
+ * only merging closures with the same core if this does not lead to new conflicts,
+ * if lookaheads to split are given, closures are only merged if their kernel elements
+ * agree on them instead of being weakly compatible
+ * @see https://doi.org/10.1007/BF00290336
+ */
+void Collection::DoTransitionsPager(const ClosurePtr& closure_start,
+	const Terminal::t_terminalset* split_lookaheads)
+{
+	closure_start->ResolveLookaheads();
+
+	// closures with the same core
+	std::unordered_map<t_hash, std::vector<ClosurePtr>> cores{};
+	cores[closure_start->hash(true)].push_back(closure_start);
+
+	// already calculated transitions, key: [from closure, transition symbol]
+	std::unordered_map<const Closure*, std::unordered_map<t_hash, ClosurePtr>> successors{};
+
+	// closures whose transitions have to be (re-)calculated
+	std::deque<ClosurePtr> closures_todo{ closure_start };
+
+	while(closures_todo.size())
+	{
+		ClosurePtr closure_from = closures_todo.front();
+		closures_todo.pop_front();
+
+		for(const SymbolPtr& trans_sym : closure_from->GetPossibleTransitionSymbols())
+		{
+			auto [closure_to, elems_from] = closure_from->DoTransition(trans_sym);
+			closure_to->ResolveLookaheads();
+
+			// transition already known -> propagate new lookaheads to the successor
+			std::unordered_map<t_hash, ClosurePtr>& closure_successors = successors[closure_from.get()];
+			if(auto iterSucc = closure_successors.find(trans_sym->hash());
+				iterSucc != closure_successors.end())
+			{
+				if(iterSucc->second->AddLookaheads(closure_to))
+					closures_todo.push_back(iterSucc->second);
+				continue;
+			}
+
+			// look for a compatible closure with the same core
+			std::vector<ClosurePtr>& same_core = cores[closure_to->hash(true)];
+			ClosurePtr closure_to_existing = nullptr;
+			for(const ClosurePtr& closure : same_core)
+			{
+				bool compatible = split_lookaheads
+					? closure->HasSameKernelLookaheads(*closure_to, *split_lookaheads)
+					: closure->IsWeaklyCompatible(*closure_to);
+				if(compatible)
+				{
+					closure_to_existing = closure;
+					break;
+				}
+			}
+
+			std::ostringstream ostrMsg;
+			ostrMsg << "Calculating " << (closure_to_existing ? "" : "new ") <<  "transition "
+				<< closure_from->GetId() << " " << g_options.GetArrowChar() << " "
+				<< (closure_to_existing ? closure_to_existing : closure_to)->GetId()
+				<< ". Total closures: " << m_collection.size()
+				<< ", total transitions: " << m_transitions.size()
+				<< ".";
+			ReportProgress(ostrMsg.str(), false);
+
+			if(closure_to_existing)
+			{
+				// merge with the compatible closure
+				if(closure_to_existing->AddLookaheads(closure_to))
+					closures_todo.push_back(closure_to_existing);
+			}
+			else
+			{
+				// new unique closure
+				closure_to_existing = closure_to;
+				same_core.push_back(closure_to);
+				m_collection.push_back(closure_to);
+				closures_todo.push_back(closure_to);
+			}
+
+			closure_successors.emplace(std::make_pair(trans_sym->hash(), closure_to_existing));
+			m_transitions.emplace(std::make_tuple(
+				closure_from, closure_to_existing, trans_sym, elems_from));
+		}
+	}
+}
+
+
+/**
+ * calculate the lookaheads of all elements by tracing them back through the
+ * predecessor closures and collecting the spontaneously generated lookaheads,
+ * this is plain lalr(1) propagation on the finished collection,
+ * so the resulting lookahead sets are the same as the ones from the element dependencies
+ * @see https://doi.org/10.1145/69622.357187
+ */
+void Collection::ResolveLookaheadsFromPredecessors()
+{
+	// predecessors of each closure
+	std::unordered_map<const Closure*, std::vector<const Closure*>> predecessors{};
+	for(const t_transition& transition : m_transitions)
+	{
+		if(std::get<2>(transition)->IsEps())
+			continue;
+		predecessors[std::get<1>(transition).get()].push_back(std::get<0>(transition).get());
+	}
+
+	// closures of each element
+	std::unordered_map<const Element*, const Closure*> elem_closures{};
+	for(const ClosurePtr& closure : m_collection)
+	{
+		for(const ElementPtr& elem : closure->GetElements())
+			elem_closures.emplace(std::make_pair(elem.get(), closure.get()));
+	}
 
-	for(ClosurePtr& closure : m_collection)
+	// elements step back to the element in the predecessor closure
+	// or to the element in the same closure whose cursor is before the lhs nonterminal
+	auto get_back_steps = [&predecessors, &elem_closures](const Element* elem)
+		-> std::vector<std::pair<const Element*, Terminal::t_terminalset>>
+	{
+		std::vector<std::pair<const Element*, Terminal::t_terminalset>> steps;
+		const Closure* closure = elem_closures[elem];
+
+		if(elem->GetCursor() > 0)
+		{
+			for(const Closure* pred : predecessors[closure])
+			{
+				for(const ElementPtr& pred_elem : pred->GetElements())
+				{
+					if(pred_elem->GetCursor() + 1 == elem->GetCursor() &&
+						*pred_elem->GetLhs() == *elem->GetLhs() &&
+						*pred_elem->GetRhs() == *elem->GetRhs())
+						steps.emplace_back(std::make_pair(pred_elem.get(), Terminal::t_terminalset{}));
+				}
+			}
+
+			return steps;
+		}
+
+		for(const ElementPtr& closure_elem : closure->GetElements())
+		{
+			const SymbolPtr& sym = closure_elem->GetSymbolAtCursor();
+			if(!sym || sym->IsTerminal() || *sym != *elem->GetLhs())
+				continue;
+
+			// spontaneous lookaheads from the symbols following the nonterminal
+			const WordPtr& rhs = closure_elem->GetRhs();
+			t_index cursor = closure_elem->GetCursor();
+			bool nullable = (cursor + 1 >= rhs->size());
+
+			Terminal::t_terminalset spontaneous;
+			for(const TerminalPtr& first : rhs->CalcFirst(nullptr, cursor + 1))
+			{
+				if(first->IsEps())
+					nullable = true;
+				else
+					spontaneous.insert(first);
+			}
+
+			// only continue tracing if the rest of the rule can vanish
+			steps.emplace_back(std::make_pair(
+				nullable ? closure_elem.get() : nullptr, std::move(spontaneous)));
+		}
+
+		return steps;
+	};
+
+	std::unordered_map<const Element*, Terminal::t_terminalset> all_lookaheads{};
+
+	for(const ClosurePtr& closure : m_collection)
 	{
 		std::ostringstream ostrMsg;
-		ostrMsg << "Calculating lookaheads for state " << closure->GetId() << ".";
+		ostrMsg << "Tracing lookaheads back from state " << closure->GetId() << ".";
 		ReportProgress(ostrMsg.str(), false);
-		closure->ResolveLookaheads();
+
+		for(const ElementPtr& elem : closure->GetElements())
+		{
+			Terminal::t_terminalset lookaheads;
+			std::unordered_set<const Element*> seen_elems{ elem.get() };
+			std::deque<const Element*> todo{ elem.get() };
+
+			while(todo.size())
+			{
+				const Element* cur_elem = todo.front();
+				todo.pop_front();
+
+				// initial lookaheads of the starting element
+				if(cur_elem->GetLookaheadDependencies().size() == 0)
+				{
+					const Terminal::t_terminalset& initial = cur_elem->GetLookaheads();
+					lookaheads.insert(initial.begin(), initial.end());
+				}
+
+				for(auto& [step_elem, spontaneous] : get_back_steps(cur_elem))
+				{
+					lookaheads.insert(spontaneous.begin(), spontaneous.end());
+
+					if(step_elem && !seen_elems.contains(step_elem))
+					{
+						seen_elems.insert(step_elem);
+						todo.push_back(step_elem);
+					}
+				}
+			}
+
+			all_lookaheads.emplace(std::make_pair(elem.get(), std::move(lookaheads)));
+		}
+	}
+
+	for(const ClosurePtr& closure : m_collection)
+	{
+		for(const ElementPtr& elem : closure->GetElements())
+			elem->AddLookaheads(all_lookaheads[elem.get()]);
+	}
+}
+
+
+/**
+ * split the closures on the lanes whose lookaheads lead to reduce/reduce conflicts
+ * when merged, the collection is rebuilt from the starting elements and closures
+ * with the same core are only merged if their kernel elements agree on these lookaheads,
+ * the other lookaheads are still merged like in the lalr(1) collection
+ */
+void Collection::SplitLanes(const Closure::t_elements& start_elems)
+{
+	// lookaheads that are shared by different reductions
+	Terminal::t_terminalset conflict_lookaheads;
+	for(const ClosurePtr& closure : m_collection)
+	{
+		const Terminal::t_terminalset lookaheads = closure->GetReduceConflictLookaheads();
+		conflict_lookaheads.insert(lookaheads.begin(), lookaheads.end());
+	}
+
+	// no lanes have to be split
+	if(!conflict_lookaheads.size())
+		return;
+
+	std::ostringstream ostrMsg;
+	ostrMsg << "Splitting lanes with " << conflict_lookaheads.size()
+		<< " conflicting lookahead(s).";
+	ReportProgress(ostrMsg.str(), true);
+
+	ClosurePtr closure_start = std::make_shared<Closure>();
+	for(const ElementPtr& elem : start_elems)
+		closure_start->AddElement(std::make_shared<Element>(*elem));
+
+	m_collection.clear();
+	m_transitions.clear();
+	m_closure_cache = nullptr;
+
+	AddClosure(closure_start);
+	DoTransitionsPager(closure_start, &conflict_lookaheads);
+}
+
+
+void Collection::DoTransitions()
+{
+	m_closure_cache = nullptr;
+
+	if(m_algo == CollectionAlgorithm::PAGER)
+	{
+		// lookaheads are already resolved during the construction
+		DoTransitionsPager(*m_collection.begin());
+		ReportProgress("Calculated transitions and lookaheads.", true);
+	}
+	else
+	{
+		// copy the starting elements, in case the lanes have to be split later
+		Closure::t_elements start_elems;
+		if(m_la_algo == LookaheadAlgorithm::LANES)
+		{
+			for(const ElementPtr& elem : (*m_collection.begin())->GetElements())
+			{
+				if(elem->GetLookaheadDependencies().size() == 0)
+					start_elems.push_back(std::make_shared<Element>(*elem));
+			}
+		}
+
+		if(m_num_threads > 1)
+			DoTransitionsParallel(*m_collection.begin());
+		else
+			DoTransitions(*m_collection.begin());
+		ReportProgress("Calculated transitions.", true);
+
+		if(m_la_algo == LookaheadAlgorithm::LANES)
+		{
+			ResolveLookaheadsFromPredecessors();
+			SplitLanes(start_elems);
+		}
+		else
+		{
+			for(ClosurePtr& closure : m_collection)
+			{
+				std::ostringstream ostrMsg;
+				ostrMsg << "Calculating lookaheads for state " << closure->GetId() << ".";
+				ReportProgress(ostrMsg.str(), false);
+				closure->ResolveLookaheads();
+			}
+		}
+		ReportProgress("Calculated lookaheads.", true);
 	}
-	ReportProgress("Calculated lookaheads.", true);
 
 	Simplify();
 	ReportProgress("Simplified transitions.", true);
@@ -320,8 +793,12 @@ void Collection::DoTransitions()
 			std::cerr << "Error: " << ostrConflicts.str() << std::endl;
 	};
 
-	report_conflicts(HasReduceConflicts(), "reduce/reduce");
-	report_conflicts(HasShiftReduceConflicts(), "shift/reduce");
+	// with a conflict solver, conflicts are handled when creating the tables or parser
+	if(!m_conflict_solver)
+	{
+		report_conflicts(HasReduceConflicts(), "reduce/reduce");
+		report_conflicts(HasShiftReduceConflicts(), "shift/reduce");
+	}
 
 	ReportProgress("Calculated transitions.", true);
 }
@@ -338,15 +815,14 @@ void Collection::Simplify()
 
 	// cleanup closure ids
 	std::unordered_map<t_state_id, t_state_id> idmap{};
-	std::unordered_set<t_hash> already_seen{};
+	std::unordered_set<const Closure*> already_seen{};
 	t_state_id newid{};
 
 	for(const ClosurePtr& closure : m_collection)
 	{
 		t_state_id oldid = closure->GetId();
-		t_hash hash = closure->hash();
 
-		if(already_seen.contains(hash))
+		if(already_seen.contains(closure.get()))
 			continue;
 
 		auto iditer = idmap.find(oldid);
@@ -355,7 +831,7 @@ void Collection::Simplify()
 				std::make_pair(oldid, newid++)).first;
 
 		closure->SetId(iditer->second);
-		already_seen.insert(hash);
+		already_seen.insert(closure.get());
 	}
 }
 
@@ -405,7 +881,7 @@ std::set<t_state_id> Collection::HasShiftReduceConflicts() const
 			const ClosurePtr& stateFrom = std::get<0>(tup);
 			const SymbolPtr& symTrans = std::get<2>(tup);
 
-			if(stateFrom->hash() != closure->hash())
+			if(stateFrom != closure)
 				continue;
 			if(symTrans->IsEps() || !symTrans->IsTerminal())
 				continue;
@@ -487,17 +963,123 @@ void Collection::SetStopOnConflicts(bool b)
 }
 
 
+/**
+ * select the algorithm for creating the collection
+ */
+void Collection::SetAlgorithm(CollectionAlgorithm algo)
+{
+	m_algo = algo;
+}
+
+
+/**
+ * select the algorithm for calculating the lalr(1) lookaheads
+ */
+void Collection::SetLookaheadAlgorithm(LookaheadAlgorithm algo)
+{
+	m_la_algo = algo;
+}
+
+
+/**
+ * number of threads to use for calculating the transitions,
+ * 0: use all available hardware threads
+ */
+void Collection::SetNumThreads(std::size_t num_threads)
+{
+	if(num_threads == 0)
+		num_threads = std::max<std::size_t>(std::thread::hardware_concurrency(), 1);
+	m_num_threads = num_threads;
+}
+
+
 /**
  * try to solve a shift/reduce conflict
  */
-bool Collection::SolveConflict(
+/**
+ * set a user-defined function to resolve conflicts
+ */
+void Collection::SetConflictSolver(const t_conflict_solver& solver)
+{
+	m_conflict_solver = solver;
+}
+
+
+bool Collection::HasConflictSolver() const
+{
+	return static_cast<bool>(m_conflict_solver);
+}
+
+
+/**
+ * collect the conflicting elements and pass them to the conflict solver
+ */
+std::optional<t_conflict_solution> Collection::CallConflictSolver(const ClosurePtr& closure,
+	const TerminalPtr& lookahead, const Terminal::t_terminalset* lookbacks) const
+{
+	if(!m_conflict_solver)
+		return std::nullopt;
+
+	Conflict conflict{};
+	conflict.closure = closure;
+	conflict.lookahead = lookahead;
+	conflict.lookbacks = lookbacks ? *lookbacks : GetLookbackTerminals(closure);
+
+	for(const ElementPtr& elem : closure->GetElements())
+	{
+		if(elem->IsCursorAtEnd())
+		{
+			if(elem->GetLookaheads().contains(lookahead))
+				conflict.reduce_elems.push_back(elem);
+		}
+		else if(const SymbolPtr& sym = elem->GetSymbolAtCursor(); sym && *sym == *lookahead)
+		{
+			conflict.shift_elems.push_back(elem);
+		}
+	}
+
+	return m_conflict_solver(conflict);
+}
+
+
+/**
+ * try to resolve a shift/reduce conflict, first using the conflict solver
+ * and then the operator precedences and associativities
+ */
+bool Collection::SolveConflict(const ClosurePtr& closure,
 	const SymbolPtr& sym_at_cursor, const Terminal::t_terminalset& lookbacks,
-	t_index* shiftEntry, t_index* reduceEntry) const
+	t_index* shiftEntry, t_index* reduceEntry,
+	std::optional<t_semantic_id>* reduce_rule) const
 {
 	// no conflict?
 	if(*shiftEntry==ERROR_VAL || *reduceEntry==ERROR_VAL)
 		return true;
 
+	if(closure && sym_at_cursor && sym_at_cursor->IsTerminal())
+	{
+		const TerminalPtr& term_at_cursor =
+			std::dynamic_pointer_cast<Terminal>(sym_at_cursor);
+
+		if(auto solution = CallConflictSolver(closure, term_at_cursor, &lookbacks); solution)
+		{
+			switch(solution->first)
+			{
+				case ConflictAction::SHIFT:
+					*reduceEntry = ERROR_VAL;
+					return true;
+				case ConflictAction::REDUCE:
+					*shiftEntry = ERROR_VAL;
+					if(reduce_rule)
+						*reduce_rule = solution->second;
+					return true;
+				case ConflictAction::ERROR:
+					return false;
+				case ConflictAction::DEFAULT:
+					break;
+			}
+		}
+	}
+
 	bool solution_found = false;
 
 	// try to resolve conflict using operator precedences/associativities
@@ -559,6 +1141,21 @@ bool Collection::SolveConflict(
 }
 
 
+/**
+ * try to resolve a reduce/reduce conflict using the conflict solver
+ * @return rule to reduce or nothing if the conflict could not be resolved
+ */
+std::optional<t_semantic_id> Collection::SolveReduceConflict(
+	const ClosurePtr& closure, const TerminalPtr& lookahead) const
+{
+	auto solution = CallConflictSolver(closure, lookahead, nullptr);
+	if(!solution || solution->first != ConflictAction::REDUCE)
+		return std::nullopt;
+
+	return solution->second;
+}
+
+
 /**
  * write out the transitions graph to an ostream
  * @see https://graphviz.org/doc/info/shapes.html#html
@@ -738,6 +1335,7 @@ std::ostream& operator<<(std::ostream& ostr, const Collection& coll)
 	ostr << "--------------------------------------------------------------------------------\n";
 	if(use_colour)
 		ostr << no_col;
+	ostr << "Algorithm: " << coll.GetAlgorithmName() << "\n\n";
 
 	for(const ClosurePtr& closure : coll.m_collection)
 	{
@@ -896,4 +1494,36 @@ bool Collection::GetStopOnConflicts() const
 	return m_stopOnConflicts;
 }
 
+
+std::size_t Collection::GetNumThreads() const
+{
+	return m_num_threads;
+}
+
+
+CollectionAlgorithm Collection::GetAlgorithm() const
+{
+	return m_algo;
+}
+
+
+LookaheadAlgorithm Collection::GetLookaheadAlgorithm() const
+{
+	return m_la_algo;
+}
+
+
+/**
+ * get a description of the algorithms used to create the collection
+ */
+std::string Collection::GetAlgorithmName() const
+{
+	if(m_algo == CollectionAlgorithm::PAGER)
+		return "minimal LR(1) (Pager), lookaheads propagated during construction";
+
+	if(m_la_algo == LookaheadAlgorithm::LANES)
+		return "LALR(1) with split conflicting lanes, lookaheads traced through the predecessor states";
+	return "LALR(1), lookaheads from element dependencies";
+}
+
 } // namespace lalr1
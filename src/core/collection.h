@@ -33,6 +33,57 @@ class Collection;
 using CollectionPtr = std::shared_ptr<Collection>;
 
 
+/**
+ * algorithms for creating the collection
+ */
+enum class CollectionAlgorithm
+{
+	LALR,    // merge all closures with the same core
+	PAGER,   // only merge weakly compatible closures (minimal lr(1))
+};
+
+
+/**
+ * algorithms for calculating the lookaheads of lalr(1) collections
+ */
+enum class LookaheadAlgorithm
+{
+	DEPENDENCIES,  // resolve the lookahead dependencies between the elements
+	LANES,         // trace the elements back through their predecessor closures and split the lanes with conflicts
+};
+
+
+/**
+ * actions a conflict solver can choose
+ */
+enum class ConflictAction
+{
+	DEFAULT,  // use the operator precedences and associativities
+	SHIFT,    // shift the lookahead
+	REDUCE,   // reduce using the given rule
+	ERROR,    // don't resolve the conflict
+};
+
+
+/**
+ * a shift/reduce or reduce/reduce conflict passed to a conflict solver
+ */
+struct Conflict
+{
+	ClosurePtr closure{};                    // state having the conflict
+	TerminalPtr lookahead{};                 // conflicting lookahead terminal
+	Terminal::t_terminalset lookbacks{};     // terminals leading to the state
+	std::vector<ElementPtr> shift_elems{};   // elements that can shift the lookahead
+	std::vector<ElementPtr> reduce_elems{};  // elements that can reduce with the lookahead
+};
+
+
+// conflict solver callback, returns the chosen action and, for reductions, the rule
+using t_conflict_solution = std::pair<ConflictAction, std::optional<t_semantic_id>>;
+using t_conflict_solver = std::function<t_conflict_solution(const Conflict&)>;
+
+
+
 /**
  * LALR(1) collection of closures
  */
@@ -93,18 +144,31 @@ public:
 	bool SaveGraph(const std::string& file, bool write_full_coll = true) const;
 
 	void SetStopOnConflicts(bool b = true);
+	void SetNumThreads(std::size_t num_threads);
+	void SetAlgorithm(CollectionAlgorithm algo);
+	void SetLookaheadAlgorithm(LookaheadAlgorithm algo);
 
 	void SetProgressObserver(std::function<void(const std::string&, bool)> func);
 	void ReportProgress(const std::string& msg, bool finished = false);
 
-	bool SolveConflict(
+	void SetConflictSolver(const t_conflict_solver& solver);
+	bool HasConflictSolver() const;
+
+	bool SolveConflict(const ClosurePtr& closure,
 		const SymbolPtr& sym_at_cursor, const Terminal::t_terminalset& lookbacks,
-		t_index* shiftEntry, t_index* reduceEntry) const;
+		t_index* shiftEntry, t_index* reduceEntry,
+		std::optional<t_semantic_id>* reduce_rule = nullptr) const;
+	std::optional<t_semantic_id> SolveReduceConflict(
+		const ClosurePtr& closure, const TerminalPtr& lookahead) const;
 
 	// getters
 	const t_closures& GetClosures() const;
 	const t_transitions& GetTransitions() const;
 	bool GetStopOnConflicts() const;
+	std::size_t GetNumThreads() const;
+	CollectionAlgorithm GetAlgorithm() const;
+	LookaheadAlgorithm GetLookaheadAlgorithm() const;
+	std::string GetAlgorithmName() const;
 
 
 public:
@@ -116,9 +180,17 @@ protected:
 	Terminal::t_terminalset _GetLookbackTerminals(const ClosurePtr& closure) const;
 
 	void DoTransitions(const ClosurePtr& closure);
+	void DoTransitionsParallel(const ClosurePtr& closure);
+	void RenumberDepthFirst(const ClosurePtr& closure_start);
+	void DoTransitionsPager(const ClosurePtr& closure,
+		const Terminal::t_terminalset* split_lookaheads = nullptr);
+	void ResolveLookaheadsFromPredecessors();
+	void SplitLanes(const Closure::t_elements& start_elems);
 	void Simplify();
 
 	static t_hash hash_transition(const t_transition& trans);
+	std::optional<t_conflict_solution> CallConflictSolver(const ClosurePtr& closure,
+		const TerminalPtr& lookahead, const Terminal::t_terminalset* lookbacks) const;
 
 
 private:
@@ -129,8 +201,12 @@ private:
 	mutable t_seen_closures m_seen_closures{};  // set of seen closures
 
 	bool m_stopOnConflicts{true};               // stop table/code generation on conflicts
+	std::size_t m_num_threads{1};               // number of threads for calculating the transitions
+	CollectionAlgorithm m_algo{CollectionAlgorithm::LALR};
+	LookaheadAlgorithm m_la_algo{LookaheadAlgorithm::DEPENDENCIES};
 
 	std::function<void(const std::string& msg, bool finished)> m_progress_observer{};
+	t_conflict_solver m_conflict_solver{};      // user-defined conflict resolution
 
 	friend std::ostream& operator<<(std::ostream& ostr, const Collection& colls);
 };
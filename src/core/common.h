@@ -26,6 +26,7 @@ const constinit t_index ACCEPT_VAL = std::numeric_limits<t_index>::max()-1;  //
 
 const constinit t_symbol_id EPS_IDENT = std::numeric_limits<t_symbol_id>::max()-2;  // epsilon token id
 const constinit t_symbol_id END_IDENT = std::numeric_limits<t_symbol_id>::max()-3;  // end token id
+const constinit t_symbol_id ERRTOK_IDENT = std::numeric_limits<t_symbol_id>::max()-4;  // 'error' token id
 
 
 // (input) token types
@@ -83,6 +84,23 @@ t_str get_escaped_char(t_ch ch)
 }
 
 
+template<class t_str = std::string>
+t_str get_escaped_str(const t_str& str)
+{
+	t_str escaped;
+
+	for(typename t_str::value_type ch : str)
+	{
+		if(ch == '\n')
+			escaped += "\\n";
+		else
+			escaped += get_escaped_char<t_str>(ch);
+	}
+
+	return escaped;
+}
+
+
 } // namespace lalr1
 
 #endif
@@ -265,6 +265,28 @@ void Element::ResolveLookaheads(std::size_t recurse_depth)
 }
 
 
+/**
+ * directly add already resolved lookaheads
+ * @return true if new lookaheads were added
+ */
+bool Element::AddLookaheads(const Terminal::t_terminalset& lookaheads)
+{
+	if(!m_lookaheads)
+		m_lookaheads = Terminal::t_terminalset{};
+
+	bool added = false;
+	for(const TerminalPtr& la : lookaheads)
+	{
+		if(auto [iter, inserted] = m_lookaheads->insert(la); inserted)
+			added = true;
+	}
+
+	if(added)
+		m_hash = std::nullopt;
+	return added;
+}
+
+
 /**
  * get possible transition symbol
  */
@@ -64,6 +64,7 @@ public:
 	void AddLookaheadDependency(const t_dependency& dep);
 	void AddLookaheadDependency(const ElementPtr& elem, bool calc_first);
 	void ResolveLookaheads(std::size_t recurse_depth = 0);
+	bool AddLookaheads(const Terminal::t_terminalset& lookaheads);
 
 	const SymbolPtr& GetPossibleTransitionSymbol() const;
 
@@ -7,6 +7,8 @@
 
 #include "genoptions.h"
 
+#include <algorithm>
+
 
 namespace lalr1 {
 
@@ -65,6 +67,19 @@ t_index GenOptions::GetStartingState() const
 { return m_starting_state; }
 
 
+void GenOptions::AddStartRule(t_semantic_id rule_id)
+{ m_start_rules.push_back(rule_id); }
+
+const std::vector<t_semantic_id>& GenOptions::GetStartRules() const
+{ return m_start_rules; }
+
+bool GenOptions::IsAcceptingRule(t_semantic_id rule_id) const
+{
+	return rule_id == m_accepting_rule || std::find(
+		m_start_rules.begin(), m_start_rules.end(), rule_id) != m_start_rules.end();
+}
+
+
 /**
  * use non-terminal names for closures functions
  */
@@ -81,4 +96,60 @@ bool GenOptions::GetUseNegativeTableValues() const
 void GenOptions::SetUseNegativeTableValues(bool b)
 { m_useNegativeTableValues = b; }
 
+
+/**
+ * merge states with equivalent table entries
+ */
+bool GenOptions::GetMergeEquivalentStates() const
+{ return m_mergeEquivalentStates; }
+
+void GenOptions::SetMergeEquivalentStates(bool b)
+{ m_mergeEquivalentStates = b; }
+
+
+/**
+ * use default reductions for states that reduce the same rule for every lookahead,
+ * these are currently only supported by the rust runtime
+ */
+bool GenOptions::GetUseDefaultReductions() const
+{ return m_useDefaultReductions; }
+
+void GenOptions::SetUseDefaultReductions(bool b)
+{ m_useDefaultReductions = b; }
+
+
+/**
+ * reserved values of the exported tables,
+ * they must not collide with any state, rule index, or terminal id
+ */
+t_index GenOptions::GetErrorValue() const
+{ return m_error_val; }
+
+void GenOptions::SetErrorValue(t_index val)
+{ m_error_val = val; }
+
+t_index GenOptions::GetAcceptValue() const
+{ return m_accept_val; }
+
+void GenOptions::SetAcceptValue(t_index val)
+{ m_accept_val = val; }
+
+t_symbol_id GenOptions::GetEndId() const
+{ return m_end_id; }
+
+void GenOptions::SetEndId(t_symbol_id id)
+{ m_end_id = id; }
+
+t_symbol_id GenOptions::GetEpsId() const
+{ return m_eps_id; }
+
+void GenOptions::SetEpsId(t_symbol_id id)
+{ m_eps_id = id; }
+
+t_symbol_id GenOptions::GetErrorTokenId() const
+{ return m_errtok_id; }
+
+void GenOptions::SetErrorTokenId(t_symbol_id id)
+{ m_errtok_id = id; }
+
 } // namespace lalr1
@@ -9,6 +9,9 @@
 #define __LALR1_GEN_OPTIONS_H__
 
 #include "types.h"
+#include "common.h"
+
+#include <vector>
 
 
 namespace lalr1 {
@@ -23,6 +26,11 @@ public:
 	void SetAcceptingRule(t_semantic_id rule_id);
 	void SetStartingState(t_index start);
 
+	// a rule of the form start -> marker nonterminal makes the nonterminal a secondary start symbol
+	void AddStartRule(t_semantic_id rule_id);
+	const std::vector<t_semantic_id>& GetStartRules() const;
+	bool IsAcceptingRule(t_semantic_id rule_id) const;
+
 	bool GetUseOpChar() const;
 	bool GetGenDebugCode() const;
 	bool GetGenErrorCode() const;
@@ -36,6 +44,28 @@ public:
 	bool GetUseNegativeTableValues() const;
 	void SetUseNegativeTableValues(bool b = true);
 
+	bool GetMergeEquivalentStates() const;
+	void SetMergeEquivalentStates(bool b = true);
+
+	bool GetUseDefaultReductions() const;
+	void SetUseDefaultReductions(bool b = true);
+
+	// reserved values written to the exported tables
+	t_index GetErrorValue() const;
+	void SetErrorValue(t_index val);
+
+	t_index GetAcceptValue() const;
+	void SetAcceptValue(t_index val);
+
+	t_symbol_id GetEndId() const;
+	void SetEndId(t_symbol_id id);
+
+	t_symbol_id GetEpsId() const;
+	void SetEpsId(t_symbol_id id);
+
+	t_symbol_id GetErrorTokenId() const;
+	void SetErrorTokenId(t_symbol_id id);
+
 
 private:
 	bool m_useOpChar{true};                     // use printable character for operators if possible
@@ -44,9 +74,18 @@ private:
 	bool m_genPartialMatches{true};             // generates code for handling partial rule matches
 	bool m_useStateNames{false};                // name closure functions
 	bool m_useNegativeTableValues{true};
+	bool m_mergeEquivalentStates{false};        // merge states having the same actions in the tables
+	bool m_useDefaultReductions{false};         // reduce without lookahead in states having only one reduction
 
 	t_semantic_id m_accepting_rule{0};          // rule which leads to accepting the grammar
 	t_index m_starting_state{0};                // parser starting state
+	std::vector<t_semantic_id> m_start_rules{}; // rules leading to secondary start symbols
+
+	t_index m_error_val{ERROR_VAL};             // 'error' table entry
+	t_index m_accept_val{ACCEPT_VAL};           // 'accept' table entry
+	t_symbol_id m_end_id{END_IDENT};            // end token id
+	t_symbol_id m_eps_id{EPS_IDENT};            // epsilon token id
+	t_symbol_id m_errtok_id{ERRTOK_IDENT};      // 'error' token id
 };
 
 } // namespace lalr1
@@ -38,6 +38,11 @@ Parser& Parser::operator=(const Parser& parser)
 	m_numRhsSymsPerRule = parser.m_numRhsSymsPerRule;
 	m_vecLhsIndices = parser.m_vecLhsIndices;
 	m_semantics = parser.m_semantics;
+	m_end = parser.m_end;
+	m_err = parser.m_err;
+	m_acc = parser.m_acc;
+	m_errtok = parser.m_errtok;
+	m_errtok_idx = parser.m_errtok_idx;
 	m_starting_state = parser.m_starting_state;
 	m_accepting_rule = parser.m_accepting_rule;
 	m_debug = parser.m_debug;
@@ -243,7 +248,7 @@ void Parser::SetSemanticIdxMap(const t_mapSemanticIdIdx* map)
  */
 t_semantic_id Parser::GetRuleId(t_index idx) const
 {
-	if(idx == ACCEPT_VAL)
+	if(idx == m_acc)
 		idx = m_accepting_rule;
 
 	if(auto iter = m_mapSemanticIdx_inv.find(idx); iter != m_mapSemanticIdx_inv.end())
@@ -283,6 +288,25 @@ t_astbaseptr Parser::Parse(const t_toknodes& input) const
 	// already seen partial rules
 	t_active_rules active_rules;
 
+	// yacc-style error recovery using the 'error' token,
+	// further errors are only reported after shifting some tokens
+	const bool use_errtok = m_errtok_idx && *m_errtok_idx != m_err;
+	const std::size_t num_err_shifts = 3;
+	std::size_t err_status = 0;
+	std::vector<std::string> errors;
+
+	auto throw_errors = [&errors]()
+	{
+		std::ostringstream ostrErr;
+		for(std::size_t err_idx = 0; err_idx < errors.size(); ++err_idx)
+		{
+			if(err_idx > 0)
+				ostrErr << "\n";
+			ostrErr << errors[err_idx];
+		}
+		throw std::runtime_error(ostrErr.str());
+	};
+
 	// run the shift-reduce parser
 	while(true)
 	{
@@ -330,7 +354,7 @@ t_astbaseptr Parser::Parse(const t_toknodes& input) const
 			if(before_shift && partialmatchlen)
 				++*partialmatchlen;
 
-			if(!partialrule_idx || *partialrule_idx == ERROR_VAL)
+			if(!partialrule_idx || *partialrule_idx == m_err)
 				return;
 
 			t_semantic_id partialrule_id = GetRuleId(*partialrule_idx);
@@ -456,7 +480,7 @@ t_astbaseptr Parser::Parse(const t_toknodes& input) const
 		t_toknode accepted_topnode;
 
 		// neither a shift nor a reduce defined
-		if(newstate == ERROR_VAL && rule_idx == ERROR_VAL)
+		if(newstate == m_err && rule_idx == m_err)
 		{
 			std::ostringstream ostrErr;
 			ostrErr << "Undefined shift and reduce entries"
@@ -466,11 +490,62 @@ t_astbaseptr Parser::Parse(const t_toknodes& input) const
 				ostrErr << " = '" << char(curtok->GetId()) << "'";
 			ostrErr << get_line_numbers(curtok) << ".";
 
-			throw std::runtime_error(ostrErr.str());
+			if(!use_errtok)
+				throw std::runtime_error(ostrErr.str());
+
+			if(err_status == 0)
+				errors.push_back(ostrErr.str());
+
+			// the error token covers the offending token
+			t_astbaseptr errtok = std::make_shared<ASTBase>(m_errtok, *m_errtok_idx);
+			errtok->SetTerminalOverride(true);
+			errtok->SetLineRange(curtok->GetLineRange());
+
+			// error directly after shifting the error token: discard the lookahead
+			if(err_status == num_err_shifts)
+			{
+				if(curtok->GetId() == m_end || inputidx >= input.size())
+					throw_errors();
+
+				if(m_debug)
+					std::cout << "\tDiscarding token " << curtok->GetId() << "." << std::endl;
+				curtok = input[inputidx++];
+			}
+			err_status = num_err_shifts;
+
+			// partial matches of the discarded rules are invalid
+			active_rules.clear();
+
+			// pop states until the error token can be shifted
+			while((*m_tabActionShift)(states.top(), *m_errtok_idx) == m_err)
+			{
+				if(states.size() <= 1)
+					throw_errors();
+
+				states.pop();
+				symbols.pop();
+#ifndef LALR1_DONT_USE_SYMBOL_EXP
+				symbols_exp.pop();
+#endif
+			}
+
+			t_state_id errstate = (*m_tabActionShift)(states.top(), *m_errtok_idx);
+			if(m_debug)
+			{
+				std::cout << "\tShifting error token from state " << states.top()
+					<< " to state " << errstate << "." << std::endl;
+			}
+
+			states.push(errstate);
+			symbols.emplace(std::move(errtok));
+#ifndef LALR1_DONT_USE_SYMBOL_EXP
+			symbols_exp.emplace(t_index{});
+#endif
+			continue;
 		}
 
 		// both a shift and a reduce would be possible
-		else if(newstate != ERROR_VAL && rule_idx != ERROR_VAL)
+		else if(newstate != m_err && rule_idx != m_err)
 		{
 			std::ostringstream ostrErr;
 			ostrErr << "Shift/reduce conflict between shift"
@@ -485,18 +560,22 @@ t_astbaseptr Parser::Parse(const t_toknodes& input) const
 		}
 
 		// accept
-		else if(rule_idx == ACCEPT_VAL)
+		else if(rule_idx == m_acc)
 		{
 			if(m_debug)
 				std::cout << "\tAccepting." << std::endl;
 
+			// the result is incomplete if errors had to be recovered
+			if(errors.size())
+				throw_errors();
+
 			accepted = true;
 			accepted_topnode = symbols.top();
 			rule_idx = m_accepting_rule;
 		}
 
 		// shift
-		if(newstate != ERROR_VAL)
+		if(newstate != m_err)
 		{
 			if(m_debug)
 			{
@@ -517,6 +596,9 @@ t_astbaseptr Parser::Parse(const t_toknodes& input) const
 			// partial rules
 			apply_partial_rule(true);
 
+			if(err_status > 0)
+				--err_status;
+
 			states.push(newstate);
 			symbols.emplace(std::move(curtok));
 #ifndef LALR1_DONT_USE_SYMBOL_EXP
@@ -528,7 +610,7 @@ t_astbaseptr Parser::Parse(const t_toknodes& input) const
 		}
 
 		// reduce
-		else if(rule_idx != ERROR_VAL)
+		else if(rule_idx != m_err)
 		{
 			// remove fully reduced rule from active rule stack
 			ActiveRule* active_rule = nullptr;
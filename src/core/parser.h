@@ -47,6 +47,9 @@ public:
 	void SetSemanticRules(const t_semanticrules* rules) { m_semantics = rules; }
 
 	void SetEndId(t_symbol_id id) { m_end = id; }
+	void SetErrorValue(t_index val) { m_err = val; }
+	void SetAcceptValue(t_index val) { m_acc = val; }
+	void SetErrorToken(t_symbol_id id, t_index idx) { m_errtok = id; m_errtok_idx = idx; }
 	void SetStartingState(t_index state) { m_starting_state = state; }
 	void SetAcceptingRule(t_index rule) { m_accepting_rule = rule; }
 
@@ -97,6 +100,14 @@ private:
 	// end token id
 	t_symbol_id m_end{END_IDENT};
 
+	// 'error' and 'accept' table entries
+	t_index m_err{ERROR_VAL};
+	t_index m_acc{ACCEPT_VAL};
+
+	// 'error' token for error productions
+	t_symbol_id m_errtok{ERRTOK_IDENT};
+	std::optional<t_index> m_errtok_idx{};
+
 	// state index to begin parsing with
 	t_index m_starting_state{0};
 
@@ -711,6 +711,24 @@ bool %%PARSER_CLASS%%::ApplyRule(t_semantic_id rule_id, std::size_t rule_len, t_
 
 	const t_closures& closures = m_collection->GetClosures();
 
+	// the error token needs the states on an explicit stack
+	for(const t_transition& transition : m_collection->GetTransitions())
+	{
+		if(std::get<2>(transition)->GetId() == g_errtok->GetId())
+		{
+			std::cerr << "Warning: Error productions are not supported"
+				<< " by the recursive ascent parser." << std::endl;
+			break;
+		}
+	}
+
+	// only the primary starting state has an entry point
+	if(!GetStartRules().empty())
+	{
+		std::cerr << "Warning: Secondary start symbols are not supported"
+			<< " by the recursive ascent parser." << std::endl;
+	}
+
 	for(const ClosurePtr& closure : closures)
 	{
 		bool name_valid = false;
@@ -894,6 +912,7 @@ bool %%PARSER_CLASS%%::ApplyRule(t_semantic_id rule_id, std::size_t rule_len, t_
 
 		// reduce actions
 		std::vector<std::unordered_set<SymbolPtr>> reduces_lookaheads{};
+		std::vector<t_semantic_id> reduces_rules{};
 		std::vector<std::string> reduces{};
 
 		for(const ElementPtr& elem : closure->GetElements())
@@ -919,6 +938,7 @@ bool %%PARSER_CLASS%%::ApplyRule(t_semantic_id rule_id, std::size_t rule_len, t_
 				for(const TerminalPtr& la : lookaheads)
 					reduce_lookaheads.insert(la);
 				reduces_lookaheads.emplace_back(std::move(reduce_lookaheads));
+				reduces_rules.push_back(*rule_id);
 				ostr_reduce << "\t\t{\n";
 
 				// in extended grammar, first production (rule 0) is of the form start -> ...
@@ -988,9 +1008,16 @@ bool %%PARSER_CLASS%%::ApplyRule(t_semantic_id rule_id, std::size_t rule_len, t_
 						lookbacks = m_collection->GetLookbackTerminals(closure);
 
 					t_index shift_val = 0, reduce_val = 0;  // dummy values (only need to check for ERROR_VAL)
-					if(m_collection->SolveConflict(la, *lookbacks, &shift_val, &reduce_val))
+					std::optional<t_semantic_id> reduce_rule;
+					if(m_collection->SolveConflict(closure, la, *lookbacks, &shift_val, &reduce_val, &reduce_rule))
 					{
-						if(shift_val == ERROR_VAL && reduce_val != ERROR_VAL)
+						if(reduce_rule && *reduce_rule != reduces_rules[reduce_idx])
+						{
+							// the conflict solver chose another rule to reduce
+							iter_la = reduce_lookaheads.erase(iter_la);
+							already_incremented_la = true;
+						}
+						else if(shift_val == ERROR_VAL && reduce_val != ERROR_VAL)
 						{
 							// keep reduce, remove shift
 							iter_shift = shifts.erase(iter_shift);
@@ -1218,7 +1245,7 @@ bool %%PARSER_CLASS%%::ApplyRule(t_semantic_id rule_id, std::size_t rule_len, t_
 	std::string incl = "#include \"" + filename_h + "\"";
 	std::string time_stamp = get_timestamp();
 
-	t_symbol_id end_id = g_end->GetId();
+	t_symbol_id end_id = GetEndId();
 	std::ostringstream end_id_ostr;
 	end_id_ostr << "0x" << std::hex << end_id;
 	if constexpr(std::is_unsigned_v<t_symbol_id>)
@@ -0,0 +1,831 @@
+/**
+ * imports grammar and token specifications in the lexertl/parsertl (gram_grep) format
+ * @author Tobias Weber (orcid: 0000-0002-7230-1932)
+ * @date 15-oct-2026
+ * @license see 'LICENSE' file
+ *
+ * References:
+ *	- https://github.com/BenHanson/lexertl17
+ *	- https://github.com/BenHanson/parsertl17
+ *	- https://github.com/BenHanson/gram_grep
+ */
+
+#include "specimport.h"
+#include "timer.h"
+
+#include <fstream>
+#include <sstream>
+#include <iomanip>
+#include <algorithm>
+#include <cctype>
+
+#include <boost/algorithm/string.hpp>
+
+
+namespace lalr1 {
+
+
+/**
+ * maximum nesting depth of lexer macros
+ */
+static constexpr std::size_t MAX_MACRO_DEPTH = 32;
+
+
+/**
+ * token of the grammar section
+ */
+struct GrammarToken
+{
+	enum class Type
+	{
+		IDENT,      // symbol name
+		CHAR,       // char literal, e.g. '+'
+		STRING,     // string literal, e.g. "+="
+		COLON,
+		BAR,
+		SEMICOLON,
+		DIRECTIVE,  // e.g. %prec
+	};
+
+	Type ty{Type::IDENT};
+	std::string str{};
+	std::size_t line{};
+};
+
+
+static bool is_ident_char(char c, bool first = false)
+{
+	if(first)
+		return std::isalpha(c) || c == '_' || c == '.';
+	return std::isalnum(c) || c == '_' || c == '.';
+}
+
+
+static std::string get_json_str(const std::string& str)
+{
+	std::ostringstream ostr;
+
+	for(char c : str)
+	{
+		if(c == '\"' || c == '\\')
+			ostr << '\\' << c;
+		else if(c == '\n')
+			ostr << "\\n";
+		else if(c == '\t')
+			ostr << "\\t";
+		else if(static_cast<unsigned char>(c) < 0x20)
+			ostr << "\\u" << std::hex << std::setw(4) << std::setfill('0') << int(c) << std::dec;
+		else
+			ostr << c;
+	}
+
+	return ostr.str();
+}
+
+
+/**
+ * removes c and c++ style comments, but keeps line breaks and quoted strings
+ */
+static std::string strip_comments(const std::string& str)
+{
+	std::string stripped;
+	stripped.reserve(str.length());
+
+	for(std::size_t pos = 0; pos < str.length(); ++pos)
+	{
+		char c = str[pos];
+		char next = pos + 1 < str.length() ? str[pos + 1] : 0;
+
+		if(c == '\'' || c == '\"')
+		{
+			// copy the quoted string
+			stripped += c;
+			for(++pos; pos < str.length() && str[pos] != c && str[pos] != '\n'; ++pos)
+			{
+				stripped += str[pos];
+				if(str[pos] == '\\' && pos + 1 < str.length())
+					stripped += str[++pos];
+			}
+			if(pos < str.length())
+				stripped += str[pos];
+		}
+		else if(c == '/' && next == '/')
+		{
+			while(pos < str.length() && str[pos] != '\n')
+				++pos;
+			if(pos < str.length())
+				stripped += '\n';
+		}
+		else if(c == '/' && next == '*')
+		{
+			for(pos += 2; pos < str.length(); ++pos)
+			{
+				if(str[pos] == '\n')
+					stripped += '\n';
+				else if(str[pos] == '*' && pos + 1 < str.length() && str[pos + 1] == '/')
+				{
+					++pos;
+					break;
+				}
+			}
+		}
+		else
+		{
+			stripped += c;
+		}
+	}
+
+	return stripped;
+}
+
+
+/**
+ * gets the character of a char literal, e.g. '+' or '\n'
+ */
+static std::optional<char> get_char_literal(const std::string& str)
+{
+	if(str.length() == 3 && str[0] == '\'' && str[2] == '\'')
+		return str[1];
+
+	if(str.length() == 4 && str[0] == '\'' && str[1] == '\\' && str[3] == '\'')
+	{
+		switch(str[2])
+		{
+			case 'n': return '\n';
+			case 't': return '\t';
+			case 'r': return '\r';
+			case '0': return '\0';
+			default: return str[2];
+		}
+	}
+
+	return std::nullopt;
+}
+
+
+void SpecImporter::Clear()
+{
+	m_nonterms.clear();
+	m_terms.clear();
+	m_mapNonTerms.clear();
+	m_mapTerms.clear();
+	m_start = nullptr;
+	m_start_name = "";
+	m_accepting_rule = 0;
+
+	m_tokens.clear();
+	m_macros.clear();
+	m_caseless = false;
+
+	m_warnings.clear();
+
+	m_next_term_id = m_term_id_begin;
+	m_next_prec = 10;
+}
+
+
+void SpecImporter::Warn(std::size_t line, const std::string& msg)
+{
+	std::ostringstream ostr;
+	ostr << "Line " << line << ": " << msg;
+	m_warnings.emplace_back(ostr.str());
+}
+
+
+bool SpecImporter::LoadFile(const std::string& file)
+{
+	std::ifstream ifstr{file};
+	if(!ifstr)
+		return false;
+
+	return Load(ifstr);
+}
+
+
+/**
+ * splits the specification into its sections and imports them
+ */
+bool SpecImporter::Load(std::istream& istr)
+{
+	Clear();
+
+	std::vector<std::pair<std::string, std::size_t>> sections;  // text and first line
+	sections.emplace_back("", 1);
+
+	std::string line;
+	std::size_t linenr = 0;
+	while(std::getline(istr, line))
+	{
+		++linenr;
+
+		if(boost::trim_copy(line) == "%%")
+		{
+			sections.emplace_back("", linenr + 1);
+			continue;
+		}
+
+		sections.rbegin()->first += line + "\n";
+	}
+
+	if(sections.size() < 2)
+		throw std::runtime_error("The specification has no \"%%\" section separators.");
+
+	// a parser specification has parser directives in its first section
+	bool has_parser = false;
+	std::istringstream istrFirst{strip_comments(sections[0].first)};
+	for(std::string word; istrFirst >> word;)
+	{
+		if(word == "%token" || word == "%left" || word == "%right" ||
+			word == "%nonassoc" || word == "%precedence" || word == "%start")
+		{
+			has_parser = true;
+			break;
+		}
+	}
+
+	std::size_t lexer_idx = 0;
+	if(has_parser)
+	{
+		ParseParserDirectives(sections[0].first, sections[0].second);
+		ParseGrammar(sections[1].first, sections[1].second);
+		lexer_idx = 2;
+	}
+
+	if(sections.size() > lexer_idx + 1)
+	{
+		ParseLexerDirectives(sections[lexer_idx].first, sections[lexer_idx].second);
+		ParseLexerRules(sections[lexer_idx + 1].first, sections[lexer_idx + 1].second);
+	}
+	else if(!has_parser)
+	{
+		throw std::runtime_error("The specification has no lexer rules section.");
+	}
+
+	return true;
+}
+
+
+/**
+ * gets a terminal by its name or char literal, creates it if needed
+ */
+TerminalPtr SpecImporter::GetTerminal(const std::string& name)
+{
+	if(auto iter = m_mapTerms.find(name); iter != m_mapTerms.end())
+		return iter->second;
+
+	TerminalPtr term;
+	if(std::optional<char> ch = get_char_literal(name); ch)
+	{
+		// char literals use their character as id
+		term = std::make_shared<Terminal>(
+			static_cast<t_symbol_id>(static_cast<unsigned char>(*ch)),
+			std::string{*ch});
+	}
+	else
+	{
+		term = std::make_shared<Terminal>(m_next_term_id++, name);
+	}
+
+	m_terms.push_back(term);
+	m_mapTerms.emplace(std::make_pair(name, term));
+	return term;
+}
+
+
+/**
+ * %token, %left, %right, %nonassoc, %precedence and %start directives
+ */
+void SpecImporter::ParseParserDirectives(const std::string& section, std::size_t line)
+{
+	std::istringstream istr{strip_comments(section)};
+
+	for(std::string str; std::getline(istr, str); ++line)
+	{
+		std::vector<std::string> words;
+		std::istringstream istrWords{str};
+		for(std::string word; istrWords >> word;)
+			words.push_back(word);
+		if(!words.size())
+			continue;
+
+		const std::string& directive = words[0];
+		std::optional<char> assoc;
+
+		if(directive == "%start" && words.size() > 1)
+		{
+			m_start_name = words[1];
+			continue;
+		}
+		else if(directive == "%left")
+		{
+			assoc = 'l';
+		}
+		else if(directive == "%right")
+		{
+			assoc = 'r';
+		}
+		else if(directive == "%nonassoc")
+		{
+			Warn(line, "Non-associative operators are not supported, only setting their precedence.");
+		}
+		else if(directive != "%token" && directive != "%precedence")
+		{
+			Warn(line, "Ignoring unsupported directive \"" + directive + "\".");
+			continue;
+		}
+
+		bool set_prec = (directive != "%token");
+
+		for(std::size_t idx = 1; idx < words.size(); ++idx)
+		{
+			// skip type tags, e.g. %token <val> NUM
+			if(words[idx].front() == '<')
+				continue;
+
+			TerminalPtr term = GetTerminal(words[idx]);
+			if(set_prec && assoc)
+				term->SetPrecedence(m_next_prec, *assoc);
+			else if(set_prec)
+				term->SetPrecedence(m_next_prec);
+		}
+
+		// later declarations have higher precedence
+		if(set_prec)
+			m_next_prec += 10;
+	}
+}
+
+
+/**
+ * grammar rules, e.g.: expr : expr '+' expr | NUM ;
+ */
+void SpecImporter::ParseGrammar(const std::string& section, std::size_t line)
+{
+	using t_type = GrammarToken::Type;
+
+	// get the tokens of the grammar section
+	std::string text = strip_comments(section);
+	std::vector<GrammarToken> toks;
+
+	for(std::size_t pos = 0; pos < text.length();)
+	{
+		char c = text[pos];
+
+		if(c == '\n')
+		{
+			++line;
+			++pos;
+		}
+		else if(std::isspace(c))
+		{
+			++pos;
+		}
+		else if(c == '{')
+		{
+			// skip semantic actions
+			std::size_t depth = 0;
+			for(; pos < text.length(); ++pos)
+			{
+				if(text[pos] == '\n')
+					++line;
+				else if(text[pos] == '{')
+					++depth;
+				else if(text[pos] == '}' && --depth == 0)
+					break;
+			}
+			++pos;
+		}
+		else if(c == '<')
+		{
+			// skip type tags
+			pos = text.find('>', pos);
+			pos = (pos == std::string::npos) ? text.length() : pos + 1;
+		}
+		else if(c == '\'' || c == '\"')
+		{
+			std::size_t end = pos + 1;
+			for(; end < text.length() && text[end] != c && text[end] != '\n'; ++end)
+			{
+				if(text[end] == '\\')
+					++end;
+			}
+			if(end >= text.length() || text[end] != c)
+				throw std::runtime_error("Line " + std::to_string(line) + ": Unterminated literal.");
+
+			std::string lit = text.substr(pos, end - pos + 1);
+			if(c == '\"')
+				lit = lit.substr(1, lit.length() - 2);
+			toks.emplace_back(GrammarToken{c == '\'' ? t_type::CHAR : t_type::STRING, lit, line});
+			pos = end + 1;
+		}
+		else if(c == ':')
+		{
+			toks.emplace_back(GrammarToken{t_type::COLON, ":", line});
+			++pos;
+		}
+		else if(c == '|')
+		{
+			toks.emplace_back(GrammarToken{t_type::BAR, "|", line});
+			++pos;
+		}
+		else if(c == ';')
+		{
+			toks.emplace_back(GrammarToken{t_type::SEMICOLON, ";", line});
+			++pos;
+		}
+		else if(c == '%' || is_ident_char(c, true))
+		{
+			std::size_t end = pos + 1;
+			while(end < text.length() && is_ident_char(text[end]))
+				++end;
+			toks.emplace_back(GrammarToken{c == '%' ? t_type::DIRECTIVE : t_type::IDENT,
+				text.substr(pos, end - pos), line});
+			pos = end;
+		}
+		else
+		{
+			throw std::runtime_error("Line " + std::to_string(line)
+				+ ": Unexpected character '" + c + "' in grammar.");
+		}
+	}
+
+	// get the productions
+	using t_alternative = std::vector<GrammarToken>;
+	std::vector<std::pair<GrammarToken, std::vector<t_alternative>>> productions;
+	bool warned_prec = false;
+
+	for(std::size_t idx = 0; idx < toks.size();)
+	{
+		if(toks[idx].ty != t_type::IDENT || idx + 1 >= toks.size()
+			|| toks[idx + 1].ty != t_type::COLON)
+		{
+			throw std::runtime_error("Line " + std::to_string(toks[idx].line)
+				+ ": Expected a rule of the form \"lhs : rhs ;\".");
+		}
+
+		const GrammarToken& lhs = toks[idx];
+		std::vector<t_alternative> alternatives{ t_alternative{} };
+
+		for(idx += 2; idx < toks.size(); ++idx)
+		{
+			const GrammarToken& tok = toks[idx];
+
+			if(tok.ty == t_type::SEMICOLON)
+			{
+				++idx;
+				break;
+			}
+			else if(tok.ty == t_type::BAR)
+			{
+				alternatives.emplace_back(t_alternative{});
+			}
+			else if(tok.ty == t_type::DIRECTIVE && tok.str == "%empty")
+			{
+			}
+			else if(tok.ty == t_type::DIRECTIVE && tok.str == "%prec")
+			{
+				if(!warned_prec)
+					Warn(tok.line, "Rule precedences (%prec) are not supported and ignored.");
+				warned_prec = true;
+				++idx;  // skip the precedence symbol
+			}
+			else if(tok.ty == t_type::DIRECTIVE)
+			{
+				throw std::runtime_error("Line " + std::to_string(tok.line)
+					+ ": Unknown directive \"" + tok.str + "\" in grammar.");
+			}
+			else if(tok.ty == t_type::COLON)
+			{
+				throw std::runtime_error("Line " + std::to_string(tok.line)
+					+ ": Missing ';' before this rule.");
+			}
+			else
+			{
+				alternatives.rbegin()->push_back(tok);
+			}
+		}
+
+		productions.emplace_back(std::make_pair(lhs, std::move(alternatives)));
+	}
+
+	if(!productions.size())
+		throw std::runtime_error("The grammar has no rules.");
+
+	// create the non-terminals, the first one is the augmented start symbol
+	std::string start_name = m_start_name != "" ? m_start_name : productions[0].first.str;
+	std::string aug_name = "__start";
+	while(std::any_of(productions.begin(), productions.end(),
+			[&aug_name](const auto& prod) -> bool { return prod.first.str == aug_name; }))
+		aug_name += "_";
+
+	t_symbol_id nonterm_id = m_nonterm_id_begin;
+	NonTerminalPtr aug_start = std::make_shared<NonTerminal>(nonterm_id++, aug_name);
+	m_nonterms.push_back(aug_start);
+
+	for(const auto& [lhs, alternatives] : productions)
+	{
+		if(m_mapNonTerms.contains(lhs.str))
+			continue;
+		if(m_mapTerms.contains(lhs.str))
+		{
+			throw std::runtime_error("Line " + std::to_string(lhs.line)
+				+ ": Token \"" + lhs.str + "\" is used as a rule name.");
+		}
+
+		NonTerminalPtr nonterm = std::make_shared<NonTerminal>(nonterm_id++, lhs.str);
+		m_nonterms.push_back(nonterm);
+		m_mapNonTerms.emplace(std::make_pair(lhs.str, nonterm));
+	}
+
+	auto iterStart = m_mapNonTerms.find(start_name);
+	if(iterStart == m_mapNonTerms.end())
+		throw std::runtime_error("Start symbol \"" + start_name + "\" has no rules.");
+
+	t_semantic_id semantic_id = 0;
+	m_accepting_rule = semantic_id;
+	aug_start->AddRule({ iterStart->second }, semantic_id++);
+	m_start = aug_start;
+
+	// create the rules
+	for(const auto& [lhs, alternatives] : productions)
+	{
+		NonTerminalPtr nonterm = m_mapNonTerms[lhs.str];
+
+		for(const t_alternative& alternative : alternatives)
+		{
+			Word rule;
+
+			for(const GrammarToken& tok : alternative)
+			{
+				if(auto iter = m_mapNonTerms.find(tok.str);
+					tok.ty == t_type::IDENT && iter != m_mapNonTerms.end())
+				{
+					rule.AddSymbol(iter->second);
+					continue;
+				}
+
+				std::string name = tok.str;
+				if(tok.ty == t_type::IDENT && !m_mapTerms.contains(name))
+					Warn(tok.line, "Undeclared symbol \"" + name + "\" is used as token.");
+				else if(tok.ty == t_type::CHAR && !get_char_literal(name))
+					throw std::runtime_error("Line " + std::to_string(tok.line)
+						+ ": Invalid char literal " + name + ".");
+
+				rule.AddSymbol(GetTerminal(name));
+			}
+
+			if(rule.size() == 0)
+				rule.AddSymbol(g_eps);
+
+			nonterm->AddRule(rule, semantic_id++);
+		}
+	}
+}
+
+
+/**
+ * lexer macros and options, e.g.: DIGIT [0-9]
+ */
+void SpecImporter::ParseLexerDirectives(const std::string& section, std::size_t line)
+{
+	std::istringstream istr{strip_comments(section)};
+
+	for(std::string str; std::getline(istr, str); ++line)
+	{
+		boost::trim(str);
+		if(str == "")
+			continue;
+
+		std::size_t sep = str.find_first_of(" \t");
+		std::string name = str.substr(0, sep);
+		std::string val = sep == std::string::npos ? "" : boost::trim_copy(str.substr(sep));
+
+		if(name == "%option" && val == "caseless")
+			m_caseless = true;
+		else if(name == "%x" || name == "%s")
+			Warn(line, "Lexer start states are not supported, only using rules of the initial state.");
+		else if(name[0] == '%')
+			Warn(line, "Ignoring unsupported directive \"" + str + "\".");
+		else if(val == "")
+			Warn(line, "Ignoring macro \"" + name + "\" without a definition.");
+		else
+			m_macros[name] = val;
+	}
+}
+
+
+/**
+ * lexer rules, e.g.: {DIGIT}+ NUM
+ */
+void SpecImporter::ParseLexerRules(const std::string& section, std::size_t line)
+{
+	std::istringstream istr{section};
+
+	for(std::string str; std::getline(istr, str); ++line)
+	{
+		boost::trim(str);
+		if(str == "" || str.starts_with("//"))
+			continue;
+		if(str.starts_with("/*") && str.ends_with("*/"))
+			continue;
+
+		// start state prefix, e.g. <INITIAL>
+		if(str[0] == '<')
+		{
+			std::size_t end = str.find('>');
+			if(end == std::string::npos)
+				throw std::runtime_error("Line " + std::to_string(line) + ": Unterminated start state.");
+
+			std::string state = str.substr(1, end - 1);
+			if(state != "INITIAL" && state != "*")
+			{
+				Warn(line, "Skipping rule for start state \"" + state + "\".");
+				continue;
+			}
+
+			str = str.substr(end + 1);
+		}
+
+		// get the regex, which ends at the first white space outside of quotes and classes
+		std::size_t pos = 0;
+		bool in_quotes = false;
+		std::size_t in_class = 0;
+		for(; pos < str.length(); ++pos)
+		{
+			char c = str[pos];
+
+			if(c == '\\')
+				++pos;
+			else if(c == '\"' && !in_class)
+				in_quotes = !in_quotes;
+			else if(c == '[' && !in_quotes)
+				++in_class;
+			else if(c == ']' && !in_quotes && in_class)
+				--in_class;
+			else if(std::isspace(c) && !in_quotes && !in_class)
+				break;
+		}
+
+		std::string regex = str.substr(0, std::min(pos, str.length()));
+		std::string action = pos < str.length() ? boost::trim_copy(str.substr(pos)) : "";
+
+		// state transition suffix, e.g. "/*"<COMMENT>
+		if(regex.ends_with(">"))
+		{
+			if(std::size_t state_pos = regex.rfind('<'); state_pos != std::string::npos
+				&& state_pos > 0 && regex[state_pos - 1] != '\\')
+			{
+				std::string state = regex.substr(state_pos + 1, regex.length() - state_pos - 2);
+				regex.erase(state_pos);
+
+				if(state != "INITIAL" && state != ".")
+				{
+					Warn(line, "Skipping rule switching to start state \"" + state + "\".");
+					continue;
+				}
+			}
+		}
+
+		TokenSpec token;
+		token.regex = ConvertRegex(regex, line);
+		token.line = line;
+
+		if(action == "skip()")
+		{
+			token.name = action;
+		}
+		else if(action == "")
+		{
+			Warn(line, "Skipping rule without a token.");
+			continue;
+		}
+		else
+		{
+			if(action[0] == '\'' && !get_char_literal(action))
+				throw std::runtime_error("Line " + std::to_string(line)
+					+ ": Invalid char literal " + action + ".");
+			if(m_start && !m_mapTerms.contains(action))
+				Warn(line, "Token \"" + action + "\" is not used in the grammar.");
+
+			TerminalPtr term = GetTerminal(action);
+			token.id = term->GetId();
+			token.name = term->GetStrId();
+		}
+
+		m_tokens.emplace_back(std::move(token));
+	}
+}
+
+
+/**
+ * converts a lexertl regex to an ecmascript one by expanding
+ * the macros and escaping quoted strings
+ */
+std::string SpecImporter::ConvertRegex(const std::string& regex,
+	std::size_t line, std::size_t depth) const
+{
+	if(depth > MAX_MACRO_DEPTH)
+		throw std::runtime_error("Line " + std::to_string(line) + ": Macros are nested too deeply.");
+
+	static const std::string special_chars{"\\^$.|?*+()[]{}/"};
+	std::string converted;
+
+	for(std::size_t pos = 0; pos < regex.length(); ++pos)
+	{
+		char c = regex[pos];
+
+		if(c == '\\' && pos + 1 < regex.length())
+		{
+			converted += regex.substr(pos, 2);
+			++pos;
+		}
+		else if(c == '\"')
+		{
+			// quoted literal string
+			for(++pos; pos < regex.length() && regex[pos] != '\"'; ++pos)
+			{
+				char lit = regex[pos];
+				if(lit == '\\' && pos + 1 < regex.length())
+					lit = regex[++pos];
+
+				if(special_chars.find(lit) != std::string::npos)
+					converted += '\\';
+				converted += lit;
+			}
+		}
+		else if(c == '[')
+		{
+			// copy the character class
+			std::size_t depth_class = 0;
+			for(; pos < regex.length(); ++pos)
+			{
+				converted += regex[pos];
+				if(regex[pos] == '\\' && pos + 1 < regex.length())
+					converted += regex[++pos];
+				else if(regex[pos] == '[')
+					++depth_class;
+				else if(regex[pos] == ']' && --depth_class == 0)
+					break;
+			}
+		}
+		else if(c == '{' && pos + 1 < regex.length() && is_ident_char(regex[pos + 1], true))
+		{
+			// macro
+			std::size_t end = regex.find('}', pos);
+			std::string name = regex.substr(pos + 1, end - pos - 1);
+
+			auto iter = m_macros.find(name);
+			if(end == std::string::npos || iter == m_macros.end())
+				throw std::runtime_error("Line " + std::to_string(line) + ": Unknown macro \"" + name + "\".");
+
+			converted += "(?:" + ConvertRegex(iter->second, line, depth + 1) + ")";
+			pos = end;
+		}
+		else
+		{
+			converted += c;
+		}
+	}
+
+	return converted;
+}
+
+
+bool SpecImporter::SaveTokensJSON(const std::string& file) const
+{
+	std::ofstream ofstr{file};
+	if(!ofstr)
+		return false;
+
+	ofstr << "{\n";
+	ofstr << "\"infos\" : \"Token specifications imported on " << get_timestamp()
+		<< " using liblalr1 by Tobias Weber.\",\n";
+	ofstr << "\"caseless\" : " << (m_caseless ? "true" : "false") << ",\n";
+
+	ofstr << "\"tokens\" : [\n";
+	for(std::size_t idx = 0; idx < m_tokens.size(); ++idx)
+	{
+		const TokenSpec& token = m_tokens[idx];
+
+		ofstr << "\t{ \"regex\" : \"" << get_json_str(token.regex) << "\", ";
+		if(token.id)
+		{
+			ofstr << "\"id\" : " << *token.id
+				<< ", \"name\" : \"" << get_json_str(token.name) << "\" }";
+		}
+		else
+		{
+			ofstr << "\"skip\" : true }";
+		}
+
+		if(idx + 1 < m_tokens.size())
+			ofstr << ",";
+		ofstr << "\n";
+	}
+	ofstr << "]\n";
+
+	ofstr << "}" << std::endl;
+	return true;
+}
+
+} // namespace lalr1
@@ -0,0 +1,119 @@
+/**
+ * imports grammar and token specifications in the lexertl/parsertl (gram_grep) format
+ * @author Tobias Weber (orcid: 0000-0002-7230-1932)
+ * @date 15-oct-2026
+ * @license see 'LICENSE' file
+ *
+ * The specification consists of the sections:
+ *	parser directives %% grammar rules %% lexer macros %% lexer rules %%
+ * A pure lexertl token specification only has the last two sections:
+ *	lexer macros %% lexer rules %%
+ *
+ * References:
+ *	- https://github.com/BenHanson/lexertl17
+ *	- https://github.com/BenHanson/parsertl17
+ *	- https://github.com/BenHanson/gram_grep
+ */
+
+#ifndef __LALR1_SPECIMPORT_H__
+#define __LALR1_SPECIMPORT_H__
+
+#include "symbol.h"
+
+#include <iostream>
+#include <string>
+#include <vector>
+#include <unordered_map>
+#include <optional>
+
+
+namespace lalr1 {
+
+class SpecImporter;
+using SpecImporterPtr = std::shared_ptr<SpecImporter>;
+
+
+/**
+ * token regex and the corresponding terminal
+ */
+struct TokenSpec
+{
+	std::string regex{};                  // ecmascript regex
+	std::optional<t_symbol_id> id{};      // terminal id, none for skipped tokens
+	std::string name{};                   // terminal name
+	std::size_t line{};                   // line in the specification
+};
+
+
+class SpecImporter
+{
+public:
+	SpecImporter() = default;
+	~SpecImporter() = default;
+
+	bool Load(std::istream& istr);
+	bool LoadFile(const std::string& file);
+
+	// symbols of the imported grammar
+	const std::vector<NonTerminalPtr>& GetNonTerminals() const { return m_nonterms; }
+	const std::vector<TerminalPtr>& GetTerminals() const { return m_terms; }
+	NonTerminalPtr GetStartNonTerminal() const { return m_start; }
+	t_semantic_id GetAcceptingRule() const { return m_accepting_rule; }
+	bool HasGrammar() const { return m_start != nullptr; }
+
+	// token specifications in the order of the lexer rules
+	const std::vector<TokenSpec>& GetTokens() const { return m_tokens; }
+	bool GetCaseless() const { return m_caseless; }
+
+	// unsupported constructs that were skipped
+	const std::vector<std::string>& GetWarnings() const { return m_warnings; }
+
+	bool SaveTokensJSON(const std::string& file) const;
+
+	// first ids to use for named terminals and non-terminals
+	void SetTermIdBegin(t_symbol_id id) { m_term_id_begin = id; }
+	void SetNonTermIdBegin(t_symbol_id id) { m_nonterm_id_begin = id; }
+
+
+protected:
+	void Clear();
+
+	void ParseParserDirectives(const std::string& section, std::size_t line);
+	void ParseGrammar(const std::string& section, std::size_t line);
+	void ParseLexerDirectives(const std::string& section, std::size_t line);
+	void ParseLexerRules(const std::string& section, std::size_t line);
+
+	// gets a terminal by its name or char literal, creates it if needed
+	TerminalPtr GetTerminal(const std::string& name);
+
+	// converts a lexertl regex to an ecmascript one
+	std::string ConvertRegex(const std::string& regex, std::size_t line,
+		std::size_t depth = 0) const;
+
+	void Warn(std::size_t line, const std::string& msg);
+
+
+private:
+	std::vector<NonTerminalPtr> m_nonterms{};
+	std::vector<TerminalPtr> m_terms{};
+	std::unordered_map<std::string, NonTerminalPtr> m_mapNonTerms{};
+	std::unordered_map<std::string, TerminalPtr> m_mapTerms{};
+	NonTerminalPtr m_start{};             // augmented start symbol
+	std::string m_start_name{};           // from the %start directive
+	t_semantic_id m_accepting_rule{0};
+
+	std::vector<TokenSpec> m_tokens{};
+	std::unordered_map<std::string, std::string> m_macros{};
+	bool m_caseless{false};
+
+	std::vector<std::string> m_warnings{};
+
+	t_symbol_id m_term_id_begin{1000};
+	t_symbol_id m_nonterm_id_begin{10};
+	t_symbol_id m_next_term_id{1000};
+	std::size_t m_next_prec{10};
+};
+
+} // namespace lalr1
+
+#endif
@@ -23,6 +23,7 @@ namespace lalr1 {
 // special terminal symbols
 const TerminalPtr g_eps = std::make_shared<Terminal>(EPS_IDENT, "\xce\xb5", true, false);
 const TerminalPtr g_end = std::make_shared<Terminal>(END_IDENT, "\xcf\x89", false, true);
+const TerminalPtr g_errtok = std::make_shared<Terminal>(ERRTOK_IDENT, "error");
 
 
 // ----------------------------------------------------------------------------
@@ -52,6 +53,7 @@ const Symbol& Symbol::operator=(const Symbol& other)
 {
 	this->m_id = other.m_id;
 	this->m_strid = other.m_strid;
+	this->m_doc = other.m_doc;
 	this->m_iseps = other.m_iseps;
 	this->m_isend = other.m_isend;
 
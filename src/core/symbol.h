@@ -55,6 +55,9 @@ public:
 	const std::string& GetStrId() const { return m_strid; }
 	t_symbol_id GetId() const { return m_id; }
 
+	void SetDoc(const std::string& doc) { m_doc = doc; }
+	const std::string& GetDoc() const { return m_doc; }
+
 	bool IsEps() const { return m_iseps; }
 	bool IsEnd() const { return m_isend; }
 
@@ -74,6 +77,7 @@ public:
 private:
 	t_symbol_id m_id{ };      // numeric identifier of the symbol
 	std::string m_strid{ };   // string identifier of the symbol
+	std::string m_doc{ };     // description of the symbol, e.g. for hints in completions
 
 	bool m_iseps{ false };    // the symbol is the epsilon transition
 	bool m_isend{ false };    // the symbol is the end marker
@@ -298,6 +302,11 @@ private:
  */
 extern const TerminalPtr g_eps, g_end;
 
+/**
+ * yacc-style 'error' token for error productions
+ */
+extern const TerminalPtr g_errtok;
+
 
 // ----------------------------------------------------------------------------
 
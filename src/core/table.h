@@ -220,13 +220,14 @@ public:
 
 
 	/**
-	 * export table to json
+	 * export table to json, value_map replaces special values
 	 */
+	template<class t_mapped = int>
 	void SaveJSON(std::ostream& ostr, const std::string& var,
 		const std::string& row_label = "",
 		const std::string& col_label = "",
 		const std::string& elem_label = "",
-		const std::unordered_map<T, int>* value_map = nullptr) const
+		const std::unordered_map<T, t_mapped>* value_map = nullptr) const
 	{
 		ostr << "\"" << var << "\" : {\n";
 		ostr << "\t\"rows\" : " << size1() << ",\n";
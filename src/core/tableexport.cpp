@@ -36,6 +36,14 @@ namespace lalr1 {
  */
 bool TableGen::SaveParseTablesCXX(const std::string& file) const
 {
+	if(GetUseDefaultReductions())
+	{
+		std::cerr << "Warning: Default reductions are not supported by this runtime, "
+			<< "disable them to get the full reduce table." << std::endl;
+	}
+
+	CheckReservedValues();
+
 	std::ofstream ofstr{file};
 	if(!ofstr)
 		return false;
@@ -52,26 +60,40 @@ bool TableGen::SaveParseTablesCXX(const std::string& file) const
 	ofstr << "namespace _lalr1_tables {\n\n";
 
 	// save constants
-	ofstr << "const constexpr lalr1::t_index err = 0x" << std::hex << ERROR_VAL << std::dec;
+	ofstr << "const constexpr lalr1::t_index err = 0x" << std::hex << GetErrorValue() << std::dec;
 	if constexpr(std::is_unsigned_v<t_index>)
 		ofstr << "u";
 	ofstr << ";\n";
 
-	ofstr << "const constexpr lalr1::t_index acc = 0x" << std::hex << ACCEPT_VAL << std::dec;
+	ofstr << "const constexpr lalr1::t_index acc = 0x" << std::hex << GetAcceptValue() << std::dec;
 	if constexpr(std::is_unsigned_v<t_index>)
 		ofstr << "u";
 	ofstr << ";\n";
 
-	ofstr << "const constexpr lalr1::t_symbol_id eps = 0x" << std::hex << EPS_IDENT << std::dec;
+	ofstr << "const constexpr lalr1::t_symbol_id eps = 0x" << std::hex << GetEpsId() << std::dec;
+	if constexpr(std::is_unsigned_v<t_symbol_id>)
+		ofstr << "u";
+	ofstr << ";\n";
+
+	ofstr << "const constexpr lalr1::t_symbol_id end = 0x" << std::hex << GetEndId() << std::dec;
 	if constexpr(std::is_unsigned_v<t_symbol_id>)
 		ofstr << "u";
 	ofstr << ";\n";
 
-	ofstr << "const constexpr lalr1::t_symbol_id end = 0x" << std::hex << END_IDENT << std::dec;
+	ofstr << "const constexpr lalr1::t_symbol_id errtok = 0x" << std::hex << GetErrorTokenId() << std::dec;
 	if constexpr(std::is_unsigned_v<t_symbol_id>)
 		ofstr << "u";
 	ofstr << ";\n";
 
+	// table index of the error token, if the grammar has error productions
+	t_index errtok_idx = GetErrorValue();
+	if(auto iter_errtok = GetTermIndexMap().find(ERRTOK_IDENT); iter_errtok != GetTermIndexMap().end())
+		errtok_idx = iter_errtok->second;
+	ofstr << "const constexpr lalr1::t_index errtok_idx = 0x" << std::hex << errtok_idx << std::dec;
+	if constexpr(std::is_unsigned_v<t_index>)
+		ofstr << "u";
+	ofstr << ";\n";
+
 	ofstr << "const constexpr lalr1::t_index start_idx = " << GetStartingState();
 	if constexpr(std::is_unsigned_v<t_index>)
 		ofstr << "u";
@@ -117,6 +139,8 @@ bool TableGen::SaveParseTablesCXX(const std::string& file) const
 			ofstr << "eps";
 		else if(id == END_IDENT)
 			ofstr << "end";
+		else if(id == ERRTOK_IDENT)
+			ofstr << "errtok";
 		else if(GetUseOpChar() && isprintable(id))
 			ofstr << "'" << get_escaped_char(char(id)) << "'";
 		else
@@ -217,6 +241,12 @@ bool TableGen::SaveParseTablesCXX(const std::string& file) const
 	ofstr << "\t\t_lalr1_tables::err, _lalr1_tables::acc, _lalr1_tables::eps, _lalr1_tables::end, _lalr1_tables::start_idx, _lalr1_tables::acc_idx);\n";
 	ofstr << "}\n\n";
 
+	// error token getter
+	ofstr << "[[maybe_unused]] static constexpr\nstd::tuple<lalr1::t_symbol_id, lalr1::t_index>\n";
+	ofstr << "get_lalr1_error_token()\n{\n";
+	ofstr << "\treturn std::make_tuple(_lalr1_tables::errtok, _lalr1_tables::errtok_idx);\n";
+	ofstr << "}\n\n";
+
 	ofstr << "\n#endif" << std::endl;
 	return true;
 }
@@ -228,6 +258,14 @@ bool TableGen::SaveParseTablesCXX(const std::string& file) const
  */
 bool TableGen::SaveParseTablesJava(const std::string& file) const
 {
+	if(GetUseDefaultReductions())
+	{
+		std::cerr << "Warning: Default reductions are not supported by this runtime, "
+			<< "disable them to get the full reduce table." << std::endl;
+	}
+
+	CheckReservedValues();
+
 	std::ofstream ofstr{file};
 	if(!ofstr)
 		return false;
@@ -249,25 +287,31 @@ bool TableGen::SaveParseTablesJava(const std::string& file) const
 		ofstr << "\tprivate final int acc = -2;\n";
 		ofstr << "\tprivate final int end = -1;\n";
 		ofstr << "\tprivate final int eps = -2;\n";
+		ofstr << "\tprivate final int errtok = -3;\n";
 	}
 	else
 	{
-		ofstr << "\tprivate final int err = 0x" << std::hex << ERROR_VAL << std::dec;
+		ofstr << "\tprivate final int err = 0x" << std::hex << GetErrorValue() << std::dec;
 		if constexpr(std::is_unsigned_v<t_index>)
 			ofstr << "u";
 		ofstr << ";\n";
 
-		ofstr << "\tprivate final int acc = 0x" << std::hex << ACCEPT_VAL << std::dec;
+		ofstr << "\tprivate final int acc = 0x" << std::hex << GetAcceptValue() << std::dec;
 		if constexpr(std::is_unsigned_v<t_index>)
 			ofstr << "u";
 		ofstr << ";\n";
 
-		ofstr << "\tprivate final int eps = 0x" << std::hex << EPS_IDENT << std::dec;
+		ofstr << "\tprivate final int eps = 0x" << std::hex << GetEpsId() << std::dec;
+		if constexpr(std::is_unsigned_v<t_symbol_id>)
+			ofstr << "u";
+		ofstr << ";\n";
+
+		ofstr << "\tprivate final int end = 0x" << std::hex << GetEndId() << std::dec;
 		if constexpr(std::is_unsigned_v<t_symbol_id>)
 			ofstr << "u";
 		ofstr << ";\n";
 
-		ofstr << "\tprivate final int end = 0x" << std::hex << END_IDENT << std::dec;
+		ofstr << "\tprivate final int errtok = 0x" << std::hex << GetErrorTokenId() << std::dec;
 		if constexpr(std::is_unsigned_v<t_symbol_id>)
 			ofstr << "u";
 		ofstr << ";\n";
@@ -315,6 +359,8 @@ bool TableGen::SaveParseTablesJava(const std::string& file) const
 			ofstr << "eps";
 		else if(id == END_IDENT)
 			ofstr << "end";
+		else if(id == ERRTOK_IDENT)
+			ofstr << "errtok";
 		else if(GetUseOpChar() && isprintable(id))
 			ofstr << "'" << get_escaped_char(char(id)) << "'";
 		else
@@ -408,17 +454,28 @@ bool TableGen::SaveParseTablesJava(const std::string& file) const
  */
 bool TableGen::SaveParseTablesJSON(const std::string& file) const
 {
-	std::unordered_map<t_index, int> special_values
+	if(GetUseDefaultReductions())
+	{
+		std::cerr << "Warning: Default reductions are not supported by this runtime, "
+			<< "disable them to get the full reduce table." << std::endl;
+	}
+
+	// reserved values, either negative or the configured ones
+	const bool use_neg = GetUseNegativeTableValues();
+	std::unordered_map<t_index, std::string> special_values
 	{
-		{ ERROR_VAL, -1 },
-		{ ACCEPT_VAL, -2 },
+		{ ERROR_VAL, use_neg ? "-1" : std::to_string(GetErrorValue()) },
+		{ ACCEPT_VAL, use_neg ? "-2" : std::to_string(GetAcceptValue()) },
 	};
-	std::unordered_map<t_symbol_id, int> special_idents
+	std::unordered_map<t_symbol_id, std::string> special_idents
 	{
-		{ END_IDENT, -1 },
-		{ EPS_IDENT, -2 },
+		{ END_IDENT, use_neg ? "-1" : std::to_string(GetEndId()) },
+		{ EPS_IDENT, use_neg ? "-2" : std::to_string(GetEpsId()) },
+		{ ERRTOK_IDENT, use_neg ? "-3" : std::to_string(GetErrorTokenId()) },
 	};
 
+	CheckReservedValues();
+
 	std::ofstream ofstr{file};
 	if(!ofstr)
 		return false;
@@ -435,24 +492,11 @@ bool TableGen::SaveParseTablesJSON(const std::string& file) const
 	ofstr << "\n\"consts\" : {\n";
 	//ofstr << "\t\"acc_rule\" : " << m_accepting_rule << ",\n";
 
-	if(GetUseNegativeTableValues())
-	{
-		ofstr << "\t\"err\" : " << special_values[ERROR_VAL] << ",\n";
-		ofstr << "\t\"acc\" : " << special_values[ACCEPT_VAL] << ",\n";
-		ofstr << "\t\"eps\" : " << special_idents[EPS_IDENT] << ",\n";
-		ofstr << "\t\"end\" : " << special_idents[END_IDENT] << ",\n";
-	}
-	else
-	{
-		/*ofstr << "\t\"err\" : 0x" << std::hex << ERROR_VAL << std::dec << ",\n";
-		ofstr << "\t\"acc\" : 0x" << std::hex << ACCEPT_VAL << std::dec << ",\n";
-		ofstr << "\t\"eps\" : 0x" << std::hex << EPS_IDENT << std::dec << ",\n";
-		ofstr << "\t\"end\" : 0x" << std::hex << END_IDENT << std::dec << "\n";*/
-		ofstr << "\t\"err\" : " << ERROR_VAL << ",\n";
-		ofstr << "\t\"acc\" : " << ACCEPT_VAL << ",\n";
-		ofstr << "\t\"eps\" : " << EPS_IDENT << ",\n";
-		ofstr << "\t\"end\" : " << END_IDENT << ",\n";
-	}
+	ofstr << "\t\"err\" : " << special_values[ERROR_VAL] << ",\n";
+	ofstr << "\t\"acc\" : " << special_values[ACCEPT_VAL] << ",\n";
+	ofstr << "\t\"eps\" : " << special_idents[EPS_IDENT] << ",\n";
+	ofstr << "\t\"end\" : " << special_idents[END_IDENT] << ",\n";
+	ofstr << "\t\"errtok\" : " << special_idents[ERRTOK_IDENT] << ",\n";
 
 	t_index acc_rule_idx = GetTableIndex(GetAcceptingRule(), IndexTableKind::SEMANTIC);
 	ofstr << "\t\"accept\" : " << acc_rule_idx << ",\n";
@@ -553,6 +597,34 @@ bool TableGen::SaveParseTablesJSON(const std::string& file) const
 	}
 	ofstr << "],\n";
 
+	// terminal and non-terminal descriptions
+	auto write_docs = [&ofstr, &special_idents, this](const std::string& name,
+		const t_mapIdStrId& mapDocs, bool is_term)
+	{
+		ofstr << "\n\"" << name << "\" : [\n";
+		for(auto iter = mapDocs.begin(); iter != mapDocs.end(); std::advance(iter, 1))
+		{
+			const auto& [id, doc] = *iter;
+
+			ofstr << "\t[ ";
+			if(auto iter_special = special_idents.find(id); is_term && iter_special != special_idents.end())
+				ofstr << iter_special->second;
+			else if(is_term && GetUseOpChar() && isprintable(id))
+				ofstr << "\"" << get_escaped_char(char(id)) << "\"";
+			else
+				ofstr << id;
+			ofstr << ", \"" << get_escaped_str(doc) << "\" ]";
+
+			if(std::next(iter, 1) != mapDocs.end())
+				ofstr << ",";
+			ofstr << "\n";
+		}
+		ofstr << "],\n";
+	};
+
+	write_docs("term_doc", GetTermDocMap(), true);
+	write_docs("nonterm_doc", GetNontermDocMap(), false);
+
 	// semantic rule indices
 	const t_mapIdIdx& mapSemanticIdx = GetSemanticIndexMap();
 	ofstr << "\n\"semantic_idx\" : [\n";
@@ -601,6 +673,8 @@ bool TableGen::SaveParseTablesJSON(const std::string& file) const
  */
 bool TableGen::SaveParseTablesRS(const std::string& file) const
 {
+	CheckReservedValues();
+
 	std::ofstream ofstr{file};
 	if(!ofstr)
 		return false;
@@ -628,13 +702,35 @@ bool TableGen::SaveParseTablesRS(const std::string& file) const
 
 	// constants
 	t_index acc_rule_idx = GetTableIndex(GetAcceptingRule(), IndexTableKind::SEMANTIC);
-	ofstr << "pub const ERR : " << ty_idx << " = 0x" << std::hex << ERROR_VAL << std::dec << ";\n";
-	ofstr << "pub const ACC : " << ty_idx << " = 0x" << std::hex << ACCEPT_VAL << std::dec << ";\n";
-	ofstr << "pub const EPS : " << ty_sym << " = 0x" << std::hex << EPS_IDENT << std::dec << ";\n";
-	ofstr << "pub const END : " << ty_sym << " = 0x" << std::hex << END_IDENT << std::dec << ";\n";
+	ofstr << "// reserved values, distinct from the state, rule, and symbol ids\n";
+	ofstr << "pub mod sentinels\n{\n";
+	ofstr << "use super::{" << ty_idx << ", " << ty_sym << "};\n";
+	ofstr << "pub const ERR : " << ty_idx << " = 0x" << std::hex << GetErrorValue() << std::dec << ";\n";
+	ofstr << "pub const ACC : " << ty_idx << " = 0x" << std::hex << GetAcceptValue() << std::dec << ";\n";
+	ofstr << "pub const EPS : " << ty_sym << " = 0x" << std::hex << GetEpsId() << std::dec << ";\n";
+	ofstr << "pub const END : " << ty_sym << " = 0x" << std::hex << GetEndId() << std::dec << ";\n";
+	ofstr << "pub const ERRTOK : " << ty_sym << " = 0x" << std::hex << GetErrorTokenId() << std::dec << ";\n";
+	ofstr << "}\n";
+	ofstr << "use self::sentinels::{ERR, ACC, EPS, END, ERRTOK};\n\n";
+
 	ofstr << "pub const START : " << ty_idx << " = 0x" << std::hex << GetStartingState() << std::dec << ";\n";
 	ofstr << "pub const ACCEPT : " << ty_idx << " = 0x" << std::hex << acc_rule_idx << std::dec << ";\n";
 
+	// starting states of the secondary start symbols
+	const std::vector<t_start_state>& start_states = GetStartStates();
+	ofstr << "pub const START_STATES : [(" << ty_sym << ", " << ty_sym << ", " << ty_idx
+		<< "); " << start_states.size() << "] =\n[\n";
+	for(auto iter = start_states.begin(); iter != start_states.end(); std::advance(iter, 1))
+	{
+		const auto& [nonterm_id, marker_id, state] = *iter;
+		ofstr << "\t( " << nonterm_id << ", " << marker_id << ", " << state << " )";
+
+		if(std::next(iter, 1) != start_states.end())
+			ofstr << ",";
+		ofstr << "\n";
+	}
+	ofstr << "];\n";
+
 	ofstr << "\n";
 
 	// lalr(1) tables
@@ -673,6 +769,8 @@ bool TableGen::SaveParseTablesRS(const std::string& file) const
 			ofstr << "END";
 		else if(id == EPS_IDENT)
 			ofstr << "EPS";
+		else if(id == ERRTOK_IDENT)
+			ofstr << "ERRTOK";
 		else if(GetUseOpChar() && isprintable(id))
 			ofstr << "'" << get_escaped_char(char(id)) << "' as " << ty_sym;
 		else
@@ -711,6 +809,37 @@ bool TableGen::SaveParseTablesRS(const std::string& file) const
 	}
 	ofstr << "];\n";
 
+	// terminal and non-terminal descriptions
+	auto write_docs = [&ofstr, &ty_sym, this](const std::string& name,
+		const t_mapIdStrId& mapDocs, bool is_term)
+	{
+		ofstr << "pub const " << name << " : [(" << ty_sym
+			<< ", &str); " << mapDocs.size() << "] =\n[\n";
+		for(auto iter = mapDocs.begin(); iter != mapDocs.end(); std::advance(iter, 1))
+		{
+			const auto& [id, doc] = *iter;
+
+			ofstr << "\t( ";
+			if(is_term && id == END_IDENT)
+				ofstr << "END";
+			else if(is_term && id == ERRTOK_IDENT)
+				ofstr << "ERRTOK";
+			else if(is_term && GetUseOpChar() && isprintable(id))
+				ofstr << "'" << get_escaped_char(char(id)) << "' as " << ty_sym;
+			else
+				ofstr << id;
+			ofstr << ", \"" << get_escaped_str(doc) << "\" )";
+
+			if(std::next(iter, 1) != mapDocs.end())
+				ofstr << ",";
+			ofstr << "\n";
+		}
+		ofstr << "];\n";
+	};
+
+	write_docs("TERM_DOC", GetTermDocMap(), true);
+	write_docs("NONTERM_DOC", GetNontermDocMap(), false);
+
 	// semantic rule indices
 	const t_mapIdIdx& mapSemanticIdx = GetSemanticIndexMap();
 	ofstr << "pub const SEMANTIC_IDX : [(" << ty_sem << ", " << ty_idx
@@ -751,6 +880,46 @@ bool TableGen::SaveParseTablesRS(const std::string& file) const
 	}
 	ofstr << "];\n";
 
+	// rule index to reduce without looking at the lookahead
+	const auto& defaultReductions = GetDefaultReductions();
+	ofstr << "pub const DEFAULT_REDUCE : [" << ty_idx << "; "
+		<< defaultReductions.size() << "] = [ ";
+	for(auto iter = defaultReductions.begin(); iter != defaultReductions.end(); std::advance(iter, 1))
+	{
+		if(*iter == ERROR_VAL)
+			ofstr << "ERR";
+		else
+			ofstr << *iter;
+		if(std::next(iter, 1) != defaultReductions.end())
+			ofstr << ",";
+		ofstr << " ";
+	}
+	ofstr << "];\n";
+
+	// kernel items of the states
+	const std::vector<std::string>& stateItems = GetStateItems();
+	ofstr << "pub const STATE_ITEMS : [&str; " << stateItems.size() << "] =\n[\n";
+	for(auto iter = stateItems.begin(); iter != stateItems.end(); std::advance(iter, 1))
+	{
+		ofstr << "\t\"" << get_escaped_str(*iter) << "\"";
+		if(std::next(iter, 1) != stateItems.end())
+			ofstr << ",";
+		ofstr << "\n";
+	}
+	ofstr << "];\n";
+
+	// rule descriptions
+	const std::vector<std::string>& ruleStrs = GetRuleStrings();
+	ofstr << "pub const RULE_STRS : [&str; " << ruleStrs.size() << "] =\n[\n";
+	for(auto iter = ruleStrs.begin(); iter != ruleStrs.end(); std::advance(iter, 1))
+	{
+		ofstr << "\t\"" << get_escaped_str(*iter) << "\"";
+		if(std::next(iter, 1) != ruleStrs.end())
+			ofstr << ",";
+		ofstr << "\n";
+	}
+	ofstr << "];\n";
+
 	ofstr << "}\n";  // end of module
 	return true;
 }
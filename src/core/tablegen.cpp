@@ -19,6 +19,7 @@
 #include <sstream>
 #include <algorithm>
 #include <unordered_map>
+#include <map>
 #include <optional>
 #include <type_traits>
 
@@ -38,6 +39,7 @@ void TableGen::CreateTableIndices()
 	// generate table indices for terminals
 	m_mapTermIdx.clear();
 	m_mapTermStrIds.clear();
+	m_mapTermDocs.clear();
 	t_index curTermIdx = 0;
 
 	const Collection::t_closures& closures = m_collection->GetClosures();
@@ -57,6 +59,10 @@ void TableGen::CreateTableIndices()
 		// nonterminal string id map
 		const std::string& sym_strid = symTrans->GetStrId();
 		m_mapTermStrIds.try_emplace(symTrans->GetId(), sym_strid);
+
+		// terminal documentation map
+		if(const std::string& sym_doc = symTrans->GetDoc(); sym_doc != "")
+			m_mapTermDocs.try_emplace(symTrans->GetId(), sym_doc);
 	}
 
 	// add end symbol
@@ -67,6 +73,7 @@ void TableGen::CreateTableIndices()
 	m_mapNonTermIdx.clear();
 	m_mapSemanticIdx.clear();
 	m_mapNonTermStrIds.clear();
+	m_mapNonTermDocs.clear();
 	t_index curNonTermIdx = 0;
 	t_index curSemanticIdx = 0;
 
@@ -87,6 +94,10 @@ void TableGen::CreateTableIndices()
 			const std::string& sym_strid = elem->GetLhs()->GetStrId();
 			m_mapNonTermStrIds.try_emplace(sym_id, sym_strid);
 
+			// nonterminal documentation map
+			if(const std::string& sym_doc = elem->GetLhs()->GetDoc(); sym_doc != "")
+				m_mapNonTermDocs.try_emplace(sym_id, sym_doc);
+
 			if(std::optional<t_semantic_id> semantic_id =
 				elem->GetSemanticRule(); semantic_id)
 			{
@@ -244,15 +255,58 @@ bool TableGen::CreateParseTables()
 			set_tab_elem(m_ruleLhsIdx, rule_idx, lhs_idx, 0);
 			// TODO: also save lhs id to table to check against semantic rule's return type
 
+			// rule description, e.g. "expr -> expr '+' term"
+			if(m_ruleStrs.size() <= rule_idx)
+				m_ruleStrs.resize(rule_idx+1);
+			std::ostringstream ostrRule;
+			ostrRule << elem->GetLhs()->GetStrId() << " ->";
+			const WordPtr& rhs = elem->GetRhs();
+			for(t_index rhs_idx=0; rhs_idx<rhs->size(); ++rhs_idx)
+			{
+				const SymbolPtr& sym = (*rhs)[rhs_idx];
+				if(!sym->IsEps())
+					ostrRule << " " << sym->GetStrId();
+			}
+			m_ruleStrs[rule_idx] = ostrRule.str();
+
 			auto& _reduce_row = action_reduce[closure->GetId()];
 			for(const TerminalPtr& la : elem->GetLookaheads())
 			{
 				const t_index laIdx = GetTableIndex(la->GetId(), IndexTableKind::TERMINAL);
 
 				// in extended grammar, first production (rule 0) is of the form start -> ...
-				if(*rule_id == GetAcceptingRule())
+				if(IsAcceptingRule(*rule_id))
 					rule_idx = ACCEPT_VAL;
 
+				// reduce/reduce conflict?
+				if(t_index other_idx = _reduce_row[laIdx]; other_idx != ERROR_VAL && other_idx != rule_idx)
+				{
+					if(std::optional<t_semantic_id> solution =
+						m_collection->SolveReduceConflict(closure, la); solution)
+					{
+						t_index solution_idx = IsAcceptingRule(*solution) ? ACCEPT_VAL
+							: GetTableIndex(*solution, IndexTableKind::SEMANTIC);
+						set_tab_elem(_reduce_row, laIdx, solution_idx);
+						continue;
+					}
+					else if(m_collection->HasConflictSolver())
+					{
+						ok = false;
+
+						std::ostringstream ostrErr;
+						ostrErr << "Reduce/reduce conflict detected"
+							<< " for state " << closure->GetId()
+							<< " and look-ahead terminal " << la->GetStrId()
+							<< " (can reduce using rule " << other_idx
+							<< " or rule " << rule_idx << ").\n";
+
+						if(GetStopOnConflicts())
+							throw std::runtime_error(ostrErr.str());
+						else
+							std::cerr << ostrErr.str() << std::endl;
+					}
+				}
+
 				// semantic rule number -> reduce table
 				set_tab_elem(_reduce_row, laIdx, rule_idx);
 			}
@@ -315,7 +369,9 @@ bool TableGen::CreateParseTables()
 				if(!lookbacks)
 					lookbacks = m_collection->GetLookbackTerminals(closure);
 
-				if(!m_collection->SolveConflict(sym_at_cursor, *lookbacks, &shiftEntry, &reduceEntry))
+				std::optional<t_semantic_id> reduce_rule;
+				if(!m_collection->SolveConflict(closure, sym_at_cursor, *lookbacks,
+					&shiftEntry, &reduceEntry, &reduce_rule))
 				{
 					ok = false;
 
@@ -351,6 +407,13 @@ bool TableGen::CreateParseTables()
 				}
 				else  // solution found
 				{
+					// the conflict solver chose a specific rule to reduce
+					if(reduce_rule)
+					{
+						reduceEntry = IsAcceptingRule(*reduce_rule) ? ACCEPT_VAL
+							: GetTableIndex(*reduce_rule, IndexTableKind::SEMANTIC);
+					}
+
 					// also apply conflict solution to partial matches
 					/*if(shiftEntry == ERROR_VAL)
 					{
@@ -364,6 +427,343 @@ bool TableGen::CreateParseTables()
 		++state;
 	}
 
+	if(!CreateStartStates())
+		ok = false;
+	CreateStateItems();
+
+	// resolved conflicts can leave states without incoming transitions
+	RemoveUnreachableStates();
+	if(GetMergeEquivalentStates())
+		MergeEquivalentStates();
+
+	m_defaultReductions.clear();
+	m_defaultReductions.resize(m_tabActionReduce.size1(), ERROR_VAL);
+	if(GetUseDefaultReductions())
+		CreateDefaultReductions();
+
+	return ok;
+}
+
+
+/**
+ * find the states that don't shift and always reduce the same rule,
+ * these don't need to know the lookahead, so their reduce table rows can be removed
+ */
+void TableGen::CreateDefaultReductions()
+{
+	for(t_index state=0; state<m_tabActionReduce.size1(); ++state)
+	{
+		std::optional<t_index> rule_idx;
+		bool has_default = true;
+
+		for(t_index termidx=0; termidx<m_tabActionReduce.size2(); ++termidx)
+		{
+			if(m_tabActionShift(state, termidx) != ERROR_VAL)
+			{
+				has_default = false;
+				break;
+			}
+
+			t_index reduce = m_tabActionReduce(state, termidx);
+			if(reduce == ERROR_VAL)
+				continue;
+
+			// the accepting rule needs to see the end terminal
+			if(reduce == ACCEPT_VAL || (rule_idx && *rule_idx != reduce))
+			{
+				has_default = false;
+				break;
+			}
+
+			rule_idx = reduce;
+		}
+
+		if(!has_default || !rule_idx)
+			continue;
+
+		m_defaultReductions[state] = *rule_idx;
+		for(t_index termidx=0; termidx<m_tabActionReduce.size2(); ++termidx)
+			m_tabActionReduce(state, termidx) = ERROR_VAL;
+	}
+}
+
+
+/**
+ * remove the states that can't be reached from the starting state
+ */
+void TableGen::RemoveUnreachableStates()
+{
+	const std::size_t numStates = m_tabActionShift.size1();
+	if(GetStartingState() >= numStates)
+		return;
+
+	std::vector<bool> reachable(numStates, false);
+	std::vector<t_index> stack{ GetStartingState() };
+	reachable[GetStartingState()] = true;
+
+	while(stack.size())
+	{
+		t_index state = stack.back();
+		stack.pop_back();
+
+		for(const t_table* tab : { &m_tabActionShift, &m_tabJump })
+		{
+			for(t_index idx=0; idx<tab->size2(); ++idx)
+			{
+				t_index state_to = (*tab)(state, idx);
+				if(state_to == ERROR_VAL || state_to == ACCEPT_VAL || reachable[state_to])
+					continue;
+
+				reachable[state_to] = true;
+				stack.push_back(state_to);
+			}
+		}
+	}
+
+	// keep the order of the reachable states
+	std::vector<t_index> new_states(numStates, ERROR_VAL);
+	std::size_t num_new_states = 0;
+	for(t_index state=0; state<numStates; ++state)
+	{
+		if(reachable[state])
+			new_states[state] = num_new_states++;
+	}
+
+	if(num_new_states != numStates)
+		RenumberStates(new_states, num_new_states);
+}
+
+
+/**
+ * merge the states whose table entries are equal,
+ * this is done by refining the partition of states
+ * until all states in a group transition to the same groups
+ */
+void TableGen::MergeEquivalentStates()
+{
+	const std::size_t numStates = m_tabActionShift.size1();
+
+	// append a table row to a state's signature
+	auto add_row = [](std::vector<t_index>& sig, const t_table& tab, t_index state,
+		const std::vector<t_index>* groups = nullptr)
+	{
+		for(t_index idx=0; idx<tab.size2(); ++idx)
+		{
+			t_index val = tab(state, idx);
+			if(groups && val != ERROR_VAL && val != ACCEPT_VAL)
+				val = (*groups)[val];
+			sig.push_back(val);
+		}
+	};
+
+	// initially, the groups are given by the entries not referring to other states
+	std::vector<t_index> groups(numStates, 0);
+	std::size_t num_groups = 1;
+
+	while(true)
+	{
+		std::map<std::vector<t_index>, t_index> signatures;
+		std::vector<t_index> new_groups(numStates, ERROR_VAL);
+
+		for(t_index state=0; state<numStates; ++state)
+		{
+			std::vector<t_index> sig{ groups[state] };
+			add_row(sig, m_tabActionReduce, state);
+			add_row(sig, m_tabPartialRuleTerm, state);
+			add_row(sig, m_tabPartialMatchLenTerm, state);
+			add_row(sig, m_tabPartialRuleNonterm, state);
+			add_row(sig, m_tabPartialMatchLenNonterm, state);
+			add_row(sig, m_tabPartialNontermLhsId, state);
+			add_row(sig, m_tabActionShift, state, &groups);
+			add_row(sig, m_tabJump, state, &groups);
+
+			auto [iter, inserted] = signatures.emplace(
+				std::make_pair(std::move(sig), signatures.size()));
+			new_groups[state] = iter->second;
+		}
+
+		groups = std::move(new_groups);
+		if(signatures.size() == num_groups)
+			break;
+		num_groups = signatures.size();
+	}
+
+	if(num_groups != numStates)
+		RenumberStates(groups, num_groups);
+}
+
+
+/**
+ * map the states to new indices and rebuild the tables,
+ * several states can be mapped to the same new index if they are equivalent,
+ * states mapped to ERROR_VAL are removed
+ */
+void TableGen::RenumberStates(const std::vector<t_index>& new_states, std::size_t num_new_states)
+{
+	// rebuild a table using the first state mapped to each new index
+	auto renumber_table = [&new_states, num_new_states](const t_table& tab,
+		bool has_states, t_index fill) -> t_table
+	{
+		std::vector<std::vector<t_index>> rows(num_new_states);
+		for(t_index state=0; state<tab.size1(); ++state)
+		{
+			t_index new_state = new_states[state];
+			if(new_state == ERROR_VAL || rows[new_state].size())
+				continue;
+
+			std::vector<t_index>& row = rows[new_state];
+			row.reserve(tab.size2());
+			for(t_index idx=0; idx<tab.size2(); ++idx)
+			{
+				t_index val = tab(state, idx);
+				if(has_states && val != ERROR_VAL && val != ACCEPT_VAL)
+					val = new_states[val];
+				row.push_back(val);
+			}
+		}
+
+		return t_table{rows, ERROR_VAL, ACCEPT_VAL, fill, num_new_states, tab.size2()};
+	};
+
+	m_tabActionShift = renumber_table(m_tabActionShift, true, ERROR_VAL);
+	m_tabActionReduce = renumber_table(m_tabActionReduce, false, ERROR_VAL);
+	m_tabJump = renumber_table(m_tabJump, true, ERROR_VAL);
+
+	m_tabPartialRuleTerm = renumber_table(m_tabPartialRuleTerm, false, ERROR_VAL);
+	m_tabPartialMatchLenTerm = renumber_table(m_tabPartialMatchLenTerm, false, 0);
+	m_tabPartialRuleNonterm = renumber_table(m_tabPartialRuleNonterm, false, ERROR_VAL);
+	m_tabPartialMatchLenNonterm = renumber_table(m_tabPartialMatchLenNonterm, false, 0);
+	m_tabPartialNontermLhsId = renumber_table(m_tabPartialNontermLhsId, false, ERROR_VAL);
+
+	// merged states get the items of all their original states
+	std::vector<std::string> state_items(num_new_states);
+	for(t_index state=0; state<m_stateItems.size() && state<new_states.size(); ++state)
+	{
+		t_index new_state = new_states[state];
+		if(new_state == ERROR_VAL || m_stateItems[state].empty())
+			continue;
+
+		std::string& items = state_items[new_state];
+		if(items.size())
+			items += "\n";
+		items += m_stateItems[state];
+	}
+	m_stateItems = std::move(state_items);
+
+	SetStartingState(new_states[GetStartingState()]);
+	for(t_start_state& start_state : m_startStates)
+		std::get<2>(start_state) = new_states[std::get<2>(start_state)];
+}
+
+
+/**
+ * describe the kernel items of the states, these are the items whose cursor
+ * has been moved by a transition, and the start rules in the starting state
+ */
+void TableGen::CreateStateItems()
+{
+	const Collection::t_closures& closures = m_collection->GetClosures();
+
+	m_stateItems.clear();
+	m_stateItems.resize(m_tabActionShift.size1());
+
+	for(const ClosurePtr& closure : closures)
+	{
+		const t_state_id state = closure->GetId();
+		if(state >= m_stateItems.size())
+			continue;
+
+		std::ostringstream ostr;
+		for(const ElementPtr& elem : closure->GetElements())
+		{
+			std::optional<t_semantic_id> rule_id = elem->GetSemanticRule();
+			bool is_start = state == GetStartingState() && rule_id &&
+				(IsAcceptingRule(*rule_id) || std::find(GetStartRules().begin(),
+					GetStartRules().end(), *rule_id) != GetStartRules().end());
+			if(elem->GetCursor() == 0 && !is_start)
+				continue;
+
+			if(ostr.tellp() > 0)
+				ostr << "\n";
+
+			// write the item without colours, e.g. "expr -> expr . '+' term"
+			const WordPtr& rhs = elem->GetRhs();
+			ostr << elem->GetLhs()->GetStrId() << " ->";
+			for(t_index rhs_idx=0; rhs_idx<rhs->size(); ++rhs_idx)
+			{
+				if(elem->GetCursor() == rhs_idx)
+					ostr << " .";
+
+				const SymbolPtr& sym = (*rhs)[rhs_idx];
+				if(!sym->IsEps())
+					ostr << " " << sym->GetStrId();
+			}
+			if(elem->GetCursor() >= rhs->size())
+				ostr << " .";
+		}
+
+		m_stateItems[state] = ostr.str();
+	}
+}
+
+
+/**
+ * find the states following the marker terminals of the secondary start rules,
+ * parsing a secondary start symbol begins there
+ */
+bool TableGen::CreateStartStates()
+{
+	m_startStates.clear();
+	if(GetStartRules().empty())
+		return true;
+
+	const Collection::t_closures& closures = m_collection->GetClosures();
+	auto iter_start = std::find_if(closures.begin(), closures.end(),
+		[this](const ClosurePtr& closure) -> bool
+	{
+		return closure->GetId() == GetStartingState();
+	});
+	if(iter_start == closures.end())
+		return false;
+
+	bool ok = true;
+	for(t_semantic_id rule_id : GetStartRules())
+	{
+		bool found = false;
+
+		for(const ElementPtr& elem : (*iter_start)->GetElements())
+		{
+			if(elem->GetCursor() != 0 || elem->GetSemanticRule() != rule_id)
+				continue;
+
+			// rule has to be of the form start -> marker nonterminal
+			const WordPtr& rhs = elem->GetRhs();
+			if(rhs->NumSymbols(false) != 2 || !rhs->GetSymbol(0)->IsTerminal()
+				|| rhs->GetSymbol(1)->IsTerminal())
+			{
+				break;
+			}
+
+			const t_symbol_id marker_id = rhs->GetSymbol(0)->GetId();
+			const t_index marker_idx = GetTableIndex(marker_id, IndexTableKind::TERMINAL);
+			const t_index state = m_tabActionShift(GetStartingState(), marker_idx);
+			if(state == ERROR_VAL)
+				break;
+
+			m_startStates.emplace_back(std::make_tuple(
+				rhs->GetSymbol(1)->GetId(), marker_id, state));
+			found = true;
+			break;
+		}
+
+		if(!found)
+		{
+			std::cerr << "Error: Rule " << rule_id << " is no valid start rule"
+				<< " of the form start -> marker nonterminal." << std::endl;
+			ok = false;
+		}
+	}
+
 	return ok;
 }
 
@@ -417,6 +817,50 @@ t_index TableGen::GetTableIndex(t_symbol_id id, IndexTableKind table_kind) const
 }
 
 
+/**
+ * checks that the reserved table values and symbol ids
+ * don't collide with any of the grammar's states, rules, or symbols
+ */
+void TableGen::CheckReservedValues() const
+{
+	const t_index err = GetErrorValue();
+	const t_index acc = GetAcceptValue();
+	const t_symbol_id end = GetEndId();
+	const t_symbol_id eps = GetEpsId();
+	const t_symbol_id errtok = GetErrorTokenId();
+
+	std::ostringstream ostrErr;
+
+	if(err == acc)
+		ostrErr << "Error and accept values are both " << err << ". ";
+	if(end == eps || end == errtok || eps == errtok)
+		ostrErr << "End, epsilon, and error token ids have to be distinct. ";
+
+	// table entries are states or rule indices
+	const std::size_t num_entries = std::max(m_tabActionShift.size1(), m_numRhsSymsPerRule.size());
+	for(t_index val : { err, acc })
+	{
+		if(val < num_entries)
+			ostrErr << "Reserved table value " << val << " is a state or rule index. ";
+
+		// the partial match tables contain non-terminal ids
+		if(GetGenPartialMatches() && m_mapNonTermIdx.contains(val))
+			ostrErr << "Reserved table value " << val << " is a non-terminal id. ";
+	}
+
+	for(const auto& [id, idx] : m_mapTermIdx)
+	{
+		if(id == END_IDENT || id == EPS_IDENT || id == ERRTOK_IDENT)
+			continue;
+		if(id == end || id == eps || id == errtok)
+			ostrErr << "Reserved id " << id << " is also used by a terminal. ";
+	}
+
+	if(ostrErr.str().size())
+		throw std::runtime_error(ostrErr.str());
+}
+
+
 bool TableGen::GetStopOnConflicts() const
 {
 	return m_collection->GetStopOnConflicts();
@@ -20,6 +20,8 @@
 #include "collection.h"
 #include "genoptions.h"
 
+#include <tuple>
+
 
 namespace lalr1 {
 
@@ -45,6 +47,8 @@ public:
 
 	const t_mapIdStrId& GetNontermStringIdMap() const { return m_mapNonTermStrIds; }
 	const t_mapIdStrId& GetTermStringIdMap() const { return m_mapTermStrIds; }
+	const t_mapIdStrId& GetNontermDocMap() const { return m_mapNonTermDocs; }
+	const t_mapIdStrId& GetTermDocMap() const { return m_mapTermDocs; }
 
 	const t_table& GetPartialsRuleTerm() const { return m_tabPartialRuleTerm; }
 	const t_table& GetPartialsRuleNonterm() const { return m_tabPartialRuleNonterm; }
@@ -58,9 +62,23 @@ public:
 
 	const std::vector<std::size_t>& GetNumRhsSymbolsPerRule() const { return m_numRhsSymsPerRule; }
 	const std::vector<t_index>& GetRuleLhsIndices() const { return m_ruleLhsIdx; }
+	const std::vector<t_index>& GetDefaultReductions() const { return m_defaultReductions; }
+
+	// kernel items of the states, e.g. for comments in generated code
+	const std::vector<std::string>& GetStateItems() const { return m_stateItems; }
+
+	// descriptions of the rules, e.g. for the semantic function stubs
+	const std::vector<std::string>& GetRuleStrings() const { return m_ruleStrs; }
+
+	// nonterminal, marker terminal and starting state of the secondary start symbols
+	using t_start_state = std::tuple<t_symbol_id, t_symbol_id, t_index>;
+	const std::vector<t_start_state>& GetStartStates() const { return m_startStates; }
 
 	bool GetStopOnConflicts() const;
 
+	// throws if the reserved values collide with the grammar's indices or ids
+	void CheckReservedValues() const;
+
 
 	// save the parsing tables to C++ code
 	bool SaveParseTablesCXX(const std::string& file) const;
@@ -79,6 +97,13 @@ public:
 protected:
 	void CreateTableIndices();
 
+	void RemoveUnreachableStates();
+	void MergeEquivalentStates();
+	void RenumberStates(const std::vector<t_index>& new_states, std::size_t num_new_states);
+	void CreateDefaultReductions();
+	void CreateStateItems();
+	bool CreateStartStates();
+
 
 private:
 	const CollectionPtr m_collection{};         // collection of LALR(1) closures
@@ -89,6 +114,8 @@ private:
 
 	t_mapIdStrId m_mapNonTermStrIds{};          // maps the non-terminal ids to the respective string identifiers
 	t_mapIdStrId m_mapTermStrIds{};             // maps the terminal ids to the respective string identifiers
+	t_mapIdStrId m_mapNonTermDocs{};            // maps the non-terminal ids to their descriptions, if available
+	t_mapIdStrId m_mapTermDocs{};               // maps the terminal ids to their descriptions, if available
 
 	t_table m_tabActionShift{};                 // lalr(1) tables
 	t_table m_tabActionReduce{};
@@ -102,6 +129,10 @@ private:
 
 	std::vector<std::size_t> m_numRhsSymsPerRule{}; // number of symbols on rhs of a production rule
 	std::vector<t_index> m_ruleLhsIdx{};            // nonterminal index of the rule's result type
+	std::vector<t_index> m_defaultReductions{};     // rule index to reduce in a state regardless of the lookahead
+	std::vector<t_start_state> m_startStates{};     // states following the markers of the secondary start rules
+	std::vector<std::string> m_stateItems{};        // descriptions of the states' kernel items
+	std::vector<std::string> m_ruleStrs{};          // descriptions of the rules
 };
 
 } // namespace lalr1
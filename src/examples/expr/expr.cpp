@@ -76,6 +76,7 @@ static void lalr1_run_parser()
 			partials_rules_nonterm, partials_matchlen_nonterm]
 				= get_lalr1_partials_tables();
 		auto [err_idx, acc_idx, eps_id, end_id, start_idx, acc_rule_idx] = get_lalr1_constants();
+		auto [errtok_id, errtok_idx] = get_lalr1_error_token();
 
 		Parser parser;
 		parser.SetShiftTable(shift_tab);
@@ -89,6 +90,9 @@ static void lalr1_run_parser()
 		parser.SetPartialsRulesNonTerm(partials_rules_nonterm);
 		parser.SetPartialsMatchLenNonTerm(partials_matchlen_nonterm);
 		parser.SetEndId(end_id);
+		parser.SetErrorValue(err_idx);
+		parser.SetAcceptValue(acc_idx);
+		parser.SetErrorToken(errtok_id, errtok_idx);
 		parser.SetStartingState(start_idx);
 		parser.SetAcceptingRule(acc_rule_idx);
 #endif
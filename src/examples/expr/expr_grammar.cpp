@@ -35,6 +35,12 @@ void ExprGrammar::CreateGrammar(bool add_rules, bool add_semantics)
 	sym_int = std::make_shared<Terminal>(static_cast<t_symbol_id>(Token::INT), "integer");
 	ident = std::make_shared<Terminal>(static_cast<t_symbol_id>(Token::IDENT), "ident");
 
+	// descriptions, e.g. for hints in completions
+	expr->SetDoc("expression, e.g. \"2*(3 + x)\"");
+	sym_real->SetDoc("real number, e.g. \"1.5e3\"");
+	sym_int->SetDoc("integer number, e.g. \"123\"");
+	ident->SetDoc("variable or function name, e.g. \"pi\" or \"sin\"");
+
 	// precedences and associativities
 	//op_assign->SetPrecedence(10, 'r');
 	op_plus->SetPrecedence(70, 'l');
@@ -0,0 +1 @@
+../../modules/lalr1_rs/diagnostics.rs
\ No newline at end of file
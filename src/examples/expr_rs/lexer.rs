@@ -13,10 +13,29 @@ use idents::*;
 use common::*;
 
 
-fn match_int(str : &str, base : u32) -> bool
+/*
+ * match an integer with an optional base prefix (0x, 0o or 0b),
+ * a bare prefix matches too, so that the literal can still grow
+ */
+fn match_int(str : &str) -> bool
 {
-	for ch in str.chars()
+	let lower : String = str.chars().take(2).collect::<String>().to_lowercase();
+	let (digits, base) : (&str, u32) = match lower.as_str()
+	{
+		"0x" => (&str[2..], 16),
+		"0o" => (&str[2..], 8),
+		"0b" => (&str[2..], 2),
+		_ => (str, 10),
+	};
+
+	for (idx, ch) in digits.chars().enumerate()
 	{
+		// digit separator
+		if ch == '_' && idx > 0
+		{
+			continue;
+		}
+
 		if !ch.is_digit(base)
 		{
 			return false;
@@ -35,7 +54,7 @@ fn match_real(str : &str, base : u32) -> bool
 
 	for (idx, ch) in str.chars().enumerate()
 	{
-		if ch.is_digit(base)
+		if ch.is_digit(base) || (ch == '_' && idx > 0)
 		{
 			continue;
 		}
@@ -124,39 +143,25 @@ fn match_str(str : &str) -> bool
  */
 fn get_match(str : &str) -> Option<Symbol>
 {
-	// match integer
-	if match_int(str, 10)
+	// match integer, the value is set once the longest match is known
+	if match_int(str)
 	{
-		let lval = str.parse::<TLVal>().unwrap();
-		return Some(Symbol{
-			is_term : true,
-			id : TOK_INT_ID,
-			val : lval,
-			strval : Some(str.to_string())
-		});
+		return Some(Symbol { is_term : true, id : TOK_INT_ID,
+			strval : Some(str.to_string()), ..Default::default() });
 	}
 
 	// match real
 	else if match_real(str, 10)
 	{
-		let lval = str.parse::<TLVal>().unwrap();
-		return Some(Symbol{
-			is_term : true,
-			id : TOK_REAL_ID,
-			val : lval,
-			strval : Some(str.to_string())
-		});
+		return Some(Symbol { is_term : true, id : TOK_REAL_ID,
+			strval : Some(str.to_string()), ..Default::default() });
 	}
 
 	// match identifier
 	else if match_ident(str)
 	{
-		return Some(Symbol{
-			is_term : true,
-			id : TOK_IDENT_ID,
-			val : 0 as TLVal,
-			strval : Some(str.to_string())
-		});
+		return Some(Symbol { is_term : true, id : TOK_IDENT_ID,
+			strval : Some(str.to_string()), ..Default::default() });
 	}
 
 	// match single-char operators
@@ -166,12 +171,8 @@ fn get_match(str : &str) -> Option<Symbol>
 		if ch=='+' || ch=='-' || ch=='*' || ch=='/' || ch=='%' || ch=='^'
 			|| ch=='(' || ch==')' || ch==','
 		{
-			return Some(Symbol{
-				is_term : true,
-				id : ch as TSymbolId,
-				val : 0 as TLVal,
-				strval : Some(str.to_string())
-			});
+			return Some(Symbol { is_term : true, id : ch as TSymbolId,
+				strval : Some(str.to_string()), ..Default::default() });
 		}
 		else
 		{
@@ -220,14 +221,40 @@ fn get_longest_match(str : &str) -> (Option<Symbol>, usize)
 
 
 /*
- * get the longest matching string and its end index
+ * convert the string of a number token to its value
+ */
+fn set_number_value(sym : &mut Symbol) -> Result<(), NumberError>
+{
+	let str : &str = sym.strval.as_ref().unwrap();
+
+	if sym.id == TOK_INT_ID
+	{
+		let val : u64 = parse_int_literal(str)?;
+		sym.val = int_to_real(val, (0, str.len()))?;
+	}
+	else if sym.id == TOK_REAL_ID
+	{
+		sym.val = parse_real_literal(str)?;
+	}
+
+	Ok(())
+}
+
+
+/*
+ * split the input into tokens, an invalid number literal fails the whole input,
+ * its error span is the position in the input
  */
-pub fn get_all_matches(str : &str) -> Vec<Symbol>
+pub fn get_all_matches(str : &str) -> Result<Vec<Symbol>, NumberError>
 {
 	let len : usize = str.len();
 	let mut substr = str.trim().to_string();
 	let mut syms : Vec<Symbol> = Vec::<Symbol>::new();
 
+	// offsets of the trimmed string in the input
+	let lead : usize = str.len() - str.trim_start().len();
+	let trimmed_len : usize = substr.len();
+
 	loop
 	{
 		let (sym, idx) = get_longest_match(&substr);
@@ -238,7 +265,13 @@ pub fn get_all_matches(str : &str) -> Vec<Symbol>
 			break;
 		}
 
-		syms.push(sym.unwrap());
+		let mut sym = sym.unwrap();
+		let pos : usize = lead + trimmed_len - substr.len();
+		sym.span = Some((pos, pos + idx.min(substr.len())));
+
+		set_number_value(&mut sym).map_err(|err| err.offset(pos))?;
+
+		syms.push(sym);
 
 		if idx >= len
 		{
@@ -249,5 +282,5 @@ pub fn get_all_matches(str : &str) -> Vec<Symbol>
 		substr = substr[idx..].trim().to_string();
 	}
 
-	syms
+	Ok(syms)
 }
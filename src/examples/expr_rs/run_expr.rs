@@ -7,9 +7,18 @@
  */
 
 use std::convert::TryInto;
-use std::io::stdin;
+use std::io::{stdin, stdout, Write};
 use std::f64::consts::PI;
 
+#[cfg(feature = "log")]
+extern crate log;
+#[cfg(feature = "codespan")]
+extern crate codespan_reporting;
+#[cfg(feature = "smallvec")]
+extern crate smallvec;
+#[cfg(feature = "serde")]
+extern crate serde;
+
 mod expr;
 mod idents;
 mod types;
@@ -17,43 +26,47 @@ mod common;
 mod lexer;
 mod parser;
 //mod generated_parser;
+#[cfg(feature = "codespan")]
+mod diagnostics;
 
-use common::{Parsable, Symbol, TSemantics};
+use common::{Parsable, Symbol, TArgs, TSemantics, SemanticError, DebugLevel, PartialsPolicy, ParseError, ParseTrace, NumberError, get_arg};
 use types::*;
 use idents::*;
 use parser::Parser;
 //use generated_parser::Parser;
 
-const SET_DEBUG : bool = false;
+const SET_DEBUG : DebugLevel = DebugLevel::Off;
 const SET_PARTIALS : bool = false;
+const SET_PARTIALS_POLICY : PartialsPolicy = PartialsPolicy::Both;
+const SET_PROFILING : bool = false;
+const SET_STATISTICS : bool = false;
+const SET_TRACE : bool = false;
+const SET_RECOVERY : bool = false;
+const SET_MAX_SKIPPED : Option<usize> = Some(8);
 
 
-fn get_symbol(args : Vec<Symbol>, _done : bool, _retval : TLVal) -> TLVal
+fn get_symbol(args : TArgs, _done : bool, _retval : TLVal) -> Result<TLVal, SemanticError>
 {
 	if !_done
 	{
-		return 0 as TLVal;
+		return Ok(0 as TLVal);
 	}
 
 	if args[0].strval.is_none()
 	{
-		return 0 as TLVal;
+		return Ok(0 as TLVal);
 	}
 
 	let ident : &str = &args[0].strval.as_ref().unwrap();
 	match ident
 	{
-		"pi" => { PI as TLVal },
-		_ =>
-		{
-			println!("Identifier \"{}\" is unknown.", ident);
-			0 as TLVal
-		}
+		"pi" => { Ok(PI as TLVal) },
+		_ => Err(SemanticError::new(&format!("Identifier \"{}\" is unknown", ident))),
 	}
 }
 
 
-fn call_func1(args : Vec<Symbol>, _done : bool, _retval : TLVal) -> TLVal
+fn call_func1(args : TArgs, _done : bool, _retval : TLVal) -> TLVal
 {
 	if !_done
 	{
@@ -65,7 +78,7 @@ fn call_func1(args : Vec<Symbol>, _done : bool, _retval : TLVal) -> TLVal
 		return 0 as TLVal;
 	}
 
-	let arg1 : TLVal = args[2].val;
+	let arg1 : TLVal = get_arg(&args, SEM_CALL1_ID, 2).val;
 
 	let ident : &str = &args[0].strval.as_ref().unwrap();
 	match ident
@@ -83,7 +96,7 @@ fn call_func1(args : Vec<Symbol>, _done : bool, _retval : TLVal) -> TLVal
 }
 
 
-fn call_func2(args : Vec<Symbol>, _done : bool, _retval : TLVal) -> TLVal
+fn call_func2(args : TArgs, _done : bool, _retval : TLVal) -> TLVal
 {
 	if !_done
 	{
@@ -95,8 +108,8 @@ fn call_func2(args : Vec<Symbol>, _done : bool, _retval : TLVal) -> TLVal
 		return 0 as TLVal;
 	}
 
-	let arg1 : TLVal = args[2].val;
-	let arg2 : TLVal = args[4].val;
+	let arg1 : TLVal = get_arg(&args, SEM_CALL2_ID, 2).val;
+	let arg2 : TLVal = get_arg(&args, SEM_CALL2_ID, 4).val;
 
 	let ident : &str = &args[0].strval.as_ref().unwrap();
 	match ident
@@ -113,16 +126,16 @@ fn call_func2(args : Vec<Symbol>, _done : bool, _retval : TLVal) -> TLVal
 
 fn set_semantics(parser : &mut dyn Parsable)
 {
-	const SEMANTICS : [(TSemanticId, TSemantics); 16] =
+	const SEMANTICS : [(TSemanticId, TSemantics); 15] =
 	[
 		// ----------------------------------------------------------------------
-		( SEM_START_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_START_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			args[0].val
 		} ),
 
-		( SEM_BRACKETS_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_BRACKETS_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			args[1].val
@@ -131,49 +144,49 @@ fn set_semantics(parser : &mut dyn Parsable)
 
 		// ----------------------------------------------------------------------
 		// arithmetics
-		( SEM_ADD_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_ADD_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			args[0].val + args[2].val
 		} ),
 
-		( SEM_SUB_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_SUB_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			args[0].val - args[2].val
 		} ),
 
-		( SEM_MUL_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_MUL_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			args[0].val * args[2].val
 		} ),
 
-		( SEM_DIV_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_DIV_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			args[0].val / args[2].val
 		} ),
 
-		( SEM_MOD_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_MOD_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			args[0].val % args[2].val
 		} ),
 
-		( SEM_POW_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_POW_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			TLVal::powf(args[0].val, args[2].val.try_into().unwrap())
 		} ),
 
-		( SEM_UADD_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_UADD_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			args[1].val
 		} ),
 
-		( SEM_USUB_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_USUB_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			-args[1].val
@@ -182,24 +195,22 @@ fn set_semantics(parser : &mut dyn Parsable)
 
 		// ----------------------------------------------------------------------
 		// symbols and constants
-		( SEM_REAL_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_REAL_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			args[0].val
 		} ),
 
-		( SEM_INT_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_INT_ID, |args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			args[0].val
 		} ),
-
-		( SEM_IDENT_ID, get_symbol),
 		// ----------------------------------------------------------------------
 
 		// ----------------------------------------------------------------------
 		// functions
-		( SEM_CALL0_ID, |_args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		( SEM_CALL0_ID, |_args : TArgs, _done : bool, _retval : TLVal| -> TLVal
 		{
 			if !_done { return 0 as TLVal; }
 			0 as TLVal // TODO
@@ -211,6 +222,46 @@ fn set_semantics(parser : &mut dyn Parsable)
 	];
 
 	parser.set_semantics(&SEMANTICS);
+	parser.set_fallible_semantics(&[ (SEM_IDENT_ID, get_symbol) ]);
+}
+
+
+#[cfg(feature = "codespan")]
+fn print_error(line : &str, err : &ParseError)
+{
+	diagnostics::emit_diagnostic("<input>", line,
+		&diagnostics::parse_error_diagnostic(err, ()));
+}
+
+
+#[cfg(not(feature = "codespan"))]
+fn print_error(_line : &str, err : &ParseError)
+{
+	println!("Error: {}.", err);
+
+	// hints for the expected tokens
+	for term in err.expected.iter()
+	{
+		if let Some(doc) = term.doc
+		{
+			println!("\t{}: {}.", term.name, doc);
+		}
+	}
+}
+
+
+#[cfg(feature = "codespan")]
+fn print_number_error(line : &str, err : &NumberError)
+{
+	diagnostics::emit_diagnostic("<input>", line,
+		&diagnostics::number_error_diagnostic(err, ()));
+}
+
+
+#[cfg(not(feature = "codespan"))]
+fn print_number_error(_line : &str, err : &NumberError)
+{
+	println!("Error: {}.", err);
 }
 
 
@@ -218,39 +269,118 @@ fn run_parser(parser : &mut dyn Parsable)
 {
 	parser.set_debug(SET_DEBUG);
 	parser.set_partials(SET_PARTIALS);
+	parser.set_partials_policy(SET_PARTIALS_POLICY);
+	parser.set_profiling(SET_PROFILING);
+	parser.set_statistics(SET_STATISTICS);
 	let end = parser.get_end_id();
 
+	if SET_RECOVERY
+	{
+		// resume parsing after closing brackets, argument separators and at the end
+		parser.set_sync_tokens(&[')' as TSymbolId, ',' as TSymbolId, end]);
+		parser.set_max_skipped_tokens(SET_MAX_SKIPPED);
+	}
+
+	// json action trace of each parse
+	let trace : ParseTrace = ParseTrace::new();
+	if SET_TRACE
+	{
+		parser.set_observer(Some(Box::new(trace.clone())));
+	}
+
+	// input collected over several lines if it was incomplete
+	let mut line : String = String::new();
+
 	loop
 	{
-		let mut line : String = String::new();
-		stdin().read_line(&mut line).expect("Could not read input.");
-		line = line.trim().to_string();
-		if line.len() == 0
+		let mut new_line : String = String::new();
+		let at_eof : bool = stdin().read_line(&mut new_line).expect("Could not read input.") == 0;
+		let new_line : &str = new_line.trim();
+		if new_line.len() == 0 && !(at_eof && line.len() != 0)
 		{
+			if at_eof
+			{
+				break;
+			}
 			continue
 		}
 
-		let mut tokens = lexer::get_all_matches(&line);
+		if line.len() != 0
+		{
+			line.push(' ');
+		}
+		line.push_str(new_line);
+
+		let mut tokens : Vec<Symbol> = match lexer::get_all_matches(&line)
+		{
+			Ok(tokens) => tokens,
+			Err(err) =>
+			{
+				// the line can't be lexed, so it is neither parsed nor continued
+				print_number_error(&line, &err);
+				line.clear();
+				if at_eof
+				{
+					break;
+				}
+				continue;
+			},
+		};
 		tokens.push(Symbol{
 			is_term : true,
 			id : end,
 			val : 0 as TLVal,
-			strval : Some("<end>".to_string())
+			strval : Some("<end>".to_string()),
+			strid : None,
+			attachment : None,
+			span : Some((line.len(), line.len())),
 		});
-		parser.set_input(&tokens);
-		if SET_DEBUG
+		if SET_DEBUG >= DebugLevel::Trace
 		{
 			println!("Tokens: {:?}.", tokens);
 		}
+		parser.set_input_vec(tokens);
+
+		trace.clear();
+		match parser.try_parse()
+		{
+			Ok(topsym) => println!("{}", topsym.val),
+			Err(ref err) if err.is_incomplete() && !at_eof =>
+			{
+				// prompt for a continuation line
+				print!("... ");
+				stdout().flush().expect("Could not write output.");
+				continue;
+			},
+			Err(err) =>
+			{
+				// the errors following the first one have been recovered from
+				for err in std::iter::once(&*err).chain(parser.get_errors().iter().skip(1))
+				{
+					print_error(&line, err);
+				}
+			},
+		}
+
+		if let Some(profile) = parser.get_profile()
+		{
+			print!("{}", profile);
+		}
+
+		if let Some(stats) = parser.get_statistics()
+		{
+			print!("{}", stats);
+		}
 
-		if parser.parse()
+		if SET_TRACE
 		{
-			let topsym = parser.get_top_symbol().unwrap();
-			println!("{}", topsym.val);
+			println!("{}", trace.to_json());
 		}
-		else
+
+		line.clear();
+		if at_eof
 		{
-			println!("Error: Parsing failed.");
+			break;
 		}
 	}
 }
@@ -258,7 +388,7 @@ fn run_parser(parser : &mut dyn Parsable)
 
 fn main()
 {
-	let mut parser = Parser::new();
+	let mut parser : Parser = Parser::new();
 	set_semantics(&mut parser);
 	run_parser(&mut parser);
 }
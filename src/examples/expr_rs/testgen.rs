@@ -0,0 +1 @@
+../../modules/lalr1_rs/testgen.rs
\ No newline at end of file
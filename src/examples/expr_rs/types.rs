@@ -7,10 +7,20 @@
  */
 
 #![allow(unused)]
-pub use expr::lalr1_tables;
 
+// a parser generated with parsergen's --narrow-types embeds its own tables,
+// the runtime has to use them, so its driver is built with the narrow_types feature
+#[cfg(feature = "narrow_types")]
+pub use generated_parser::lalr1_types::*;
 
-pub type TLVal = f64;
+#[cfg(not(feature = "narrow_types"))]
+pub use expr::lalr1_tables;
+#[cfg(not(feature = "narrow_types"))]
 pub type TIndex = lalr1_tables::TIndex;
+#[cfg(not(feature = "narrow_types"))]
 pub type TSymbolId = lalr1_tables::TSymbolId;
+#[cfg(not(feature = "narrow_types"))]
 pub type TSemanticId = lalr1_tables::TSemanticId;
+
+
+pub type TLVal = f64;
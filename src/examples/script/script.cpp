@@ -91,6 +91,7 @@ lalr1_run_parser(const std::string& script_file,
 		auto [shift_tab, reduce_tab, jump_tab, num_rhs, lhs_idx] = get_lalr1_tables();
 		auto [term_idx, nonterm_idx, semantic_idx] = get_lalr1_table_indices();
 		auto [err_idx, acc_idx, eps_id, end_id, start_idx, acc_rule_idx] = get_lalr1_constants();
+		auto [errtok_id, errtok_idx] = get_lalr1_error_token();
 
 		Parser parser;
 		parser.SetShiftTable(shift_tab);
@@ -100,6 +101,9 @@ lalr1_run_parser(const std::string& script_file,
 		parser.SetNumRhsSymsPerRule(num_rhs);
 		parser.SetLhsIndices(lhs_idx);
 		parser.SetEndId(end_id);
+		parser.SetErrorValue(err_idx);
+		parser.SetAcceptValue(acc_idx);
+		parser.SetErrorToken(errtok_id, errtok_idx);
 		parser.SetStartingState(start_idx);
 		parser.SetAcceptingRule(acc_rule_idx);
 #endif
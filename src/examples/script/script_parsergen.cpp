@@ -95,6 +95,7 @@ static bool lr1_create_parser(
 
 		CollectionPtr collsLALR = std::make_shared<Collection>(closure);
 		collsLALR->SetProgressObserver(progress);
+		collsLALR->SetNumThreads(0);
  		collsLALR->DoTransitions();
 
 		if(verbose)
@@ -0,0 +1,153 @@
+/*
+ * build script integration, creates the parsing tables of lexertl/parsertl (gram_grep)
+ * specifications when building a crate
+ *
+ * @author Tobias Weber (orcid: 0000-0002-7230-1932)
+ * @date 15-oct-2026
+ * @license see 'LICENSE' file
+ *
+ * Usage in build.rs:
+ *	fn main()
+ *	{
+ *		lalr1_build::Config::new()
+ *			.grammar("expr.lalr")
+ *			.generate()
+ *			.expect("Cannot create the parsing tables.");
+ *	}
+ *
+ * and in the crate, in place of the tables module:
+ *	include!(concat!(env!("OUT_DIR"), "/expr.rs"));
+ *
+ * The tables are created by the "lalr1 import" command of the lalr1 tool, which is
+ * looked up in the LALR1_TOOL environment variable or otherwise in the path.
+ */
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+
+/*
+ * settings for the table generation
+ */
+#[derive(Debug, Clone, Default)]
+pub struct Config
+{
+	grammars : Vec<PathBuf>,      // input specifications
+	out_dir : Option<PathBuf>,    // output directory, default: OUT_DIR
+	tool : Option<PathBuf>,       // lalr1 tool, default: LALR1_TOOL or "lalr1"
+	quiet : bool,                 // suppress the output of the tool
+}
+
+
+impl Config
+{
+	pub fn new() -> Config
+	{
+		Config::default()
+	}
+
+
+	/*
+	 * add a grammar specification, relative paths start at the crate directory
+	 */
+	pub fn grammar<P : AsRef<Path>>(&mut self, file : P) -> &mut Config
+	{
+		self.grammars.push(file.as_ref().to_path_buf());
+		self
+	}
+
+
+	/*
+	 * set the directory for the generated files
+	 */
+	pub fn out_dir<P : AsRef<Path>>(&mut self, dir : P) -> &mut Config
+	{
+		self.out_dir = Some(dir.as_ref().to_path_buf());
+		self
+	}
+
+
+	/*
+	 * set the lalr1 tool to use
+	 */
+	pub fn tool<P : AsRef<Path>>(&mut self, tool : P) -> &mut Config
+	{
+		self.tool = Some(tool.as_ref().to_path_buf());
+		self
+	}
+
+
+	/*
+	 * suppress the output of the tool
+	 */
+	pub fn quiet(&mut self, quiet : bool) -> &mut Config
+	{
+		self.quiet = quiet;
+		self
+	}
+
+
+	/*
+	 * create the tables of all grammars and let cargo rerun the build script on changes,
+	 * returns the generated files, which are named after the grammars, e.g. expr.lalr -> expr.rs
+	 */
+	pub fn generate(&self) -> Result<Vec<PathBuf>, String>
+	{
+		let out_dir : PathBuf = match &self.out_dir
+		{
+			Some(dir) => dir.clone(),
+			None => env::var_os("OUT_DIR").map(PathBuf::from)
+				.ok_or("No output directory given and OUT_DIR is not set.".to_string())?,
+		};
+
+		let tool : PathBuf = match &self.tool
+		{
+			Some(tool) => tool.clone(),
+			None =>
+			{
+				println!("cargo:rerun-if-env-changed=LALR1_TOOL");
+				env::var_os("LALR1_TOOL").map(PathBuf::from).unwrap_or(PathBuf::from("lalr1"))
+			},
+		};
+
+		if self.grammars.is_empty()
+		{
+			return Err("No grammar given.".to_string());
+		}
+
+		let mut files : Vec<PathBuf> = Vec::new();
+		for grammar in &self.grammars
+		{
+			println!("cargo:rerun-if-changed={}", grammar.display());
+
+			let stem = grammar.file_stem()
+				.ok_or(format!("Invalid grammar file name {:?}.", grammar))?;
+			let mut tables_file : PathBuf = out_dir.join(stem);
+			tables_file.set_extension("rs");
+
+			let output = Command::new(&tool)
+				.arg("import").arg(grammar)
+				.arg("-o").arg(&tables_file)
+				.output()
+				.map_err(|err| format!("Cannot run the lalr1 tool {:?}: {}.", tool, err))?;
+
+			if !self.quiet
+			{
+				// cargo shows the output of build scripts with -vv
+				print!("{}", String::from_utf8_lossy(&output.stdout));
+				eprint!("{}", String::from_utf8_lossy(&output.stderr));
+			}
+
+			if !output.status.success()
+			{
+				return Err(format!("Cannot create the tables of {:?}: {}",
+					grammar, String::from_utf8_lossy(&output.stderr).trim()));
+			}
+
+			files.push(tables_file);
+		}
+
+		Ok(files)
+	}
+}
@@ -0,0 +1,401 @@
+/*
+ * grammar embedding, expands a lexertl/parsertl (gram_grep) specification
+ * with rust semantic actions into the parsing tables and the actions
+ *
+ * @author Tobias Weber (orcid: 0000-0002-7230-1932)
+ * @date 15-oct-2026
+ * @license see 'LICENSE' file
+ *
+ * Usage, e.g. in the tables module "expr.rs" of the runtime:
+ *	lalr1_derive::grammar!(r#"
+ *		%token NUM
+ *		%%
+ *		expr : expr '+' NUM { args[0].val + args[2].val }
+ *			| NUM { args[0].val } ;
+ *		%%
+ *	"#);
+ *
+ * The tables are created at compile time by the "lalr1 import" command of the lalr1 tool,
+ * which is looked up in the LALR1_TOOL environment variable or otherwise in the path.
+ * The actions are rust blocks evaluating to the value of the rule, they see the rhs symbols
+ * in "args", "done" is false for partial matches and "retval" is the value of the last one.
+ * The types TLVal, TSemanticId, TArgs and TSemanticAction of the runtime have to be in scope.
+ */
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, TokenTree};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+
+// number of the next grammar expansion in this compiler process
+static NEXT_EXPANSION : AtomicUsize = AtomicUsize::new(0);
+
+
+/*
+ * expands the grammar into the module "lalr1_tables" and the function "semantic_actions()"
+ */
+#[proc_macro]
+pub fn grammar(input : TokenStream) -> TokenStream
+{
+	let spec : String = match get_string_literal(input)
+	{
+		Some(spec) => spec,
+		None => return compile_error("Expected the grammar specification as a string literal."),
+	};
+
+	let tables : String = match create_tables(&spec)
+	{
+		Ok(tables) => tables,
+		Err(err) => return compile_error(&err),
+	};
+
+	let actions : Vec<(usize, String)> = match get_actions(&spec)
+	{
+		Ok(actions) => actions,
+		Err(err) => return compile_error(&err),
+	};
+
+	let mut code : String = tables;
+	code += "\n// semantic actions of the grammar rules\n";
+	code += "#[allow(unused)]\n";
+	code += "pub fn semantic_actions() -> Vec<(TSemanticId, TSemanticAction<TLVal>)>\n{\n";
+	code += "\tvec![\n";
+	for (rule_id, action) in &actions
+	{
+		code += &format!("\t\t({rule_id}, Box::new(|args : TArgs<TLVal>, done : bool, retval : TLVal| -> TLVal {{ {action} }})),\n");
+	}
+	code += "\t]\n}\n";
+
+	match code.parse::<TokenStream>()
+	{
+		Ok(stream) => stream,
+		Err(err) => compile_error(&format!("Invalid generated code: {err}.")),
+	}
+}
+
+
+/*
+ * emit an error at the macro invocation
+ */
+fn compile_error(msg : &str) -> TokenStream
+{
+	format!("compile_error!({msg:?});").parse().unwrap()
+}
+
+
+/*
+ * get the contents of a (raw) string literal
+ */
+fn get_string_literal(input : TokenStream) -> Option<String>
+{
+	let mut iter = input.into_iter();
+	let lit : String = match (iter.next(), iter.next())
+	{
+		(Some(TokenTree::Literal(lit)), None) => lit.to_string(),
+		_ => return None,
+	};
+
+	// raw string, e.g. r#"..."#
+	if let Some(raw) = lit.strip_prefix('r')
+	{
+		let hashes : usize = raw.chars().take_while(|ch| *ch == '#').count();
+		let raw : &str = &raw[hashes ..];
+		return raw.strip_prefix('"')
+			.and_then(|raw| raw.strip_suffix(&"#".repeat(hashes)))
+			.and_then(|raw| raw.strip_suffix('"'))
+			.map(|raw| raw.to_string());
+	}
+
+	// normal string with escape sequences
+	let lit : &str = lit.strip_prefix('"')?.strip_suffix('"')?;
+	let mut str : String = String::new();
+	let mut chars = lit.chars();
+	while let Some(ch) = chars.next()
+	{
+		if ch != '\\'
+		{
+			str.push(ch);
+			continue;
+		}
+
+		match chars.next()?
+		{
+			'n' => str.push('\n'),
+			't' => str.push('\t'),
+			'r' => str.push('\r'),
+			'0' => str.push('\0'),
+			'\n' =>
+			{
+				// line continuation
+				while chars.clone().next().is_some_and(|ch| ch.is_whitespace())
+				{
+					chars.next();
+				}
+			},
+			ch => str.push(ch),
+		}
+	}
+
+	Some(str)
+}
+
+
+/*
+ * run the lalr1 tool on the specification and get the rust tables
+ */
+fn create_tables(spec : &str) -> Result<String, String>
+{
+	let tool : String = env::var("LALR1_TOOL").unwrap_or("lalr1".to_string());
+
+	// every expansion gets its own directory, so that concurrent builds
+	// and expansions of the same grammar don't overwrite each other's files
+	let dir : PathBuf = env::temp_dir().join(format!("lalr1_grammar_{}_{}",
+		process::id(), NEXT_EXPANSION.fetch_add(1, Ordering::Relaxed)));
+	fs::create_dir_all(&dir)
+		.map_err(|err| format!("Cannot create {dir:?}: {err}."))?;
+
+	let tables : Result<String, String> = run_tool(&tool, spec, &dir);
+	let _ = fs::remove_dir_all(&dir);
+	tables
+}
+
+
+/*
+ * import the specification in the given directory
+ */
+fn run_tool(tool : &str, spec : &str, dir : &Path) -> Result<String, String>
+{
+	let spec_file : PathBuf = dir.join("grammar.g");
+	let tables_file : PathBuf = dir.join("grammar.rs");
+
+	fs::write(&spec_file, spec)
+		.map_err(|err| format!("Cannot write {spec_file:?}: {err}."))?;
+
+	let output = Command::new(tool)
+		.arg("import").arg(&spec_file)
+		.arg("-o").arg(&tables_file)
+		.output()
+		.map_err(|err| format!("Cannot run the lalr1 tool \"{tool}\": {err}."))?;
+
+	if !output.status.success()
+	{
+		return Err(format!("The lalr1 tool failed: {}",
+			String::from_utf8_lossy(&output.stderr).trim()));
+	}
+
+	fs::read_to_string(&tables_file)
+		.map_err(|err| format!("Cannot read {tables_file:?}: {err}."))
+}
+
+
+/*
+ * get the position after a rust comment or string or char literal starting at the given position,
+ * the position is returned unchanged if there's none, e.g. for a lifetime
+ */
+fn skip_rust_literal(text : &[char], pos : usize) -> usize
+{
+	let ch : char = text[pos];
+	let next : char = text.get(pos + 1).cloned().unwrap_or('\0');
+
+	if ch == '/' && next == '/'
+	{
+		let mut pos : usize = pos;
+		while pos < text.len() && text[pos] != '\n'
+		{
+			pos += 1;
+		}
+		pos
+	}
+	else if ch == '/' && next == '*'
+	{
+		let mut pos : usize = pos + 2;
+		while pos < text.len() && !(text[pos] == '*' && text.get(pos + 1) == Some(&'/'))
+		{
+			pos += 1;
+		}
+		pos + 2
+	}
+	else if ch == 'r' && (next == '"' || next == '#') && (pos == 0 || !is_ident_char(text[pos - 1]))
+	{
+		// raw string, e.g. r#"..."#
+		let hashes : usize = text[pos + 1 ..].iter().take_while(|ch| **ch == '#').count();
+		let mut pos : usize = pos + 1 + hashes;
+		if text.get(pos) != Some(&'"')
+		{
+			return pos - 1 - hashes;
+		}
+		pos += 1;
+		while pos < text.len() && !(text[pos] == '"' &&
+			text[pos + 1 ..].iter().take(hashes).filter(|ch| **ch == '#').count() == hashes)
+		{
+			pos += 1;
+		}
+		pos + 1 + hashes
+	}
+	else if ch == '"'
+	{
+		let mut pos : usize = pos + 1;
+		while pos < text.len() && text[pos] != '"'
+		{
+			pos += if text[pos] == '\\' { 2 } else { 1 };
+		}
+		pos + 1
+	}
+	else if ch == '\'' && next == '\\'
+	{
+		// escaped char, e.g. '\n' or '\u{7b}'
+		let mut pos : usize = pos + 2;
+		while pos < text.len() && text[pos] != '\''
+		{
+			pos += 1;
+		}
+		pos + 1
+	}
+	else if ch == '\'' && text.get(pos + 2) == Some(&'\'')
+	{
+		pos + 3
+	}
+	else
+	{
+		pos
+	}
+}
+
+
+fn is_ident_char(ch : char) -> bool
+{
+	ch.is_alphanumeric() || ch == '_'
+}
+
+
+/*
+ * get the action blocks of the grammar rules with their semantic ids,
+ * the ids are counted like in the importer: 0 is the augmented start rule,
+ * the others follow the order of the alternatives in the specification
+ */
+fn get_actions(spec : &str) -> Result<Vec<(usize, String)>, String>
+{
+	// the grammar is the second of the sections separated by "%%" lines
+	let grammar : String = spec.lines()
+		.skip_while(|line| line.trim() != "%%").skip(1)
+		.take_while(|line| line.trim() != "%%")
+		.collect::<Vec<&str>>().join("\n");
+
+	let text : Vec<char> = grammar.chars().collect();
+	let mut actions : Vec<(usize, String)> = Vec::new();
+	let mut rule_id : usize = 1;
+	let mut action : Option<String> = None;
+	let mut in_rule : bool = false;
+
+	let mut pos : usize = 0;
+	while pos < text.len()
+	{
+		let ch : char = text[pos];
+		let next : char = text.get(pos + 1).cloned().unwrap_or('\0');
+
+		if ch == '/' && next == '/'
+		{
+			while pos < text.len() && text[pos] != '\n'
+			{
+				pos += 1;
+			}
+		}
+		else if ch == '/' && next == '*'
+		{
+			pos += 2;
+			while pos < text.len() && !(text[pos] == '*' && text.get(pos + 1) == Some(&'/'))
+			{
+				pos += 1;
+			}
+			pos += 2;
+		}
+		else if ch == '\'' || ch == '"'
+		{
+			// skip literals
+			pos += 1;
+			while pos < text.len() && text[pos] != ch && text[pos] != '\n'
+			{
+				pos += if text[pos] == '\\' { 2 } else { 1 };
+			}
+			pos += 1;
+		}
+		else if ch == '{'
+		{
+			// the last action of an alternative is the one run on its reduction,
+			// braces in the action's literals and comments are not counted
+			let start : usize = pos + 1;
+			let mut depth : usize = 0;
+			while pos < text.len()
+			{
+				let end : usize = skip_rust_literal(&text, pos);
+				if end != pos
+				{
+					pos = end;
+					continue;
+				}
+
+				if text[pos] == '{'
+				{
+					depth += 1;
+				}
+				else if text[pos] == '}'
+				{
+					depth -= 1;
+					if depth == 0
+					{
+						break;
+					}
+				}
+				pos += 1;
+			}
+
+			if pos >= text.len()
+			{
+				return Err(format!("Unterminated action in rule {rule_id}."));
+			}
+			action = Some(text[start .. pos].iter().collect());
+			pos += 1;
+		}
+		else if ch == '<'
+		{
+			// skip type tags
+			while pos < text.len() && text[pos] != '>'
+			{
+				pos += 1;
+			}
+			pos += 1;
+		}
+		else if ch == ':'
+		{
+			in_rule = true;
+			pos += 1;
+		}
+		else if ch == '|' || ch == ';'
+		{
+			// end of an alternative
+			if let Some(action) = action.take()
+			{
+				actions.push((rule_id, action));
+			}
+			rule_id += 1;
+			in_rule = ch == '|';
+			pos += 1;
+		}
+		else
+		{
+			pos += 1;
+		}
+	}
+
+	if in_rule
+	{
+		return Err("Missing ';' after the last rule.".to_string());
+	}
+
+	Ok(actions)
+}
@@ -6,37 +6,290 @@
  * @license see 'LICENSE' file
  */
 
+/*
+ * std is used by default, if compiled with --cfg 'feature="no_std"'
+ * only core and alloc are used, the crate root then has to declare
+ * "#![no_std]" and "extern crate alloc;"
+ */
+#[cfg(not(feature = "no_std"))]
+use std::panic::resume_unwind;
+#[cfg(not(feature = "no_std"))]
+use std::collections::{HashMap, VecDeque};
+#[cfg(not(feature = "no_std"))]
+use std::sync::{Arc, Mutex, MutexGuard};
+#[cfg(not(feature = "no_std"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(not(feature = "no_std"))]
+use std::thread;
+#[cfg(not(feature = "no_std"))]
+use std::{any::Any, fmt, iter, mem, str};
+#[cfg(not(feature = "no_std"))]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "no_std")]
+use alloc::collections::{BTreeMap as HashMap, VecDeque};
+#[cfg(feature = "no_std")]
+use alloc::{rc::Rc, sync::Arc};
+#[cfg(feature = "no_std")]
+use core::cell::{RefCell, RefMut};
+#[cfg(feature = "no_std")]
+use core::{any::Any, fmt, iter, mem, str};
+#[cfg(feature = "no_std")]
+use core::time::Duration;
+#[cfg(feature = "no_std")]
+use alloc::{vec, vec::Vec, string::{String, ToString}, boxed::Box, format};
+
 use types;
 
 
+/*
+ * a terminal or nonterminal symbol carrying a semantic value of type T,
+ * e.g. a number or a user-defined AST node like Option<Box<Node>>,
+ * values are moved through the reductions and only cloned for partial matches,
+ * tokens can leave strval empty and refer to their text in the source by the span,
+ * lexers can attach further token data, e.g. the radix of a number or a doc comment
+ */
 #[derive(Default, Clone, Debug)]
-pub struct Symbol
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Symbol<T = types::TLVal>
 {
 	pub is_term : bool,
 	pub id : types::TSymbolId,
-	pub val : types::TLVal,
+	pub val : T,
 	pub strval : Option<String>,
+	pub strid : Option<TStrId>,          // interned string, see Interner
+	#[cfg_attr(feature = "serde", serde(skip))]
+	pub attachment : Option<TAttachment>,
+	pub span : Option<(usize, usize)>,   // start and end offset in the source
+}
+
+
+// token data of any type, shared between the clones of a symbol
+pub type TAttachment = Arc<dyn Any + Send + Sync>;
+
+// slot for the token following a reduced phrase, can be captured by the semantic functions
+pub type TLookahead<T = types::TLVal> = TShared<Option<Symbol<T>>>;
+
+
+impl<T> Symbol<T>
+{
+	/*
+	 * get the text of the symbol, either its own string or the source text covered by its span
+	 */
+	pub fn get_str<'a>(&'a self, source : &'a str) -> Option<&'a str>
+	{
+		match (&self.strval, self.span)
+		{
+			(Some(strval), _) => Some(strval),
+			(None, Some((start, end))) => source.get(start .. end),
+			(None, None) => None,
+		}
+	}
+
+
+	pub fn set_attachment<A : Any + Send + Sync>(&mut self, attachment : A)
+	{
+		self.attachment = Some(Arc::new(attachment));
+	}
+
+
+	/*
+	 * get the attached token data if it has the given type
+	 */
+	pub fn get_attachment<A : Any>(&self) -> Option<&A>
+	{
+		self.attachment.as_ref().and_then(|attachment| attachment.downcast_ref::<A>())
+	}
+
+
+	/*
+	 * release the value, string and attachment, e.g. after a partial rule consumed them,
+	 * the id and span are kept
+	 */
+	pub fn drop_data(&mut self) where T : Default
+	{
+		self.val = T::default();
+		self.strval = None;
+		self.strid = None;
+		self.attachment = None;
+	}
+
+
+	/*
+	 * move the value, string and attachment into a new symbol, e.g. to pass on a completed item,
+	 * the id and span are kept
+	 */
+	pub fn take_data(&mut self) -> Symbol<T> where T : Default
+	{
+		Symbol
+		{
+			is_term : self.is_term,
+			id : self.id,
+			val : mem::take(&mut self.val),
+			strval : self.strval.take(),
+			strid : self.strid.take(),
+			attachment : self.attachment.take(),
+			span : self.span,
+		}
+	}
+
+
+	/*
+	 * give the string of the symbol an id if it doesn't have one yet
+	 */
+	pub fn intern(&mut self, interner : &mut Interner)
+	{
+		if let (None, Some(strval)) = (self.strid, &self.strval)
+		{
+			self.strid = Some(interner.intern(strval));
+		}
+	}
+}
+
+
+/*
+ * data shared between the parser and its user,
+ * with std it can also be shared between threads
+ */
+#[cfg(not(feature = "no_std"))]
+pub type TShared<X> = Arc<Mutex<X>>;
+#[cfg(feature = "no_std")]
+pub type TShared<X> = Rc<RefCell<X>>;
+
+
+pub fn new_shared<X>(data : X) -> TShared<X>
+{
+	#[cfg(not(feature = "no_std"))]
+	return Arc::new(Mutex::new(data));
+	#[cfg(feature = "no_std")]
+	return Rc::new(RefCell::new(data));
+}
+
+
+/*
+ * get mutable access to shared data,
+ * a panic in another thread holding the lock doesn't invalidate the data
+ */
+#[cfg(not(feature = "no_std"))]
+pub fn lock_shared<X>(shared : &TShared<X>) -> MutexGuard<'_, X>
+{
+	shared.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+
+#[cfg(feature = "no_std")]
+pub fn lock_shared<X>(shared : &TShared<X>) -> RefMut<'_, X>
+{
+	shared.borrow_mut()
+}
+
+
+/*
+ * Send with std, so that parsers can be moved to other threads,
+ * without std the shared data is not thread-safe
+ */
+#[cfg(not(feature = "no_std"))]
+pub trait MaybeSend : Send {}
+#[cfg(not(feature = "no_std"))]
+impl<X : Send> MaybeSend for X {}
+
+#[cfg(feature = "no_std")]
+pub trait MaybeSend {}
+#[cfg(feature = "no_std")]
+impl<X> MaybeSend for X {}
+
+
+/*
+ * id of an interned string
+ */
+pub type TStrId = usize;
+
+
+/*
+ * stores each distinct string once, e.g. the identifiers and keywords of the input,
+ * so that they can be compared and used as keys of symbol tables by their ids
+ */
+#[derive(Default, Clone, Debug)]
+pub struct Interner
+{
+	ids : HashMap<Arc<str>, TStrId>,
+	strs : Vec<Arc<str>>,
+}
+
+
+// interner shared between the lexer, the parser and the semantic functions
+pub type TInterner = TShared<Interner>;
+
+
+impl Interner
+{
+	pub fn new() -> Interner
+	{
+		Interner::default()
+	}
+
+
+	/*
+	 * get the id of the string, adding it if it is new
+	 */
+	pub fn intern(&mut self, str : &str) -> TStrId
+	{
+		if let Some(id) = self.ids.get(str)
+		{
+			return *id;
+		}
+
+		let id : TStrId = self.strs.len();
+		let shared : Arc<str> = Arc::from(str);
+		self.strs.push(shared.clone());
+		self.ids.insert(shared, id);
+		id
+	}
+
+
+	/*
+	 * get the id of an already interned string
+	 */
+	pub fn get(&self, str : &str) -> Option<TStrId>
+	{
+		self.ids.get(str).cloned()
+	}
+
+
+	pub fn resolve(&self, id : TStrId) -> Option<&str>
+	{
+		self.strs.get(id).map(|str| &**str)
+	}
+
+
+	pub fn len(&self) -> usize
+	{
+		self.strs.len()
+	}
 }
 
 
 #[derive(Default, Clone, Debug)]
-pub struct ActiveRule
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct ActiveRule<T = types::TLVal>
 {
 	pub seen_tokens : usize,
 	pub handle : isize,
-	pub retval : types::TLVal,
+	pub retval : T,
+	pub complete : bool,   // a partial match already covered all symbols of the rule
 }
 
 
-impl ActiveRule
+impl<T : Default> ActiveRule<T>
 {
-	pub fn new() -> ActiveRule
+	pub fn new() -> ActiveRule<T>
 	{
-		let rule : ActiveRule = ActiveRule
+		let rule : ActiveRule<T> = ActiveRule
 		{
 			seen_tokens : 0,
 			handle : -1,
-			retval : 0 as types::TLVal,
+			retval : T::default(),
+			complete : false,
 		};
 
 		rule
@@ -44,20 +297,3163 @@ impl ActiveRule
 }
 
 
-pub type TSemantics = fn(Vec<Symbol>, bool, types::TLVal)
-	-> types::TLVal;
+/*
+ * source of tokens, e.g. a lexer, a token filter or a recorded stream,
+ * peek() returns the token that the next call to next() yields without consuming it,
+ * None marks the end of the input
+ */
+pub trait TokenSource<T = types::TLVal>
+{
+	fn peek(&mut self) -> Option<&Symbol<T>>;
+	fn next(&mut self) -> Option<Symbol<T>>;
+}
 
 
-pub trait Parsable
+// iterators are used as token sources via iter.peekable()
+impl<T, I : Iterator<Item = Symbol<T>>> TokenSource<T> for iter::Peekable<I>
 {
-	fn set_semantics(&mut self, sema : &[(types::TSemanticId, TSemantics)]);
-	fn set_input(&mut self, input: &[Symbol]);
-	fn set_debug(&mut self, debug : bool);
-	fn set_partials(&mut self, use_partials : bool);
+	fn peek(&mut self) -> Option<&Symbol<T>>
+	{
+		iter::Peekable::peek(self)
+	}
 
-	fn get_end_id(&self) -> types::TSymbolId;
-	fn get_top_symbol(&self) -> Option<&Symbol>;
+	fn next(&mut self) -> Option<Symbol<T>>
+	{
+		Iterator::next(self)
+	}
+}
 
-	fn reset(&mut self);
-	fn parse(&mut self) -> bool;
+
+/*
+ * reads the tokens of a token source as an iterator
+ */
+pub struct TokenSourceIter<T = types::TLVal>
+{
+	source : Box<dyn TokenSource<T> + Send>,
+}
+
+
+impl<T> TokenSourceIter<T>
+{
+	pub fn new(source : Box<dyn TokenSource<T> + Send>) -> TokenSourceIter<T>
+	{
+		TokenSourceIter { source }
+	}
+}
+
+
+impl<T> Iterator for TokenSourceIter<T>
+{
+	type Item = Symbol<T>;
+
+	fn next(&mut self) -> Option<Symbol<T>>
+	{
+		self.source.next()
+	}
+}
+
+
+/*
+ * input tokens, either given as a whole or produced lazily by an iterator
+ */
+pub struct TokenInput<T = types::TLVal>
+{
+	tokens : VecDeque<Symbol<T>>,                             // buffered tokens
+	first_index : usize,                                      // input index of the first buffered token
+	iter : Option<iter::Fuse<Box<dyn Iterator<Item = Symbol<T>> + Send>>>, // source of further tokens
+	appended : VecDeque<Symbol<T>>,                           // tokens to read after the stream
+	pinned : Option<usize>,                                   // keep the tokens from this input index on
+}
+
+
+impl<T : Clone + 'static> TokenInput<T>
+{
+	pub fn new() -> TokenInput<T>
+	{
+		TokenInput { tokens : VecDeque::new(), first_index : 0,
+			iter : None, appended : VecDeque::new(), pinned : None }
+	}
+
+	pub fn from_slice(input : &[Symbol<T>]) -> TokenInput<T>
+	{
+		TokenInput { tokens : input.iter().cloned().collect(), first_index : 0,
+			iter : None, appended : VecDeque::new(), pinned : None }
+	}
+
+	pub fn from_vec(input : Vec<Symbol<T>>) -> TokenInput<T>
+	{
+		TokenInput { tokens : input.into(), first_index : 0,
+			iter : None, appended : VecDeque::new(), pinned : None }
+	}
+
+	/*
+	 * replace the tokens, reusing the buffer of the previous input
+	 */
+	pub fn assign(&mut self, input : &[Symbol<T>])
+	{
+		self.tokens.clear();
+		self.tokens.extend(input.iter().cloned());
+		self.first_index = 0;
+		self.iter = None;
+		self.appended.clear();
+		self.pinned = None;
+	}
+
+	pub fn from_iter(input : Box<dyn Iterator<Item = Symbol<T>> + Send>) -> TokenInput<T>
+	{
+		TokenInput { tokens : VecDeque::new(), first_index : 0,
+			iter : Some(input.fuse()), appended : VecDeque::new(), pinned : None }
+	}
+
+	/*
+	 * get the token with the given input index, reading it from the iterator if needed
+	 */
+	pub fn get(&mut self, index : usize) -> Option<&Symbol<T>>
+	{
+		if index < self.first_index
+		{
+			return None;
+		}
+
+		while index >= self.first_index + self.tokens.len()
+		{
+			let token : Option<Symbol<T>> = match self.iter.as_mut().and_then(|iter| iter.next())
+			{
+				Some(token) => Some(token),
+				None => self.appended.pop_front(),
+			};
+
+			match token
+			{
+				Some(token) => self.tokens.push_back(token),
+				None => return None,
+			}
+		}
+
+		self.tokens.get(index - self.first_index)
+	}
+
+	/*
+	 * get an owned token with the given input index,
+	 * a streamed token that is not needed anymore is moved out instead of cloned
+	 */
+	pub fn take(&mut self, index : usize) -> Option<Symbol<T>>
+	{
+		let movable : bool = self.iter.is_some() && index == self.first_index &&
+			self.pinned.is_none_or(|pinned| index < pinned);
+
+		if movable && self.has(index)
+		{
+			self.first_index += 1;
+			return self.tokens.pop_front();
+		}
+
+		self.get(index).cloned()
+	}
+
+	pub fn has(&mut self, index : usize) -> bool
+	{
+		self.get(index).is_some()
+	}
+
+	/*
+	 * drop the streamed tokens before the given input index,
+	 * a complete input is kept so that it can be parsed again
+	 */
+	pub fn release(&mut self, index : usize)
+	{
+		if self.iter.is_none()
+		{
+			return;
+		}
+
+		let index : usize = match self.pinned
+		{
+			Some(pinned) => index.min(pinned),
+			None => index,
+		};
+
+		while self.first_index < index && !self.tokens.is_empty()
+		{
+			self.tokens.pop_front();
+			self.first_index += 1;
+		}
+	}
+
+	/*
+	 * keep the tokens from the given input index on, so that they can be read again
+	 */
+	pub fn pin(&mut self, index : usize)
+	{
+		self.pinned = Some(self.pinned.map_or(index, |pinned| pinned.min(index)));
+	}
+
+	pub fn unpin(&mut self)
+	{
+		self.pinned = None;
+	}
+
+	/*
+	 * number of tokens of a complete input, None for a stream
+	 */
+	pub fn len(&self) -> Option<usize>
+	{
+		match self.iter
+		{
+			Some(_) => None,
+			None => Some(self.first_index + self.tokens.len()),
+		}
+	}
+
+	/*
+	 * drop the tokens of a complete input from the given index on
+	 */
+	pub fn truncate(&mut self, len : usize)
+	{
+		if self.iter.is_none() && len >= self.first_index
+		{
+			self.tokens.truncate(len - self.first_index);
+		}
+	}
+
+	/*
+	 * replace the tokens in the input index range [start, end)
+	 * @return false if the range has already been released
+	 */
+	pub fn replace(&mut self, start : usize, end : usize, input : &[Symbol<T>]) -> bool
+	{
+		if start < self.first_index
+		{
+			return false;
+		}
+
+		// read all replaced tokens from a stream
+		if end > start
+		{
+			self.get(end - 1);
+		}
+
+		let from : usize = (start - self.first_index).min(self.tokens.len());
+		let to : usize = (end.max(start) - self.first_index).min(self.tokens.len());
+		let tail : VecDeque<Symbol<T>> = self.tokens.split_off(to);
+		self.tokens.truncate(from);
+		self.tokens.extend(input.iter().cloned());
+		self.tokens.extend(tail);
+		true
+	}
+
+	/*
+	 * add tokens after the ones still to be read
+	 */
+	pub fn append(&mut self, input : &[Symbol<T>])
+	{
+		match self.iter
+		{
+			Some(_) => self.appended.extend(input.iter().cloned()),
+			None => self.tokens.extend(input.iter().cloned()),
+		}
+	}
+
+	/*
+	 * count the input indices from the given one on, e.g. for the rest of
+	 * an input whose beginning was parsed by another process
+	 */
+	pub fn start_at(&mut self, index : usize)
+	{
+		self.first_index = index;
+		self.pinned = None;
+	}
+
+	/*
+	 * start counting the input indices anew, a stream continues with its remaining tokens
+	 */
+	pub fn rewind(&mut self)
+	{
+		self.first_index = 0;
+		self.pinned = None;
+	}
+
+	/*
+	 * read the tokens from the given input index on, advancing the index
+	 * and stepping over the tokens with the given (sorted) ids
+	 */
+	pub fn cursor<'a>(&'a mut self, index : &'a mut usize, skip : &'a [types::TSymbolId])
+		-> InputCursor<'a, T>
+	{
+		InputCursor { input : self, index, skip }
+	}
+}
+
+
+/*
+ * copy the buffered and appended tokens, e.g. for a forked parser,
+ * an input stream can't be copied, so the copy ends after the already read tokens
+ */
+impl<T : Clone> Clone for TokenInput<T>
+{
+	fn clone(&self) -> TokenInput<T>
+	{
+		let mut tokens : VecDeque<Symbol<T>> = self.tokens.clone();
+		tokens.extend(self.appended.iter().cloned());
+
+		TokenInput { tokens, first_index : self.first_index,
+			iter : None, appended : VecDeque::new(), pinned : self.pinned }
+	}
+}
+
+
+/*
+ * token source reading an input from an index that is kept by the parser,
+ * consumed streamed tokens are released unless they are pinned
+ */
+pub struct InputCursor<'a, T : 'a = types::TLVal>
+{
+	input : &'a mut TokenInput<T>,
+	index : &'a mut usize,
+	skip : &'a [types::TSymbolId],   // sorted
+}
+
+
+impl<'a, T : Clone + 'static> InputCursor<'a, T>
+{
+	/*
+	 * consume the tokens that the parser ignores
+	 */
+	fn skip_tokens(&mut self)
+	{
+		if self.skip.is_empty()
+		{
+			return;
+		}
+
+		while let Some(token) = self.input.get(*self.index)
+		{
+			if self.skip.binary_search(&token.id).is_err()
+			{
+				break;
+			}
+
+			*self.index += 1;
+			self.input.release(*self.index);
+		}
+	}
+}
+
+
+impl<'a, T : Clone + 'static> TokenSource<T> for InputCursor<'a, T>
+{
+	fn peek(&mut self) -> Option<&Symbol<T>>
+	{
+		self.skip_tokens();
+		self.input.get(*self.index)
+	}
+
+	fn next(&mut self) -> Option<Symbol<T>>
+	{
+		self.skip_tokens();
+		let token : Option<Symbol<T>> = self.input.take(*self.index);
+		*self.index += 1;
+		self.input.release(*self.index);
+		token
+	}
+}
+
+
+/*
+ * table entries and ids used as array indices,
+ * the tables can have narrower types than usize, see parsergen's --narrow-types
+ */
+pub trait TableIndex
+{
+	fn idx(self) -> usize;
+}
+
+
+macro_rules! impl_table_index
+{
+	($($ty:ty),*) =>
+	{
+		$(
+			impl TableIndex for $ty
+			{
+				// the cast is only redundant for the usize tables
+				#[allow(clippy::unnecessary_cast)]
+				#[inline]
+				fn idx(self) -> usize
+				{
+					self as usize
+				}
+			}
+		)*
+	};
+}
+
+impl_table_index!(u8, u16, u32, u64, usize);
+
+
+/*
+ * maps symbol ids to table indices, small ids like characters or consecutive token ids
+ * are looked up directly in an array, the others, e.g. the sentinels, in a hash map
+ */
+#[derive(Default, Clone, Debug)]
+pub struct IdIndexMap
+{
+	direct : Vec<Option<types::TIndex>>,
+	sparse : HashMap<types::TSymbolId, types::TIndex>,
+}
+
+
+impl IdIndexMap
+{
+	pub fn new(entries : &[(types::TSymbolId, types::TIndex)]) -> IdIndexMap
+	{
+		// keep the array small compared to the number of entries
+		let max_direct : usize = entries.len() * 16 + 1024;
+		let num_direct : usize = entries.iter()
+			.map(|entry| entry.0.idx())
+			.filter(|id| *id < max_direct)
+			.max()
+			.map_or(0, |id| id + 1);
+
+		let mut map : IdIndexMap = IdIndexMap { direct : vec![None; num_direct],
+			sparse : HashMap::new() };
+
+		for (id, idx) in entries
+		{
+			if id.idx() < num_direct
+			{
+				map.direct[id.idx()] = Some(*idx);
+			}
+			else
+			{
+				map.sparse.insert(*id, *idx);
+			}
+		}
+
+		map
+	}
+
+
+	pub fn get(&self, id : types::TSymbolId) -> Option<types::TIndex>
+	{
+		match self.direct.get(id.idx())
+		{
+			Some(idx) => *idx,
+			None => self.sparse.get(&id).cloned(),
+		}
+	}
+}
+
+
+/*
+ * get an array mapping the dense table indices to the ids
+ */
+pub fn get_index_ids<TId : Copy + Default>(entries : &[(TId, types::TIndex)]) -> Vec<TId>
+{
+	let mut ids : Vec<TId> = vec![TId::default(); entries.iter()
+		.map(|entry| entry.1.idx() + 1).max().unwrap_or(0)];
+
+	for (id, idx) in entries
+	{
+		ids[idx.idx()] = *id;
+	}
+
+	ids
+}
+
+
+/*
+ * semantic function invocations to run if partial matches are enabled
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartialsPolicy
+{
+	Both,         // run the partial matches and the full match
+	FinalValue,   // skip partial matches covering the whole rule, the full match yields the value
+	Incremental,  // skip the full match if a partial match already covered the whole rule
+}
+
+
+/*
+ * verbosity of the parser output
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DebugLevel
+{
+	Off,      // no output
+	Errors,   // only print errors
+	Actions,  // also print the parser actions, e.g. the applied rules
+	Trace,    // also print every lookahead and state
+}
+
+
+/*
+ * right-hand side symbols passed to the semantic functions,
+ * if compiled with --cfg 'feature="smallvec"' rules with up to 8 symbols
+ * get their arguments without a heap allocation
+ */
+#[cfg(feature = "smallvec")]
+pub type TArgs<T = types::TLVal> = ::smallvec::SmallVec<[Symbol<T>; 8]>;
+#[cfg(not(feature = "smallvec"))]
+pub type TArgs<T = types::TLVal> = Vec<Symbol<T>>;
+
+
+pub type TSemantics<T = types::TLVal> = fn(TArgs<T>, bool, T) -> T;
+
+// semantic function that can capture its environment, e.g. a symbol table
+pub type TSemanticAction<T = types::TLVal> = Box<dyn FnMut(TArgs<T>, bool, T) -> T + Send>;
+
+// semantic function that additionally gets the parser's user context, e.g. an AST arena
+pub type TContextAction<T = types::TLVal, C = ()> = Box<dyn FnMut(&mut C, TArgs<T>, bool, T) -> T + Send>;
+
+// semantic function for the rules that have none, it gets the rule and left-hand side ids
+pub type TDefaultAction<T = types::TLVal> = Box<dyn FnMut(types::TSemanticId, types::TSymbolId, TArgs<T>) -> T + Send>;
+
+// semantic functions that can fail, e.g. on a type error or an undefined identifier
+pub type TFallibleSemantics<T = types::TLVal> = fn(TArgs<T>, bool, T) -> Result<T, SemanticError>;
+pub type TFallibleAction<T = types::TLVal, C = ()> =
+	Box<dyn FnMut(&mut C, TArgs<T>, RuleMatch, T) -> Result<T, SemanticError> + Send>;
+
+// semantic functions that are told explicitly whether a rule is fully or partially matched
+pub type TMatchAction<T = types::TLVal> = Box<dyn FnMut(TArgs<T>, RuleMatch, T) -> T + Send>;
+
+
+/*
+ * the part of a rule that a semantic function is applied to,
+ * the boolean flag of the other semantic functions is true for a full match
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleMatch
+{
+	// the rule is reduced
+	Full,
+
+	// the first seen right-hand side symbols have been matched, if before_shift is set,
+	// the last argument is the lookahead terminal which is about to be shifted
+	Partial { seen : usize, before_shift : bool },
+}
+
+
+impl RuleMatch
+{
+	pub fn is_full(&self) -> bool
+	{
+		*self == RuleMatch::Full
+	}
+}
+
+
+/*
+ * wrap a semantic function that does not use the user context
+ */
+pub fn to_context_action<T : 'static, C : 'static>(mut action : TSemanticAction<T>) -> TContextAction<T, C>
+{
+	Box::new(move |_context : &mut C, args : TArgs<T>, done : bool, retval : T|
+		action(args, done, retval))
+}
+
+
+/*
+ * wrap a semantic function that cannot fail
+ */
+pub fn to_fallible_action<T : 'static, C : 'static>(mut action : TContextAction<T, C>) -> TFallibleAction<T, C>
+{
+	Box::new(move |context : &mut C, args : TArgs<T>, rule_match : RuleMatch, retval : T|
+		Ok(action(context, args, rule_match.is_full(), retval)))
+}
+
+
+/*
+ * wrap a semantic function that gets the kind of match
+ */
+pub fn from_match_action<T : 'static, C : 'static>(mut action : TMatchAction<T>) -> TFallibleAction<T, C>
+{
+	Box::new(move |_context : &mut C, args : TArgs<T>, rule_match : RuleMatch, retval : T|
+		Ok(action(args, rule_match, retval)))
+}
+
+
+/*
+ * wrap a fallible semantic function that does not use the user context
+ */
+pub fn from_fallible_fn<T : 'static, C : 'static>(func : TFallibleSemantics<T>) -> TFallibleAction<T, C>
+{
+	Box::new(move |_context : &mut C, args : TArgs<T>, rule_match : RuleMatch, retval : T|
+		func(args, rule_match.is_full(), retval))
+}
+
+
+/*
+ * semantic functions of the rules and the default function for rules without an own one,
+ * shared by the table-driven and the generated parsers,
+ * the functions are shared with the copies of a forked parser
+ */
+pub struct SemanticRules<T = types::TLVal, C = ()>
+{
+	actions : HashMap<types::TSemanticId, TShared<TFallibleAction<T, C>>>,
+	default_action : Option<TShared<TDefaultAction<T>>>,
+}
+
+
+impl<T, C> Clone for SemanticRules<T, C>
+{
+	fn clone(&self) -> SemanticRules<T, C>
+	{
+		SemanticRules { actions : self.actions.clone(), default_action : self.default_action.clone() }
+	}
+}
+
+
+impl<T : 'static, C : 'static> SemanticRules<T, C>
+{
+	pub fn new() -> SemanticRules<T, C>
+	{
+		SemanticRules { actions : HashMap::new(), default_action : None }
+	}
+
+
+	/*
+	 * remove the functions of all rules, the default function is kept
+	 */
+	pub fn clear(&mut self)
+	{
+		self.actions.clear();
+	}
+
+
+	/*
+	 * add or replace the function of a rule
+	 */
+	pub fn insert(&mut self, rule_id : types::TSemanticId, action : TFallibleAction<T, C>)
+	{
+		self.actions.insert(rule_id, new_shared(action));
+	}
+
+
+	pub fn set_default(&mut self, action : Option<TDefaultAction<T>>)
+	{
+		self.default_action = action.map(new_shared);
+	}
+
+
+	pub fn has_default(&self) -> bool
+	{
+		self.default_action.is_some()
+	}
+
+
+	pub fn get(&self, rule_id : types::TSemanticId) -> Option<&TShared<TFallibleAction<T, C>>>
+	{
+		self.actions.get(&rule_id)
+	}
+
+
+	/*
+	 * apply the full match of a rule, rules without a function use the default one,
+	 * without any function the given return value is passed through
+	 */
+	pub fn apply(&mut self, context : &mut C, call : SemanticCall, args : TArgs<T>, retval : T)
+		-> Result<T, SemanticError>
+	{
+		if let Some(action) = self.actions.get(&call.rule_id)
+		{
+			call_semantics(&mut lock_shared(action), context, call, args, RuleMatch::Full, retval)
+		}
+		else if let Some(default_action) = &self.default_action
+		{
+			Ok((*lock_shared(default_action))(call.rule_id, call.lhs_id, args))
+		}
+		else
+		{
+			Ok(retval)
+		}
+	}
+}
+
+
+/*
+ * error returned by a semantic function
+ */
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct SemanticError
+{
+	pub msg : String,
+}
+
+
+impl SemanticError
+{
+	pub fn new(msg : &str) -> SemanticError
+	{
+		SemanticError { msg : msg.to_string() }
+	}
+}
+
+
+impl fmt::Display for SemanticError
+{
+	fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result
+	{
+		write!(f, "{}", self.msg)
+	}
+}
+
+// receives the completed top-level items with their values, see Parsable::set_item_handler
+pub type TItemHandler<T = types::TLVal> = Box<dyn FnMut(Symbol<T>) + Send>;
+
+pub type TErrorHandler<T = types::TLVal> = Box<dyn FnMut(&ParseError<T>) + Send>;
+
+// merges the input discarded by the error recovery into the symbol shifted after it
+pub type TDiscardHandler<T = types::TLVal> = Box<dyn FnMut(&mut Symbol<T>, Vec<Symbol<T>>) + Send>;
+
+
+/*
+ * observer of the parser actions, e.g. for tracing, coverage or animation tools,
+ * all callbacks do nothing by default
+ */
+pub trait ParseObserver<T = types::TLVal> : MaybeSend
+{
+	// the token was shifted and the state entered
+	fn on_shift(&mut self, _state : types::TIndex, _token : &Symbol<T>) {}
+
+	// the rule was reduced in the given state, the arguments are the rhs symbols
+	fn on_reduce(&mut self, _state : types::TIndex, _rule_id : types::TSemanticId,
+		_lhs_id : types::TSymbolId, _args : &[Symbol<T>]) {}
+
+	// the rule was partially applied with the already matched rhs symbols
+	fn on_partial(&mut self, _state : types::TIndex, _rule_id : types::TSemanticId,
+		_lhs_id : types::TSymbolId, _args : &[Symbol<T>]) {}
+
+	// a syntax or semantic error occurred, also for errors that are recovered
+	fn on_error(&mut self, _error : &ParseError<T>) {}
+}
+
+
+/*
+ * origin of a debug message, used as target name for the log crate
+ */
+#[derive(Clone, Copy, Debug)]
+pub enum LogTarget
+{
+	Parser,                        // "lalr1::parser"
+	State(types::TIndex),          // "lalr1::state::<state>"
+	Rule(types::TSemanticId),      // "lalr1::rule::<semantic id>"
+}
+
+
+impl fmt::Display for LogTarget
+{
+	fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result
+	{
+		match self
+		{
+			LogTarget::Parser => write!(f, "lalr1::parser"),
+			LogTarget::State(state) => write!(f, "lalr1::state::{}", state),
+			LogTarget::Rule(rule) => write!(f, "lalr1::rule::{}", rule),
+		}
+	}
+}
+
+
+/*
+ * write a debug message of the given level,
+ * if compiled with --cfg 'feature="log"' the message is passed on to the log crate
+ * and filtered by the installed logger, otherwise it is printed if the debug level allows it
+ */
+#[cfg(feature = "log")]
+pub fn log_msg(_debug : DebugLevel, level : DebugLevel, target : LogTarget, msg : fmt::Arguments)
+{
+	let log_level = match level
+	{
+		DebugLevel::Off | DebugLevel::Errors => ::log::Level::Error,
+		DebugLevel::Actions => ::log::Level::Debug,
+		DebugLevel::Trace => ::log::Level::Trace,
+	};
+
+	if log_level <= ::log::max_level()
+	{
+		let target : String = target.to_string();
+		::log::log!(target: &target, log_level, "{}", msg);
+	}
+}
+
+
+#[cfg(all(not(feature = "log"), not(feature = "no_std")))]
+pub fn log_msg(debug : DebugLevel, level : DebugLevel, _target : LogTarget, msg : fmt::Arguments)
+{
+	if level == DebugLevel::Off || debug < level
+	{
+		return;
+	}
+
+	if level == DebugLevel::Errors
+	{
+		println!("Error: {}", msg);
+	}
+	else
+	{
+		println!("{}", msg);
+	}
+}
+
+
+/*
+ * without std there's nowhere to print to, use the log feature instead
+ */
+#[cfg(all(not(feature = "log"), feature = "no_std"))]
+pub fn log_msg(_debug : DebugLevel, _level : DebugLevel, _target : LogTarget, _msg : fmt::Arguments)
+{
+}
+
+
+/*
+ * get the string identifier of a nonterminal
+ */
+pub fn get_nonterm_name(id : types::TSymbolId) -> &'static str
+{
+	for entry in types::lalr1_tables::NONTERM_IDX.iter()
+	{
+		if entry.0 == id
+		{
+			return entry.2;
+		}
+	}
+
+	"<unknown>"
+}
+
+
+/*
+ * get the string identifier of a terminal
+ */
+pub fn get_term_name(id : types::TSymbolId) -> &'static str
+{
+	types::lalr1_tables::TERM_IDX.iter()
+		.find(|entry| entry.0 == id)
+		.map_or("<unknown>", |entry| entry.2)
+}
+
+
+/*
+ * get the grammar name of a terminal or nonterminal symbol
+ */
+pub fn get_symbol_name<T>(symbol : &Symbol<T>) -> Option<&'static str>
+{
+	let table : &[(types::TSymbolId, types::TIndex, &'static str)] = match symbol.is_term
+	{
+		true => &types::lalr1_tables::TERM_IDX,
+		false => &types::lalr1_tables::NONTERM_IDX,
+	};
+
+	table.iter().find(|entry| entry.0 == symbol.id).map(|entry| entry.2)
+}
+
+
+/*
+ * a terminal that is valid in a given state, e.g. for completions
+ */
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize))]
+pub struct ExpectedTerm
+{
+	pub id : types::TSymbolId,
+	pub name : &'static str,
+	pub doc : Option<&'static str>,   // human-readable description, if given in the grammar
+}
+
+
+/*
+ * only the id is read, the name and description are taken from the grammar
+ */
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for ExpectedTerm
+{
+	fn deserialize<D>(deserializer : D) -> Result<ExpectedTerm, D::Error>
+		where D : ::serde::Deserializer<'de>
+	{
+		#[derive(::serde::Deserialize)]
+		struct TermId
+		{
+			id : types::TSymbolId,
+		}
+
+		let term : TermId = TermId::deserialize(deserializer)?;
+		Ok(ExpectedTerm { id : term.id, name : get_term_name(term.id), doc : get_term_doc(term.id) })
+	}
+}
+
+
+/*
+ * get the terminals having a shift or reduce action in a state, ordered by table index,
+ * the rows of states with a default reduction are empty, see expected_terminals_on_stack()
+ */
+pub fn expected_terminals(state : types::TIndex) -> Vec<(types::TSymbolId, &'static str)>
+{
+	let tables = &types::lalr1_tables::TERM_IDX;
+	let shift = &types::lalr1_tables::SHIFT[state.idx()];
+	let reduce = &types::lalr1_tables::REDUCE[state.idx()];
+
+	let mut terms : Vec<&(types::TSymbolId, types::TIndex, &'static str)> = tables.iter()
+		.filter(|entry| shift[entry.1.idx()] != types::lalr1_tables::sentinels::ERR
+			|| reduce[entry.1.idx()] != types::lalr1_tables::sentinels::ERR)
+		.collect();
+
+	// order by table index
+	terms.sort_by_key(|entry| entry.1);
+	terms.iter().map(|entry| (entry.0, entry.2)).collect()
+}
+
+
+/*
+ * get the terminals that can really be shifted next on the given state stack,
+ * following the (default) reductions, e.g. for completions
+ */
+pub fn expected_terminals_on_stack(stack : &[types::TIndex]) -> Vec<(types::TSymbolId, &'static str)>
+{
+	let mut terms : Vec<&(types::TSymbolId, types::TIndex, &'static str)> =
+		types::lalr1_tables::TERM_IDX.iter()
+		.filter(|entry| simulate_shift(&mut stack.to_vec(), entry.1))
+		.collect();
+
+	// order by table index
+	terms.sort_by_key(|entry| entry.1);
+	terms.iter().map(|entry| (entry.0, entry.2)).collect()
+}
+
+
+/*
+ * run a prefix of the input through the tables, without semantic functions, and get
+ * the terminals that can follow it, e.g. for editor completion,
+ * the tokens with the given (sorted) ids are skipped
+ */
+pub fn complete_prefix<T : Clone>(prefix : &[Symbol<T>], skip : &[types::TSymbolId])
+	-> Result<Vec<ExpectedTerm>, Box<ParseError<T>>>
+{
+	let to_expected = |stack : &[types::TIndex]| -> Vec<ExpectedTerm>
+	{
+		expected_terminals_on_stack(stack).into_iter()
+			.map(|(id, name)| ExpectedTerm { id, name, doc : get_term_doc(id) })
+			.collect()
+	};
+
+	let mut stack : Vec<types::TIndex> = vec![types::lalr1_tables::START];
+	for (position, token) in prefix.iter().enumerate()
+	{
+		if skip.binary_search(&token.id).is_ok()
+		{
+			continue;
+		}
+
+		let top_state : types::TIndex = *stack.last().unwrap();
+		let kind : ParseErrorKind = match types::lalr1_tables::TERM_IDX.iter()
+			.find(|entry| entry.0 == token.id)
+		{
+			Some(entry) =>
+			{
+				// keep the stack as it was if the token can't be shifted
+				let mut next_stack : Vec<types::TIndex> = stack.clone();
+				if simulate_shift(&mut next_stack, entry.1)
+				{
+					stack = next_stack;
+					continue;
+				}
+				ParseErrorKind::NoAction
+			},
+			None => ParseErrorKind::InvalidTerminal,
+		};
+
+		let mut err : ParseError<T> = ParseError::new(kind, Some(top_state),
+			Some(token.clone()), position);
+		err.expected = to_expected(&stack);
+		return Err(Box::new(err));
+	}
+
+	Ok(to_expected(&stack))
+}
+
+
+/*
+ * run the input through the tables from the given state stack, without semantic functions,
+ * and find the longest prefix after which the end of the input would be accepted,
+ * the tokens with the given (sorted) ids are skipped
+ * @return the length of the prefix, None if no prefix containing a token matches
+ */
+pub fn longest_match<T>(input : &[Symbol<T>], stack : &[types::TIndex], skip : &[types::TSymbolId])
+	-> Option<usize>
+{
+	let term_index = |id : types::TSymbolId| types::lalr1_tables::TERM_IDX.iter()
+		.find(|entry| entry.0 == id).map(|entry| entry.1);
+	let end_idx : types::TIndex = term_index(types::lalr1_tables::sentinels::END)?;
+
+	let mut stack : Vec<types::TIndex> = stack.to_vec();
+	let mut longest : Option<usize> = None;
+	let mut num_shifted : usize = 0;
+
+	for (position, token) in input.iter().enumerate()
+	{
+		if skip.binary_search(&token.id).is_ok()
+		{
+			continue;
+		}
+
+		match term_index(token.id)
+		{
+			Some(term_idx) if simulate_shift(&mut stack, term_idx) => num_shifted += 1,
+			_ => break,
+		}
+
+		if num_shifted > 0 && simulate_shift(&mut stack.clone(), end_idx)
+		{
+			longest = Some(position + 1);
+		}
+	}
+
+	longest
+}
+
+
+/*
+ * region of a host input that was parsed as the requested nonterminal
+ */
+#[derive(Clone, Debug)]
+pub struct Island<T = types::TLVal>
+{
+	pub start : usize,        // input index of the first token
+	pub end : usize,          // input index after the last token
+	pub result : Symbol<T>,   // the parsed nonterminal
+}
+
+
+/*
+ * get the terminals having a shift or reduce action in a state with their descriptions
+ */
+pub fn get_expected_terms(state : types::TIndex) -> Vec<ExpectedTerm>
+{
+	expected_terminals(state).into_iter()
+		.map(|(id, name)| ExpectedTerm { id, name, doc : get_term_doc(id) })
+		.collect()
+}
+
+
+/*
+ * get the description of a terminal
+ */
+pub fn get_term_doc(id : types::TSymbolId) -> Option<&'static str>
+{
+	types::lalr1_tables::TERM_DOC.iter()
+		.find(|entry| entry.0 == id)
+		.map(|entry| entry.1)
+}
+
+
+/*
+ * get the description of a nonterminal
+ */
+pub fn get_nonterm_doc(id : types::TSymbolId) -> Option<&'static str>
+{
+	types::lalr1_tables::NONTERM_DOC.iter()
+		.find(|entry| entry.0 == id)
+		.map(|entry| entry.1)
+}
+
+
+/*
+ * get the source span covered by a sequence of symbols, e.g. the arguments of a rule
+ */
+pub fn get_span<T>(symbols : &[Symbol<T>]) -> Option<(usize, usize)>
+{
+	let start : Option<usize> = symbols.iter().find_map(|sym| sym.span).map(|span| span.0);
+	let end : Option<usize> = symbols.iter().rev().find_map(|sym| sym.span).map(|span| span.1);
+
+	match (start, end)
+	{
+		(Some(start), Some(end)) => Some((start, end)),
+		_ => None,
+	}
+}
+
+
+/*
+ * is the state the main starting state or one following the marker of a secondary start rule?
+ */
+fn is_start_state(state : types::TIndex) -> bool
+{
+	state == types::lalr1_tables::START ||
+		types::lalr1_tables::START_STATES.iter().any(|start| start.2 == state)
+}
+
+
+/*
+ * does the state reached after an item lead to the reduction of a rule "list -> list item",
+ * "num_syms" is the number of the rule's symbols seen so far, terminals following the item,
+ * e.g. separators, are shifted up to "max_terms" times
+ */
+fn appends_to_list(state : types::TIndex, list_idx : types::TIndex, num_syms : usize, max_terms : usize) -> bool
+{
+	let err = types::lalr1_tables::sentinels::ERR;
+	let acc = types::lalr1_tables::sentinels::ACC;
+
+	let reduces_list = |rule_idx : types::TIndex| rule_idx != err && rule_idx != acc &&
+		types::lalr1_tables::LHS_IDX[rule_idx.idx()] == list_idx &&
+		types::lalr1_tables::NUM_RHS_SYMS[rule_idx.idx()].idx() == num_syms;
+
+	if reduces_list(types::lalr1_tables::DEFAULT_REDUCE[state.idx()]) ||
+		types::lalr1_tables::REDUCE[state.idx()].iter().any(|rule_idx| reduces_list(*rule_idx))
+	{
+		return true;
+	}
+
+	max_terms > 0 && types::lalr1_tables::SHIFT[state.idx()].iter().any(|next_state|
+		*next_state != err && appends_to_list(*next_state, list_idx, num_syms + 1, max_terms - 1))
+}
+
+
+/*
+ * is the item that was just reduced a top-level item, i.e. not nested inside another construct?
+ * "states" is the state stack below the item, the item is top-level if it directly follows a
+ * start state, or if it follows a list which directly follows a start state and the item is
+ * appended to this list by a rule "list -> list item", possibly with separators after the item
+ */
+pub fn is_top_level_item(states : &[types::TIndex], item_id : types::TSymbolId) -> bool
+{
+	let err = types::lalr1_tables::sentinels::ERR;
+
+	let (state, below) : (types::TIndex, &[types::TIndex]) = match states.split_last()
+	{
+		Some((state, below)) => (*state, below),
+		None => return false,
+	};
+
+	if is_start_state(state)
+	{
+		return true;
+	}
+
+	// the list has to follow a start state
+	let start_state : types::TIndex = match below.last()
+	{
+		Some(start_state) if is_start_state(*start_state) => *start_state,
+		_ => return false,
+	};
+
+	let item_idx : types::TIndex = match types::lalr1_tables::NONTERM_IDX.iter().find(|entry| entry.0 == item_id)
+	{
+		Some(entry) => entry.1,
+		None => return false,
+	};
+
+	let item_state : types::TIndex = types::lalr1_tables::JUMP[state.idx()][item_idx.idx()];
+	if item_state == err
+	{
+		return false;
+	}
+
+	// the nonterminals leading from the start state to the state below the item are the list candidates
+	types::lalr1_tables::JUMP[start_state.idx()].iter().enumerate().any(|(list_idx, next_state)|
+		*next_state == state && list_idx != item_idx.idx() &&
+		appends_to_list(item_state, list_idx as types::TIndex, 2, 2))
+}
+
+
+/*
+ * get the states the symbols lead to from the main starting state,
+ * e.g. for a parser that doesn't keep a state stack
+ * @return None if the symbols can't follow each other
+ */
+pub fn get_goto_states<T>(symbols : &[Symbol<T>]) -> Option<Vec<types::TIndex>>
+{
+	let err = types::lalr1_tables::sentinels::ERR;
+	let mut states : Vec<types::TIndex> = vec![types::lalr1_tables::START];
+
+	for sym in symbols
+	{
+		let state : usize = states.last()?.idx();
+		let next_state : types::TIndex = match sym.is_term
+		{
+			true => types::lalr1_tables::TERM_IDX.iter().find(|entry| entry.0 == sym.id)
+				.map(|entry| types::lalr1_tables::SHIFT[state][entry.1.idx()])?,
+			false => types::lalr1_tables::NONTERM_IDX.iter().find(|entry| entry.0 == sym.id)
+				.map(|entry| types::lalr1_tables::JUMP[state][entry.1.idx()])?,
+		};
+
+		if next_state == err
+		{
+			return None;
+		}
+		states.push(next_state);
+	}
+
+	Some(states)
+}
+
+
+/*
+ * source text that is skipped by the lexer, e.g. whitespace and comments
+ */
+#[derive(Default, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Trivia
+{
+	pub text : String,
+	pub span : Option<(usize, usize)>,
+}
+
+
+/*
+ * node of a concrete syntax tree, built by the parser in tree mode
+ */
+#[derive(Default, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct ParseTree
+{
+	pub id : types::TSymbolId,                // terminal or nonterminal id
+	pub rule_id : Option<types::TSemanticId>, // reduced rule, None for terminals
+	pub strval : Option<String>,              // token string of a terminal
+	pub span : Option<(usize, usize)>,
+	pub children : Vec<ParseTree>,            // for terminal tokens: the error nodes before them
+	pub leading : Vec<Trivia>,                // trivia before a terminal
+	pub trailing : Vec<Trivia>,               // trivia after the last terminal, only for the root
+}
+
+
+impl ParseTree
+{
+	/*
+	 * create the node of a reduced rule, terminal arguments become leaves
+	 * keeping the trivia that the lexer has attached to their values
+	 */
+	pub fn node(rule_id : types::TSemanticId, lhs_id : types::TSymbolId,
+		args : TArgs<ParseTree>) -> ParseTree
+	{
+		let span : Option<(usize, usize)> = get_span(&args);
+		let mut children : Vec<ParseTree> = Vec::new();
+		for arg in args
+		{
+			ParseTree::add_child(&mut children, arg);
+		}
+
+		ParseTree { id : lhs_id, rule_id : Some(rule_id), strval : None,
+			span, children, leading : Vec::new(), trailing : Vec::new() }
+	}
+
+	/*
+	 * create an error node holding the input that the error recovery discarded
+	 */
+	pub fn error(discarded : Vec<Symbol<ParseTree>>) -> ParseTree
+	{
+		let span : Option<(usize, usize)> = get_span(&discarded);
+		let mut children : Vec<ParseTree> = Vec::new();
+		for symbol in discarded
+		{
+			ParseTree::add_child(&mut children, symbol);
+		}
+
+		ParseTree { id : types::lalr1_tables::sentinels::ERRTOK, rule_id : None, strval : None,
+			span, children, leading : Vec::new(), trailing : Vec::new() }
+	}
+
+	/*
+	 * discard handler of the tree mode, the error node is put before the next token
+	 * or, before the end of the input, after the children of the root
+	 */
+	pub fn discard(next : &mut Symbol<ParseTree>, discarded : Vec<Symbol<ParseTree>>)
+	{
+		next.val.children.push(ParseTree::error(discarded));
+	}
+
+	/*
+	 * add a symbol as child, terminals become leaves after their error nodes
+	 * and an error token becomes an error node
+	 */
+	fn add_child(children : &mut Vec<ParseTree>, symbol : Symbol<ParseTree>)
+	{
+		if !symbol.is_term
+		{
+			children.push(symbol.val);
+			return;
+		}
+
+		let mut val : ParseTree = symbol.val;
+
+		// the error token stands for the error node of the input it replaces
+		if symbol.id == types::lalr1_tables::sentinels::ERRTOK
+		{
+			let mut error : ParseTree = val.children.pop()
+				.unwrap_or_else(|| ParseTree::error(Vec::new()));
+			error.span = error.span.or(symbol.span);
+			children.push(error);
+			return;
+		}
+
+		children.append(&mut val.children);
+		children.push(ParseTree { id : symbol.id, rule_id : None, strval : symbol.strval,
+			span : symbol.span, children : Vec::new(), leading : val.leading,
+			trailing : Vec::new() });
+	}
+
+	pub fn is_leaf(&self) -> bool
+	{
+		self.rule_id.is_none() && !self.is_error()
+	}
+
+	/*
+	 * is this an error node for discarded input?
+	 */
+	pub fn is_error(&self) -> bool
+	{
+		self.rule_id.is_none() && self.id == types::lalr1_tables::sentinels::ERRTOK
+	}
+
+	/*
+	 * get the source text of the tree including its trivia
+	 */
+	pub fn to_source(&self) -> String
+	{
+		let mut source : String = String::new();
+		self.write_source(&mut source);
+		source
+	}
+
+	fn write_source(&self, source : &mut String)
+	{
+		for trivia in self.leading.iter()
+		{
+			source.push_str(&trivia.text);
+		}
+
+		if let Some(strval) = &self.strval
+		{
+			source.push_str(strval);
+		}
+
+		for child in self.children.iter()
+		{
+			child.write_source(source);
+		}
+
+		for trivia in self.trailing.iter()
+		{
+			source.push_str(&trivia.text);
+		}
+	}
+}
+
+
+/*
+ * convert lexer tokens for the tree mode, the source text between
+ * the token spans is attached to the following token as trivia
+ */
+pub fn to_tree_tokens<T>(source : &str, tokens : &[Symbol<T>]) -> Vec<Symbol<ParseTree>>
+{
+	let mut last_end : usize = 0;
+
+	tokens.iter().enumerate().map(|(idx, token)|
+	{
+		// the text after the last token belongs to the end token
+		let (start, end) : (usize, usize) = match token.span
+		{
+			Some(span) => span,
+			None if idx == tokens.len() - 1 => (source.len(), source.len()),
+			None => (last_end, last_end),
+		};
+
+		let mut leading : Vec<Trivia> = Vec::new();
+		if let Some(text) = source.get(last_end .. start).filter(|text| !text.is_empty())
+		{
+			leading.push(Trivia { text : text.to_string(), span : Some((last_end, start)) });
+		}
+		last_end = last_end.max(end);
+
+		Symbol { is_term : token.is_term, id : token.id,
+			val : ParseTree { leading, ..Default::default() },
+			strval : token.get_str(source).map(|strval| strval.to_string()), strid : token.strid,
+			attachment : token.attachment.clone(), span : token.span }
+	}).collect()
+}
+
+
+/*
+ * write the tree as s-expression, e.g. "(expr (expr 1) + (expr 2))"
+ */
+impl fmt::Display for ParseTree
+{
+	fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result
+	{
+		if self.is_leaf()
+		{
+			return match &self.strval
+			{
+				Some(strval) => write!(f, "{}", strval),
+				None => write!(f, "{}", get_term_name(self.id)),
+			};
+		}
+
+		match self.is_error()
+		{
+			true => write!(f, "(error")?,
+			false => write!(f, "({}", get_nonterm_name(self.id))?,
+		}
+		for child in self.children.iter()
+		{
+			write!(f, " {}", child)?;
+		}
+		write!(f, ")")
+	}
+}
+
+
+/*
+ * run time and number of calls of a semantic function
+ */
+#[derive(Default, Clone, Debug)]
+pub struct RuleProfileEntry
+{
+	pub lhs_id : types::TSymbolId,
+	pub time : Duration,         // accumulated wall time
+	pub calls : usize,           // calls for full matches
+	pub partial_calls : usize,   // calls for partial matches
+}
+
+
+/*
+ * accumulates the time spent in each semantic function during a parse
+ */
+#[derive(Default, Clone, Debug)]
+pub struct RuleProfile
+{
+	entries : HashMap<types::TSemanticId, RuleProfileEntry>,
+}
+
+
+impl RuleProfile
+{
+	pub fn new() -> RuleProfile
+	{
+		RuleProfile::default()
+	}
+
+
+	pub fn reset(&mut self)
+	{
+		self.entries.clear();
+	}
+
+
+	pub fn add(&mut self, rule_id : types::TSemanticId, lhs_id : types::TSymbolId,
+		done : bool, time : Duration)
+	{
+		let entry = self.entries.entry(rule_id).or_default();
+		entry.lhs_id = lhs_id;
+		entry.time += time;
+
+		if done
+		{
+			entry.calls += 1;
+		}
+		else
+		{
+			entry.partial_calls += 1;
+		}
+	}
+
+
+	pub fn get(&self, rule_id : types::TSemanticId) -> Option<&RuleProfileEntry>
+	{
+		self.entries.get(&rule_id)
+	}
+
+
+	/*
+	 * get the entries, the most expensive rules first
+	 */
+	pub fn get_sorted(&self) -> Vec<(types::TSemanticId, &RuleProfileEntry)>
+	{
+		let mut entries : Vec<(types::TSemanticId, &RuleProfileEntry)> =
+			self.entries.iter().map(|(id, entry)| (*id, entry)).collect();
+		entries.sort_by(|a, b| b.1.time.cmp(&a.1.time).then(a.0.cmp(&b.0)));
+		entries
+	}
+}
+
+
+impl fmt::Display for RuleProfile
+{
+	fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result
+	{
+		writeln!(f, "{:>8} {:<16} {:>10} {:>10} {:>14} {:>14}",
+			"rule", "lhs", "calls", "partials", "total [ms]", "mean [us]")?;
+
+		for (rule_id, entry) in self.get_sorted()
+		{
+			let num_calls : usize = entry.calls + entry.partial_calls;
+			let total : f64 = entry.time.as_secs_f64();
+			let mean : f64 = if num_calls > 0 { total / (num_calls as f64) } else { 0. };
+
+			writeln!(f, "{:>8} {:<16} {:>10} {:>10} {:>14.4} {:>14.4}",
+				rule_id, get_nonterm_name(entry.lhs_id), entry.calls, entry.partial_calls,
+				total * 1e3, mean * 1e6)?;
+		}
+
+		Ok(())
+	}
+}
+
+
+/*
+ * counts of the parser actions and maximum stack depths during a parse
+ */
+#[derive(Default, Clone, Debug)]
+pub struct ParseStats
+{
+	pub shifts : usize,
+	pub reductions : usize,
+	pub tokens : usize,             // consumed input tokens
+	pub max_state_depth : usize,
+	pub max_symbol_depth : usize,
+	rule_reductions : HashMap<types::TSemanticId, usize>,
+}
+
+
+impl ParseStats
+{
+	pub fn new() -> ParseStats
+	{
+		ParseStats::default()
+	}
+
+
+	pub fn reset(&mut self)
+	{
+		self.shifts = 0;
+		self.reductions = 0;
+		self.tokens = 0;
+		self.max_state_depth = 0;
+		self.max_symbol_depth = 0;
+		self.rule_reductions.clear();
+	}
+
+
+	pub fn add_token(&mut self)
+	{
+		self.tokens += 1;
+	}
+
+
+	pub fn add_shift(&mut self, state_depth : usize, symbol_depth : usize)
+	{
+		self.shifts += 1;
+		self.update_depth(state_depth, symbol_depth);
+	}
+
+
+	pub fn add_reduction(&mut self, rule_id : types::TSemanticId, state_depth : usize, symbol_depth : usize)
+	{
+		self.reductions += 1;
+		*self.rule_reductions.entry(rule_id).or_default() += 1;
+		self.update_depth(state_depth, symbol_depth);
+	}
+
+
+	pub fn update_depth(&mut self, state_depth : usize, symbol_depth : usize)
+	{
+		self.max_state_depth = self.max_state_depth.max(state_depth);
+		self.max_symbol_depth = self.max_symbol_depth.max(symbol_depth);
+	}
+
+
+	/*
+	 * get the number of reductions using a rule
+	 */
+	pub fn get_reductions(&self, rule_id : types::TSemanticId) -> usize
+	{
+		self.rule_reductions.get(&rule_id).cloned().unwrap_or(0)
+	}
+
+
+	/*
+	 * get the reductions per rule, the most frequent rules first
+	 */
+	pub fn get_sorted_reductions(&self) -> Vec<(types::TSemanticId, usize)>
+	{
+		let mut entries : Vec<(types::TSemanticId, usize)> =
+			self.rule_reductions.iter().map(|(id, num)| (*id, *num)).collect();
+		entries.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+		entries
+	}
+}
+
+
+impl fmt::Display for ParseStats
+{
+	fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result
+	{
+		writeln!(f, "tokens: {}, shifts: {}, reductions: {}",
+			self.tokens, self.shifts, self.reductions)?;
+		writeln!(f, "maximum depth of state stack: {}, symbol stack: {}",
+			self.max_state_depth, self.max_symbol_depth)?;
+
+		writeln!(f, "{:>8} {:>10}", "rule", "reductions")?;
+		for (rule_id, num) in self.get_sorted_reductions()
+		{
+			writeln!(f, "{:>8} {:>10}", rule_id, num)?;
+		}
+
+		Ok(())
+	}
+}
+
+
+/*
+ * parser action recorded by a ParseTrace
+ */
+#[derive(Clone, Debug)]
+pub enum TraceEvent
+{
+	// shifted terminal and entered state
+	Shift { state : types::TIndex, id : types::TSymbolId,
+		strval : Option<String>, span : Option<(usize, usize)> },
+
+	// reduced rule with its number of rhs symbols
+	Reduce { state : types::TIndex, rule_id : types::TSemanticId,
+		lhs_id : types::TSymbolId, num_args : usize },
+
+	// partially matched rule with the number of already seen rhs symbols
+	Partial { state : types::TIndex, rule_id : types::TSemanticId,
+		lhs_id : types::TSymbolId, num_args : usize },
+
+	Error { state : Option<types::TIndex>, msg : String },
+}
+
+
+/*
+ * observer recording the full action trace of a parse, e.g. to replay it in a visualisation,
+ * clones share the same trace, so one clone can be passed to the parser
+ */
+#[derive(Clone, Default)]
+pub struct ParseTrace
+{
+	events : TShared<Vec<TraceEvent>>,
+}
+
+
+impl ParseTrace
+{
+	pub fn new() -> ParseTrace
+	{
+		ParseTrace::default()
+	}
+
+
+	pub fn clear(&self)
+	{
+		lock_shared(&self.events).clear();
+	}
+
+
+	pub fn get_events(&self) -> Vec<TraceEvent>
+	{
+		lock_shared(&self.events).clone()
+	}
+
+
+	/*
+	 * serialise the trace as json array of events
+	 * @see https://en.wikipedia.org/wiki/JSON
+	 */
+	pub fn to_json(&self) -> String
+	{
+		let opt_str = |val : Option<String>| -> String
+		{
+			val.unwrap_or_else(|| "null".to_string())
+		};
+
+		let mut json : String = String::from("[\n");
+		let events = lock_shared(&self.events);
+
+		for (idx, event) in events.iter().enumerate()
+		{
+			json += &match event
+			{
+				TraceEvent::Shift { state, id, strval, span } => format!(
+					"\t{{ \"action\" : \"shift\", \"state\" : {}, \"symbol\" : {}, \"name\" : \"{}\", \
+					\"text\" : {}, \"span\" : {} }}",
+					state, id, json_escape(get_term_name(*id)),
+					opt_str(strval.as_ref().map(|strval| format!("\"{}\"", json_escape(strval)))),
+					opt_str(span.map(|span| format!("[ {}, {} ]", span.0, span.1)))),
+
+				TraceEvent::Reduce { state, rule_id, lhs_id, num_args } => format!(
+					"\t{{ \"action\" : \"reduce\", \"state\" : {}, \"rule\" : {}, \"lhs\" : {}, \
+					\"name\" : \"{}\", \"args\" : {} }}",
+					state, rule_id, lhs_id, json_escape(get_nonterm_name(*lhs_id)), num_args),
+
+				TraceEvent::Partial { state, rule_id, lhs_id, num_args } => format!(
+					"\t{{ \"action\" : \"partial\", \"state\" : {}, \"rule\" : {}, \"lhs\" : {}, \
+					\"name\" : \"{}\", \"args\" : {} }}",
+					state, rule_id, lhs_id, json_escape(get_nonterm_name(*lhs_id)), num_args),
+
+				TraceEvent::Error { state, msg } => format!(
+					"\t{{ \"action\" : \"error\", \"state\" : {}, \"message\" : \"{}\" }}",
+					opt_str(state.map(|state| state.to_string())), json_escape(msg)),
+			};
+
+			if idx + 1 < events.len()
+			{
+				json.push(',');
+			}
+			json.push('\n');
+		}
+
+		json.push(']');
+		json
+	}
+}
+
+
+impl<T> ParseObserver<T> for ParseTrace
+{
+	fn on_shift(&mut self, state : types::TIndex, token : &Symbol<T>)
+	{
+		lock_shared(&self.events).push(TraceEvent::Shift { state, id : token.id,
+			strval : token.strval.clone(), span : token.span });
+	}
+
+	fn on_reduce(&mut self, state : types::TIndex, rule_id : types::TSemanticId,
+		lhs_id : types::TSymbolId, args : &[Symbol<T>])
+	{
+		lock_shared(&self.events).push(TraceEvent::Reduce { state, rule_id,
+			lhs_id, num_args : args.len() });
+	}
+
+	fn on_partial(&mut self, state : types::TIndex, rule_id : types::TSemanticId,
+		lhs_id : types::TSymbolId, args : &[Symbol<T>])
+	{
+		lock_shared(&self.events).push(TraceEvent::Partial { state, rule_id,
+			lhs_id, num_args : args.len() });
+	}
+
+	fn on_error(&mut self, error : &ParseError<T>)
+	{
+		lock_shared(&self.events).push(TraceEvent::Error { state : error.state,
+			msg : error.to_string() });
+	}
+}
+
+
+/*
+ * escape a string for a json string literal
+ */
+fn json_escape(str : &str) -> String
+{
+	let mut escaped : String = String::new();
+
+	for ch in str.chars()
+	{
+		match ch
+		{
+			'"' => escaped += "\\\"",
+			'\\' => escaped += "\\\\",
+			'\n' => escaped += "\\n",
+			'\r' => escaped += "\\r",
+			'\t' => escaped += "\\t",
+			ch if (ch as u32) < 0x20 => escaped += &format!("\\u{:04x}", ch as u32),
+			ch => escaped.push(ch),
+		}
+	}
+
+	escaped
+}
+
+
+/*
+ * get the table index of a rule from its semantic id
+ */
+fn get_rule_idx(rule_id : types::TSemanticId) -> Option<usize>
+{
+	types::lalr1_tables::SEMANTIC_IDX.iter()
+		.find(|entry| entry.0 == rule_id)
+		.map(|entry| entry.1.idx())
+}
+
+
+/*
+ * get the string representation of a rule, e.g. "expr -> expr + expr"
+ */
+pub fn get_rule_str(rule_id : types::TSemanticId) -> &'static str
+{
+	get_rule_idx(rule_id)
+		.and_then(|rule_idx| types::lalr1_tables::RULE_STRS.get(rule_idx).copied())
+		.unwrap_or("<unknown>")
+}
+
+
+/*
+ * checked access to the arguments of a semantic function,
+ * an index beyond the rule's right-hand side is reported with the rule
+ */
+pub fn get_arg<T>(args : &TArgs<T>, rule_id : types::TSemanticId, idx : usize) -> &Symbol<T>
+{
+	match args.get(idx)
+	{
+		Some(arg) => arg,
+		None => panic!("Semantic rule {} \"{}\" accessed argument {}, but it only has {} argument(s).",
+			rule_id, get_rule_str(rule_id), idx, args.len()),
+	}
+}
+
+
+/*
+ * report a semantic function that gets more or, for a full match,
+ * fewer arguments than its rule has right-hand side symbols
+ */
+fn check_num_args(rule_id : types::TSemanticId, num_args : usize, rule_match : RuleMatch, debug : DebugLevel)
+{
+	let num_rhs : usize = match get_rule_idx(rule_id)
+	{
+		Some(rule_idx) => types::lalr1_tables::NUM_RHS_SYMS[rule_idx].idx(),
+		None => return,
+	};
+
+	if num_args > num_rhs || (rule_match.is_full() && num_args != num_rhs)
+	{
+		log_msg(debug, DebugLevel::Errors, LogTarget::Rule(rule_id), format_args!(
+			"Semantic rule {} \"{}\" gets {} argument(s), but its right-hand side has {} symbol(s) (full match: {}).",
+			rule_id, get_rule_str(rule_id), num_args, num_rhs, rule_match.is_full()));
+	}
+}
+
+
+/*
+ * the rule a semantic function is called for, and how the parser reports and profiles the call
+ */
+pub struct SemanticCall<'a>
+{
+	pub rule_id : types::TSemanticId,
+	pub lhs_id : types::TSymbolId,
+	pub debug : DebugLevel,
+	pub profile : Option<&'a mut RuleProfile>,
+}
+
+
+/*
+ * call a semantic function, in debug mode report rules whose
+ * arguments don't match their right-hand side
+ */
+#[cfg(not(feature = "no_std"))]
+pub fn call_semantics<T, C>(semantics : &mut TFallibleAction<T, C>, context : &mut C,
+	mut call : SemanticCall, args : TArgs<T>, rule_match : RuleMatch, retval : T) -> Result<T, SemanticError>
+{
+	// time the semantic function
+	if let Some(profile) = call.profile.take()
+	{
+		let (rule_id, lhs_id) = (call.rule_id, call.lhs_id);
+		let start = Instant::now();
+		let retval = call_semantics(semantics, context, call, args, rule_match, retval);
+		profile.add(rule_id, lhs_id, rule_match.is_full(), start.elapsed());
+		return retval;
+	}
+
+	if call.debug >= DebugLevel::Actions
+	{
+		check_num_args(call.rule_id, args.len(), rule_match, call.debug);
+	}
+
+	semantics(context, args, rule_match, retval)
+}
+
+
+/*
+ * call a semantic function, without std there's no clock for profiling
+ */
+#[cfg(feature = "no_std")]
+pub fn call_semantics<T, C>(semantics : &mut TFallibleAction<T, C>, context : &mut C,
+	call : SemanticCall, args : TArgs<T>, rule_match : RuleMatch, retval : T) -> Result<T, SemanticError>
+{
+	if call.debug >= DebugLevel::Actions
+	{
+		check_num_args(call.rule_id, args.len(), rule_match, call.debug);
+	}
+
+	semantics(context, args, rule_match, retval)
+}
+
+
+/*
+ * detects parsers that keep running without consuming any input,
+ * e.g. because of cyclic rules or a recovery strategy that doesn't advance
+ */
+#[derive(Default, Clone, Debug)]
+pub struct ProgressWatchdog
+{
+	input_index : usize,
+	steps : usize,
+	max_depth : usize,
+}
+
+
+impl ProgressWatchdog
+{
+	pub fn new() -> ProgressWatchdog
+	{
+		ProgressWatchdog { input_index : 0, steps : 0, max_depth : 0 }
+	}
+
+
+	pub fn reset(&mut self)
+	{
+		*self = ProgressWatchdog::new();
+	}
+
+
+	/*
+	 * register a parser step at the given input position and stack depth,
+	 * without a loop, every state can only be visited once per stack level
+	 * before input needs to be consumed
+	 * @return false if the parser doesn't make progress
+	 */
+	pub fn step(&mut self, input_index : usize, depth : usize) -> bool
+	{
+		if input_index != self.input_index
+		{
+			self.input_index = input_index;
+			self.steps = 0;
+			self.max_depth = depth;
+		}
+
+		self.steps += 1;
+		self.max_depth = self.max_depth.max(depth);
+
+		let num_states : usize = types::lalr1_tables::SHIFT.len();
+		self.steps <= (self.max_depth + 1) * num_states
+	}
+
+
+	/*
+	 * number of steps since the last consumed input
+	 */
+	pub fn get_steps(&self) -> usize
+	{
+		self.steps
+	}
+}
+
+
+/*
+ * limits protecting against adversarial inputs, None means unlimited
+ */
+#[derive(Default, Clone, Copy, Debug)]
+pub struct ParseLimits
+{
+	pub max_tokens : Option<usize>,      // number of read input tokens
+	pub max_reductions : Option<usize>,  // number of applied rules
+	pub timeout : Option<Duration>,      // wall-clock time since the start of the parse (ignored for no_std)
+	pub max_depth : Option<usize>,       // depth of the state stack or of the state function calls
+}
+
+
+/*
+ * checks the parse limits, exceeding one aborts the parse
+ * with a ParseErrorKind::LimitExceeded error
+ */
+#[derive(Clone, Debug)]
+pub struct LimitGuard
+{
+	limits : ParseLimits,
+	reductions : usize,
+	#[cfg(not(feature = "no_std"))]
+	start : Instant,
+}
+
+
+impl LimitGuard
+{
+	pub fn new(limits : ParseLimits) -> LimitGuard
+	{
+		LimitGuard
+		{
+			limits,
+			reductions : 0,
+			#[cfg(not(feature = "no_std"))]
+			start : Instant::now(),
+		}
+	}
+
+
+	pub fn reset(&mut self)
+	{
+		self.reductions = 0;
+		#[cfg(not(feature = "no_std"))]
+		{
+			self.start = Instant::now();
+		}
+	}
+
+
+	/*
+	 * check the limits before shifting the given number of read tokens
+	 * @return false if a limit is exceeded
+	 */
+	pub fn check_tokens(&self, num_tokens : usize) -> bool
+	{
+		self.limits.max_tokens.is_none_or(|max_tokens| num_tokens <= max_tokens)
+			&& self.in_time()
+	}
+
+
+	/*
+	 * count a reduction and check the limits
+	 * @return false if a limit is exceeded
+	 */
+	pub fn add_reduction(&mut self) -> bool
+	{
+		self.reductions += 1;
+
+		self.limits.max_reductions.is_none_or(|max_reductions| self.reductions <= max_reductions)
+			&& self.in_time()
+	}
+
+
+	/*
+	 * check the depth the state stack will have after the next action
+	 * @return false if the maximum depth is exceeded
+	 */
+	pub fn check_depth(&self, depth : usize) -> bool
+	{
+		self.limits.max_depth.is_none_or(|max_depth| depth <= max_depth)
+	}
+
+
+	#[cfg(not(feature = "no_std"))]
+	fn in_time(&self) -> bool
+	{
+		self.limits.timeout.is_none_or(|timeout| self.start.elapsed() <= timeout)
+	}
+
+
+	/*
+	 * without std there's no clock, the timeout is ignored
+	 */
+	#[cfg(feature = "no_std")]
+	fn in_time(&self) -> bool
+	{
+		true
+	}
+}
+
+
+/*
+ * error while unescaping a literal, pos is the byte offset
+ * of the offending character in the literal
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnescapeError
+{
+	pub pos : usize,
+	pub msg : String,
+}
+
+
+impl fmt::Display for UnescapeError
+{
+	fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result
+	{
+		write!(f, "{} at position {}", self.msg, self.pos)
+	}
+}
+
+
+/*
+ * read exactly the given number of hex digits
+ */
+fn unescape_hex(chars : &mut iter::Peekable<str::CharIndices>, num_digits : usize,
+	pos : usize) -> Result<u32, UnescapeError>
+{
+	let mut code : u32 = 0;
+	for _ in 0..num_digits
+	{
+		match chars.next()
+		{
+			Some((_, ch)) if ch.is_ascii_hexdigit() => code = code*16 + ch.to_digit(16).unwrap(),
+			Some((idx, ch)) => return Err(UnescapeError { pos : idx,
+				msg : format!("Invalid hex digit '{}' in escape sequence", ch) }),
+			None => return Err(UnescapeError { pos,
+				msg : "Unterminated escape sequence".to_string() }),
+		}
+	}
+
+	Ok(code)
+}
+
+
+/*
+ * convert a unicode code point to a char
+ */
+fn unescape_code(code : u32, pos : usize) -> Result<char, UnescapeError>
+{
+	match char::from_u32(code)
+	{
+		Some(ch) => Ok(ch),
+		None => Err(UnescapeError { pos,
+			msg : format!("Invalid unicode code point 0x{:x}", code) }),
+	}
+}
+
+
+/*
+ * replace the escape sequences in a string literal (without the quotes),
+ * supported are \n, \t, \r, \0, \\, \", \', \xHH, \uHHHH and \u{H...}
+ */
+pub fn unescape_str(str : &str) -> Result<String, UnescapeError>
+{
+	let mut result : String = String::with_capacity(str.len());
+	let mut chars = str.char_indices().peekable();
+
+	while let Some((pos, ch)) = chars.next()
+	{
+		if ch != '\\'
+		{
+			result.push(ch);
+			continue;
+		}
+
+		let (_, esc) = match chars.next()
+		{
+			Some(esc) => esc,
+			None => return Err(UnescapeError { pos,
+				msg : "Unterminated escape sequence".to_string() }),
+		};
+
+		match esc
+		{
+			'n' => result.push('\n'),
+			't' => result.push('\t'),
+			'r' => result.push('\r'),
+			'0' => result.push('\0'),
+			'\\' | '"' | '\'' => result.push(esc),
+
+			// byte-sized code, restricted to ascii
+			'x' =>
+			{
+				let code = unescape_hex(&mut chars, 2, pos)?;
+				if code > 0x7f
+				{
+					return Err(UnescapeError { pos,
+						msg : format!("Hex escape 0x{:x} is out of the ascii range", code) });
+				}
+				result.push(code as u8 as char);
+			}
+
+			// unicode code point, either as \uHHHH or as \u{H...}
+			'u' =>
+			{
+				let code = if let Some((_, '{')) = chars.peek()
+				{
+					chars.next();
+
+					let mut code : u32 = 0;
+					let mut num_digits : usize = 0;
+					loop
+					{
+						match chars.next()
+						{
+							Some((_, '}')) if num_digits > 0 => break,
+							Some((idx, ch)) if ch.is_ascii_hexdigit() =>
+							{
+								num_digits += 1;
+								if num_digits > 6
+								{
+									return Err(UnescapeError { pos : idx,
+										msg : "Too many digits in unicode escape sequence".to_string() });
+								}
+								code = code*16 + ch.to_digit(16).unwrap();
+							}
+							Some((idx, ch)) => return Err(UnescapeError { pos : idx,
+								msg : format!("Invalid character '{}' in unicode escape sequence", ch) }),
+							None => return Err(UnescapeError { pos,
+								msg : "Unterminated unicode escape sequence".to_string() }),
+						}
+					}
+					code
+				}
+				else
+				{
+					unescape_hex(&mut chars, 4, pos)?
+				};
+
+				result.push(unescape_code(code, pos)?);
+			}
+
+			_ => return Err(UnescapeError { pos,
+				msg : format!("Unknown escape sequence '\\{}'", esc) }),
+		}
+	}
+
+	Ok(result)
+}
+
+
+/*
+ * remove the quotes around a string or char literal and unescape it,
+ * error positions refer to the quoted literal
+ */
+pub fn unquote_str(str : &str, quote : char) -> Result<String, UnescapeError>
+{
+	if !str.starts_with(quote)
+	{
+		return Err(UnescapeError { pos : 0,
+			msg : format!("Literal does not start with {}", quote) });
+	}
+
+	// the closing quote must not be escaped
+	let inner : &str = &str[quote.len_utf8() ..];
+	let mut escaped : bool = false;
+	for (idx, ch) in inner.char_indices()
+	{
+		if escaped
+		{
+			escaped = false;
+		}
+		else if ch == '\\'
+		{
+			escaped = true;
+		}
+		else if ch == quote
+		{
+			if idx + ch.len_utf8() != inner.len()
+			{
+				return Err(UnescapeError { pos : quote.len_utf8() + idx + ch.len_utf8(),
+					msg : "Unexpected characters after the end of the literal".to_string() });
+			}
+
+			return unescape_str(&inner[.. idx]).map_err(|err| UnescapeError {
+				pos : err.pos + quote.len_utf8(), msg : err.msg });
+		}
+	}
+
+	Err(UnescapeError { pos : str.len(), msg : "Unterminated literal".to_string() })
+}
+
+
+/*
+ * unescape a quoted char literal, e.g. 'a' or '\u{3b1}'
+ */
+pub fn unescape_char(str : &str) -> Result<char, UnescapeError>
+{
+	let unescaped : String = unquote_str(str, '\'')?;
+
+	let mut chars = unescaped.chars();
+	match (chars.next(), chars.next())
+	{
+		(Some(ch), None) => Ok(ch),
+		(None, _) => Err(UnescapeError { pos : 1, msg : "Empty char literal".to_string() }),
+		_ => Err(UnescapeError { pos : 1, msg : "Char literal has more than one character".to_string() }),
+	}
+}
+
+
+/*
+ * error while parsing a number literal, span is the byte range
+ * of the offending part of the literal
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumberError
+{
+	pub span : (usize, usize),
+	pub msg : String,
+}
+
+
+impl NumberError
+{
+	/*
+	 * move the span by the position of the literal in the input
+	 */
+	pub fn offset(mut self, pos : usize) -> NumberError
+	{
+		self.span = (self.span.0 + pos, self.span.1 + pos);
+		self
+	}
+}
+
+
+impl fmt::Display for NumberError
+{
+	fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result
+	{
+		write!(f, "{} at position {}..{}", self.msg, self.span.0, self.span.1)
+	}
+}
+
+
+/*
+ * parse the digits of an integer literal in the given base,
+ * underscores can be used to separate the digits
+ */
+pub fn parse_int(str : &str, base : u32) -> Result<u64, NumberError>
+{
+	let mut val : u64 = 0;
+	let mut num_digits : usize = 0;
+
+	for (idx, ch) in str.char_indices()
+	{
+		if ch == '_' && num_digits > 0
+		{
+			continue;
+		}
+
+		let digit = match ch.to_digit(base)
+		{
+			Some(digit) => digit,
+			None => return Err(NumberError { span : (idx, idx + ch.len_utf8()),
+				msg : format!("Invalid digit '{}' for base {}", ch, base) }),
+		};
+
+		val = match val.checked_mul(base as u64).and_then(|val| val.checked_add(digit as u64))
+		{
+			Some(val) => val,
+			None => return Err(NumberError { span : (0, str.len()),
+				msg : format!("Integer literal \"{}\" exceeds the maximum value {}", str, u64::MAX) }),
+		};
+		num_digits += 1;
+	}
+
+	if num_digits == 0
+	{
+		return Err(NumberError { span : (0, str.len()), msg : "Integer literal has no digits".to_string() });
+	}
+
+	Ok(val)
+}
+
+
+/*
+ * parse an integer literal, the base is given by an optional prefix (0x, 0o or 0b)
+ */
+pub fn parse_int_literal(str : &str) -> Result<u64, NumberError>
+{
+	let lower : String = str.chars().take(2).collect::<String>().to_lowercase();
+	let base : u32 = match lower.as_str()
+	{
+		"0x" => 16,
+		"0o" => 8,
+		"0b" => 2,
+		_ => return parse_int(str, 10),
+	};
+
+	parse_int(&str[2..], base).map_err(|err| err.offset(2))
+}
+
+
+/*
+ * parse a floating-point literal, reporting values that are out of range
+ */
+pub fn parse_real_literal(str : &str) -> Result<f64, NumberError>
+{
+	let digits : String = str.chars().filter(|ch| *ch != '_').collect();
+	let val : f64 = match digits.parse::<f64>()
+	{
+		Ok(val) => val,
+		Err(_) => return Err(NumberError { span : (0, str.len()),
+			msg : format!("Invalid real literal \"{}\"", str) }),
+	};
+
+	if val.is_infinite()
+	{
+		return Err(NumberError { span : (0, str.len()),
+			msg : format!("Real literal \"{}\" is too large", str) });
+	}
+
+	// non-zero mantissa, but the value is zero
+	let has_mantissa : bool = digits.chars().take_while(|ch| *ch != 'e' && *ch != 'E')
+		.any(|ch| ch.is_ascii_digit() && ch != '0');
+	if val == 0. && has_mantissa
+	{
+		return Err(NumberError { span : (0, str.len()),
+			msg : format!("Real literal \"{}\" is too small", str) });
+	}
+
+	Ok(val)
+}
+
+
+/*
+ * convert an integer to a floating-point value, reporting a loss of precision
+ */
+pub fn int_to_real(val : u64, span : (usize, usize)) -> Result<f64, NumberError>
+{
+	let real : f64 = val as f64;
+	if real as u128 != val as u128
+	{
+		return Err(NumberError { span,
+			msg : format!("Integer {} cannot be represented exactly as a real value", val) });
+	}
+
+	Ok(real)
+}
+
+
+/*
+ * reasons for a failed parse
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum ParseErrorKind
+{
+	NoAction,            // no shift or reduce action for the lookahead
+	Conflict,            // unresolved shift/reduce conflict
+	InvalidTerminal,     // invalid terminal transition
+	InvalidNonterminal,  // invalid non-terminal transition
+	NoProgress,          // the parser doesn't consume any more input
+	UnexpectedEnd,       // the input ended before it was accepted
+	Suspended,           // the input is exhausted, parsing resumes once more tokens are appended
+	InvalidStart,        // the nonterminal is no start symbol of the grammar
+	Semantic,            // a semantic function returned an error
+	LimitExceeded,       // the token, reduction or time limit was exceeded
+	DepthExceeded,       // the input is nested too deeply
+}
+
+
+/*
+ * a single-token edit of the input that lets parsing continue
+ */
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub enum Repair
+{
+	Insert(ExpectedTerm),  // a terminal is missing before the offending token
+	Delete,                // the offending token is superfluous
+}
+
+
+/*
+ * run the parsing tables on a copy of the state stack,
+ * performing all reductions until the terminal is shifted or accepted
+ * @return false if the terminal leads to an error
+ */
+pub fn simulate_shift(stack : &mut Vec<types::TIndex>, term_idx : types::TIndex) -> bool
+{
+	let err = types::lalr1_tables::sentinels::ERR;
+	let acc = types::lalr1_tables::sentinels::ACC;
+
+	// each state can only be visited once per stack level without a shift
+	let max_steps : usize = (stack.len() + 1) * types::lalr1_tables::SHIFT.len();
+	for _ in 0 .. max_steps
+	{
+		let top_state : types::TIndex = match stack.last()
+		{
+			Some(state) => *state,
+			None => return false,
+		};
+
+		let mut new_state : types::TIndex = err;
+		let mut rule_idx : types::TIndex = types::lalr1_tables::DEFAULT_REDUCE[top_state.idx()];
+		if rule_idx == err
+		{
+			new_state = types::lalr1_tables::SHIFT[top_state.idx()][term_idx.idx()];
+			rule_idx = types::lalr1_tables::REDUCE[top_state.idx()][term_idx.idx()];
+		}
+
+		if (new_state == err && rule_idx == err) || (new_state != err && rule_idx != err)
+		{
+			return false;
+		}
+		else if rule_idx == acc || new_state != err
+		{
+			if new_state != err
+			{
+				stack.push(new_state);
+			}
+			return true;
+		}
+
+		// reduce
+		let num_rhs : usize = types::lalr1_tables::NUM_RHS_SYMS[rule_idx.idx()].idx();
+		if stack.len() <= num_rhs
+		{
+			return false;
+		}
+		stack.truncate(stack.len() - num_rhs);
+
+		let jump_state : types::TIndex = types::lalr1_tables::JUMP
+			[stack.last().unwrap().idx()][types::lalr1_tables::LHS_IDX[rule_idx.idx()].idx()];
+		if jump_state == err
+		{
+			return false;
+		}
+		stack.push(jump_state);
+	}
+
+	false
+}
+
+
+/*
+ * can the terminal continue the input, i.e. is it neither the end nor the error token?
+ */
+fn is_continuation(id : types::TSymbolId) -> bool
+{
+	id != types::lalr1_tables::sentinels::END && id != types::lalr1_tables::sentinels::ERRTOK
+}
+
+
+/*
+ * can any of the expected terminals actually be shifted on the given state stack?
+ */
+pub fn has_continuation(states : &[types::TIndex], expected : &[ExpectedTerm]) -> bool
+{
+	expected.iter().filter(|term| is_continuation(term.id)).any(|term|
+	{
+		match types::lalr1_tables::TERM_IDX.iter().find(|entry| entry.0 == term.id)
+		{
+			Some(entry) => simulate_shift(&mut states.to_vec(), entry.1),
+			None => false,
+		}
+	})
+}
+
+
+/*
+ * find a token to insert before the offending token or check if it can be deleted,
+ * next_idx is the table index of the token following the offending one,
+ * insertions after which the next token can also be parsed are preferred,
+ * otherwise the first one in the terminal table is taken
+ */
+pub fn find_repair(states : &[types::TIndex], expected : &[ExpectedTerm],
+	lookahead_idx : Option<types::TIndex>, next_idx : Option<types::TIndex>) -> Option<Repair>
+{
+	// insert a missing terminal, the offending token has to follow
+	if let Some(lookahead_idx) = lookahead_idx
+	{
+		// best candidate as (number of parsable following tokens, terminal)
+		let mut best : Option<(usize, &ExpectedTerm)> = None;
+
+		for entry in types::lalr1_tables::TERM_IDX.iter()
+		{
+			let term : &ExpectedTerm = match expected.iter().find(|term| term.id == entry.0)
+			{
+				Some(term) if is_continuation(term.id) => term,
+				_ => continue,
+			};
+
+			let mut stack : Vec<types::TIndex> = states.to_vec();
+			if !simulate_shift(&mut stack, entry.1) || !simulate_shift(&mut stack, lookahead_idx)
+			{
+				continue;
+			}
+
+			let num_parsed : usize = match next_idx
+			{
+				Some(next_idx) if simulate_shift(&mut stack, next_idx) => 2,
+				_ => 1,
+			};
+
+			if best.is_none_or(|(best_parsed, _)| num_parsed > best_parsed)
+			{
+				best = Some((num_parsed, term));
+			}
+		}
+
+		if let Some((_, term)) = best
+		{
+			return Some(Repair::Insert(term.clone()));
+		}
+	}
+
+	// delete the offending token, the next one has to be valid
+	if let Some(next_idx) = next_idx
+	{
+		let mut stack : Vec<types::TIndex> = states.to_vec();
+		if simulate_shift(&mut stack, next_idx)
+		{
+			return Some(Repair::Delete);
+		}
+	}
+
+	None
+}
+
+
+/*
+ * error information of a failed parse
+ */
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct ParseError<T = types::TLVal>
+{
+	pub kind : ParseErrorKind,
+	pub state : Option<types::TIndex>,  // state in which the error occurred
+	pub token : Option<Symbol<T>>,      // offending token
+	#[cfg_attr(feature = "serde", serde(skip_deserializing))]
+	pub token_name : Option<&'static str>,  // grammar name of the offending token's symbol
+	pub position : usize,               // index of the offending token in the input
+	pub expected : Vec<ExpectedTerm>,   // terminals that would have been valid
+	pub repair : Option<Repair>,        // suggested edit of the input, if any
+	pub incomplete : bool,              // the input ended, but more tokens could complete it
+	pub semantic : Option<(types::TSemanticId, SemanticError)>,  // failed rule and its error
+}
+
+
+impl<T> ParseError<T>
+{
+	pub fn new(kind : ParseErrorKind, state : Option<types::TIndex>,
+		token : Option<Symbol<T>>, position : usize) -> ParseError<T>
+	{
+		// only report expected terminals if the lookahead was the problem
+		let expected : Vec<ExpectedTerm> = match (kind, state)
+		{
+			(ParseErrorKind::NoAction, Some(state)) |
+			(ParseErrorKind::InvalidTerminal, Some(state)) |
+			(ParseErrorKind::UnexpectedEnd, Some(state)) |
+			(ParseErrorKind::Suspended, Some(state)) => get_expected_terms(state),
+			_ => Vec::new(),
+		};
+
+		// the input has ended if the end token or nothing was read
+		let at_end : bool = match (&token, kind)
+		{
+			(Some(token), _) => token.id == types::lalr1_tables::sentinels::END,
+			(None, ParseErrorKind::UnexpectedEnd) |
+			(None, ParseErrorKind::Suspended) => true,
+			_ => false,
+		};
+
+		// without a state the input ran out before the end token, so more could follow
+		let incomplete : bool = at_end && (state.is_none()
+			|| expected.iter().any(|term| is_continuation(term.id)));
+
+		let token_name : Option<&'static str> = token.as_ref().and_then(get_symbol_name);
+
+		ParseError { kind, state, token, token_name, position,
+			expected, repair : None, incomplete, semantic : None }
+	}
+
+	/*
+	 * error of a semantic function, the symbol is the nonterminal of the failed rule
+	 */
+	pub fn semantic(rule_id : types::TSemanticId, symbol : Symbol<T>, position : usize,
+		error : SemanticError) -> ParseError<T>
+	{
+		let mut err : ParseError<T> = ParseError::new(ParseErrorKind::Semantic, None, Some(symbol), position);
+		err.semantic = Some((rule_id, error));
+		err
+	}
+
+	/*
+	 * did the input end prematurely, i.e. could further input lines complete it?
+	 */
+	pub fn is_incomplete(&self) -> bool
+	{
+		self.incomplete
+	}
+}
+
+
+impl<T> fmt::Display for ParseError<T>
+{
+	fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result
+	{
+		let descr : &str = match self.kind
+		{
+			ParseErrorKind::NoAction => "No shift or reduce action defined",
+			ParseErrorKind::Conflict => "Unresolved shift/reduce conflict",
+			ParseErrorKind::InvalidTerminal => "Invalid terminal transition",
+			ParseErrorKind::InvalidNonterminal => "Invalid non-terminal transition",
+			ParseErrorKind::NoProgress => "Internal error: No progress",
+			ParseErrorKind::UnexpectedEnd => "Unexpected end of input",
+			ParseErrorKind::Suspended => "Parsing suspended at the end of the available input",
+			ParseErrorKind::InvalidStart => "No starting state for the given symbol",
+			ParseErrorKind::Semantic => "Semantic function failed",
+			ParseErrorKind::LimitExceeded => "Parse limit exceeded",
+			ParseErrorKind::DepthExceeded => "Maximum stack depth exceeded",
+		};
+		write!(f, "{}", descr)?;
+
+		if let Some(token) = &self.token
+		{
+			match (&token.strval, self.token_name)
+			{
+				(Some(strval), Some(name)) => write!(f, " for token \"{}\" ('{}')", strval, name)?,
+				(Some(strval), None) => write!(f, " for token \"{}\"", strval)?,
+				(None, Some(name)) => write!(f, " for symbol '{}'", name)?,
+				(None, None) => write!(f, " for symbol {}", token.id)?,
+			}
+		}
+
+		if let Some(state) = self.state
+		{
+			write!(f, " in state {}", state)?;
+		}
+
+		write!(f, " at input position {}", self.position)?;
+
+		if let Some((start, end)) = self.token.as_ref().and_then(|token| token.span)
+		{
+			write!(f, " (source {}..{})", start, end)?;
+		}
+
+		if let Some((rule_id, error)) = &self.semantic
+		{
+			write!(f, " in rule {}: {}", rule_id, error)?;
+		}
+
+		// expected terminals, e.g. "expected ')' or ','"
+		for (idx, term) in self.expected.iter().enumerate()
+		{
+			let sep : &str = if idx == 0 { ", expected " }
+				else if idx == self.expected.len() - 1 { " or " }
+				else { ", " };
+			write!(f, "{}'{}'", sep, term.name)?;
+		}
+
+		// suggested repair, e.g. "missing ')' before ','?"
+		let token_str : String = match self.token.as_ref().and_then(|token| token.strval.as_ref())
+		{
+			Some(strval) => format!("\"{}\"", strval),
+			None => "the token".to_string(),
+		};
+		match &self.repair
+		{
+			Some(Repair::Insert(term)) => write!(f, " (missing '{}' before {}?)", term.name, token_str)?,
+			Some(Repair::Delete) => write!(f, " (superfluous {}?)", token_str)?,
+			None => {},
+		}
+
+		Ok(())
+	}
+}
+
+
+/*
+ * a problem found by a tolerant parse, e.g. for the error list of an editor
+ */
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Diagnostic<T = types::TLVal>
+{
+	pub message : String,
+	pub span : Option<(usize, usize)>,  // source range of the offending token
+	pub position : usize,               // index of the offending token in the input
+	pub error : ParseError<T>,
+}
+
+
+impl<T : Clone> Diagnostic<T>
+{
+	pub fn from_error(error : &ParseError<T>) -> Diagnostic<T>
+	{
+		Diagnostic
+		{
+			message : error.to_string(),
+			span : error.token.as_ref().and_then(|token| token.span),
+			position : error.position,
+			error : error.clone(),
+		}
+	}
+}
+
+
+impl<T> fmt::Display for Diagnostic<T>
+{
+	fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result
+	{
+		match self.span
+		{
+			Some((start, end)) => write!(f, "{}..{}: {}", start, end, self.message),
+			None => write!(f, "{}", self.message),
+		}
+	}
+}
+
+
+pub trait Parsable<T = types::TLVal>
+{
+	fn set_semantics(&mut self, sema : &[(types::TSemanticId, TSemantics<T>)]);
+
+	// semantic functions as closures, set_semantic_actions replaces all rules,
+	// set_semantic_action adds or replaces the function of a single rule
+	fn set_semantic_actions(&mut self, sema : Vec<(types::TSemanticId, TSemanticAction<T>)>);
+	fn set_semantic_action(&mut self, rule_id : types::TSemanticId, action : TSemanticAction<T>);
+
+	// semantic function of a single rule that gets a RuleMatch instead of the full match flag
+	fn set_match_action(&mut self, rule_id : types::TSemanticId, action : TMatchAction<T>);
+
+	// semantic functions that can fail, replacing the ones of the given rules,
+	// an error aborts the parse, or is recorded like a syntax error if error recovery is enabled
+	fn set_fallible_semantics(&mut self, sema : &[(types::TSemanticId, TFallibleSemantics<T>)]);
+
+	// semantic function for all rules without an own one, e.g. to build a generic tree
+	fn set_default_action(&mut self, action : Option<TDefaultAction<T>>);
+	fn set_input(&mut self, input: &[Symbol<T>]);
+
+	// take over the input tokens without copying them
+	fn set_input_vec(&mut self, input : Vec<Symbol<T>>);
+
+	// intern the strings of the tokens that don't have a string id yet
+	fn set_interner(&mut self, interner : Option<TInterner>);
+
+	// receives the lookahead before each full reduction, None at the end of the input
+	fn set_lookahead_slot(&mut self, slot : Option<TLookahead<T>>);
+	fn set_input_stream(&mut self, input : Box<dyn Iterator<Item = Symbol<T>> + Send>);
+	fn append_input(&mut self, input : &[Symbol<T>]);
+
+	// terminals that the parser skips when reading the lookahead, e.g. comments or newlines
+	fn set_skip_terms(&mut self, terms : &[types::TSymbolId]);
+
+	// terminals, including keywords, that can follow the given tokens, e.g. for editor completion,
+	// an error is returned if the tokens are no valid prefix of the input
+	fn complete(&self, prefix : &[Symbol<T>]) -> Result<Vec<ExpectedTerm>, Box<ParseError<T>>>;
+	fn set_debug(&mut self, debug : DebugLevel);
+	fn set_partials(&mut self, use_partials : bool);
+	fn set_partials_policy(&mut self, policy : PartialsPolicy);
+
+	// rules that are only applied as a full match, e.g. ones with expensive or side-effecting semantics
+	fn set_no_partials(&mut self, rules : &[types::TSemanticId]);
+
+	// only check if the input is accepted without running any semantic functions,
+	// e.g. to validate test corpora or for fuzzing, the result then has a default value
+	fn set_recognize_only(&mut self, recognize_only : bool);
+	// pass the completed top-level items of the given nonterminal to the handler, e.g. for unbounded inputs,
+	// the item's value is moved to the handler, so that it doesn't accumulate on the symbol stack
+	fn set_item_handler(&mut self, handler : Option<(types::TSymbolId, TItemHandler<T>)>);
+	fn set_profiling(&mut self, profile : bool);
+	fn set_statistics(&mut self, stats : bool);
+	fn set_error_handler(&mut self, handler : TErrorHandler<T>);
+
+	// observer notified of every shift, reduction, partial rule and error, None removes it
+	fn set_observer(&mut self, observer : Option<Box<dyn ParseObserver<T>>>);
+
+	// terminals at which parsing resumes after an error, e.g. ';', '}' and the end token,
+	// an empty set disables the error recovery
+	fn set_sync_tokens(&mut self, sync : &[types::TSymbolId]);
+
+	// maximum number of tokens the recovery may skip before giving up, None for no limit
+	fn set_max_skipped_tokens(&mut self, max_skipped : Option<usize>);
+
+	// number of errors after which the recovery gives up, None for no limit
+	fn set_max_errors(&mut self, max_errors : Option<usize>);
+
+	// keep the symbols and tokens discarded by the error recovery instead of dropping them,
+	// e.g. for the error nodes of the tree mode
+	fn set_discard_handler(&mut self, handler : Option<TDiscardHandler<T>>);
+
+	// abort parsing after too many tokens or reductions, after a timeout or for too deep nesting
+	fn set_limits(&mut self, limits : ParseLimits);
+
+	// suspend parsing with a ParseErrorKind::Suspended error when the input runs out,
+	// it continues with the appended tokens on resume()
+	fn set_pausable(&mut self, pausable : bool);
+
+	fn get_end_id(&self) -> types::TSymbolId;
+	fn get_top_symbol(&self) -> Option<&Symbol<T>>;
+	fn get_profile(&self) -> Option<&RuleProfile>;
+	fn get_statistics(&self) -> Option<&ParseStats>;
+	fn get_errors(&self) -> &[ParseError<T>];
+
+	// the most recent error with its state, offending token and input position, if any
+	fn last_error(&self) -> Option<&ParseError<T>>
+	{
+		self.get_errors().last()
+	}
+
+	fn reset(&mut self);
+	// the errors are boxed, they are large compared to the parsed symbols
+	fn try_parse(&mut self) -> Result<Symbol<T>, Box<ParseError<T>>>;
+	fn resume(&mut self) -> Result<Symbol<T>, Box<ParseError<T>>>;
+
+	// move the accepted symbol out of the parser, e.g. after parse()
+	fn take_result(&mut self) -> Option<Symbol<T>>;
+
+	// read the tokens lazily, e.g. directly from a lexer
+	fn set_input_iter<I>(&mut self, input : I)
+		where I : Iterator<Item = Symbol<T>> + Send + 'static, Self : Sized
+	{
+		self.set_input_stream(Box::new(input));
+	}
+
+	// read the tokens from a custom lexer, token filter or recorded stream
+	fn set_token_source(&mut self, source : Box<dyn TokenSource<T> + Send>) where T : 'static
+	{
+		self.set_input_stream(Box::new(TokenSourceIter::new(source)));
+	}
+
+	fn parse(&mut self) -> bool
+	{
+		self.try_parse().is_ok()
+	}
+
+	// parse the whole input, recovering from the errors at the sync tokens or error productions,
+	// returns the result if the input could be accepted anyway and all problems that were found
+	fn parse_tolerant(&mut self) -> (Option<Symbol<T>>, Vec<Diagnostic<T>>) where T : Clone
+	{
+		let parsed : Result<Symbol<T>, Box<ParseError<T>>> = self.try_parse();
+
+		let mut diagnostics : Vec<Diagnostic<T>> = self.get_errors().iter()
+			.map(Diagnostic::from_error).collect();
+
+		let result : Option<Symbol<T>> = match parsed
+		{
+			Ok(symbol) => Some(symbol),
+			Err(error) =>
+			{
+				// errors that are not recorded, e.g. an empty input
+				if diagnostics.is_empty()
+				{
+					diagnostics.push(Diagnostic::from_error(&error));
+				}
+				self.take_result()
+			},
+		};
+
+		(result, diagnostics)
+	}
+}
+
+
+/*
+ * parse independent inputs in parallel, e.g. a large number of files,
+ * each worker thread creates its own parser and reuses it for its inputs,
+ * the parse tables are static and shared by all parsers
+ * @param num_threads number of worker threads, 0 to use the available parallelism
+ * @return the results of the parse function in the order of the inputs
+ */
+#[cfg(not(feature = "no_std"))]
+pub fn parse_batch<I, P, R, FCreate, FParse>(inputs : &[I], num_threads : usize,
+	create_parser : FCreate, parse : FParse) -> Vec<R>
+	where I : Sync, R : Send, FCreate : Fn() -> P + Sync, FParse : Fn(&mut P, &I) -> R + Sync
+{
+	let num_threads : usize = match num_threads
+	{
+		0 => thread::available_parallelism().map_or(1, |num| num.get()),
+		_ => num_threads,
+	}.min(inputs.len()).max(1);
+
+	// the workers take the next unparsed input
+	let next_input : &AtomicUsize = &AtomicUsize::new(0);
+	let create_parser : &FCreate = &create_parser;
+	let parse : &FParse = &parse;
+
+	let parsed : Vec<Vec<(usize, R)>> = thread::scope(|scope|
+	{
+		let workers : Vec<_> = (0..num_threads).map(|_| scope.spawn(move ||
+		{
+			let mut parser : P = create_parser();
+			let mut parsed : Vec<(usize, R)> = Vec::new();
+
+			loop
+			{
+				let idx : usize = next_input.fetch_add(1, Ordering::Relaxed);
+				if idx >= inputs.len()
+				{
+					break;
+				}
+
+				parsed.push((idx, parse(&mut parser, &inputs[idx])));
+			}
+
+			parsed
+		})).collect();
+
+		workers.into_iter().map(|worker| match worker.join()
+		{
+			Ok(parsed) => parsed,
+			Err(err) => resume_unwind(err),
+		}).collect()
+	});
+
+	// restore the input order
+	let mut results : Vec<Option<R>> = (0..inputs.len()).map(|_| None).collect();
+	for (idx, result) in parsed.into_iter().flatten()
+	{
+		results[idx] = Some(result);
+	}
+
+	results.into_iter().map(|result| result.unwrap()).collect()
+}
+
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+
+	type TLTerm = (types::TSymbolId, types::TIndex, &'static str);
+
+
+	/*
+	 * get the terminals that can be shifted on the given stack, without the end and error tokens
+	 */
+	fn shiftable_terms(stack : &[types::TIndex]) -> Vec<&'static TLTerm>
+	{
+		types::lalr1_tables::TERM_IDX.iter()
+			.filter(|entry| is_continuation(entry.0) && simulate_shift(&mut stack.to_vec(), entry.1))
+			.collect()
+	}
+
+
+	/*
+	 * find a terminal that can start the input and one that can follow it
+	 */
+	fn get_term_pair() -> (&'static TLTerm, &'static TLTerm)
+	{
+		for first in shiftable_terms(&[types::lalr1_tables::START])
+		{
+			let mut stack : Vec<types::TIndex> = vec![types::lalr1_tables::START];
+			simulate_shift(&mut stack, first.1);
+
+			if let Some(second) = shiftable_terms(&stack).first()
+			{
+				return (first, second);
+			}
+		}
+
+		panic!("the grammar has no input of two terminals");
+	}
+
+
+	#[test]
+	fn unescape_str_test()
+	{
+		assert_eq!(unescape_str("a\\tb\\n"), Ok("a\tb\n".to_string()));
+		assert_eq!(unescape_str("\\\\ \\\" \\' \\0"), Ok("\\ \" ' \0".to_string()));
+		assert_eq!(unescape_str("\\x41\\u00e4\\u{1F600}"), Ok("Aä😀".to_string()));
+
+		assert_eq!(unescape_str("ab\\q").unwrap_err().pos, 2);
+		assert_eq!(unescape_str("ab\\").unwrap_err().pos, 2);
+		assert_eq!(unescape_str("\\xff").unwrap_err().pos, 0);
+		assert_eq!(unescape_str("\\x4g").unwrap_err().pos, 3);
+		assert_eq!(unescape_str("\\u{d800}").unwrap_err().pos, 0);
+		assert_eq!(unescape_str("\\u{1234567}").unwrap_err().pos, 9);
+		assert_eq!(unescape_str("\\u{}").unwrap_err().pos, 3);
+		assert_eq!(unescape_str("\\u{12").unwrap_err().pos, 0);
+	}
+
+
+	#[test]
+	fn unquote_str_test()
+	{
+		assert_eq!(unquote_str("\"a\\\"b\"", '"'), Ok("a\"b".to_string()));
+		assert_eq!(unquote_str("\"\"", '"'), Ok(String::new()));
+		assert_eq!(unescape_char("'\\u{3b1}'"), Ok('α'));
+
+		// error positions refer to the quoted literal
+		assert_eq!(unquote_str("abc\"", '"').unwrap_err().pos, 0);
+		assert_eq!(unquote_str("\"abc", '"').unwrap_err().pos, 4);
+		assert_eq!(unquote_str("\"abc\\\"", '"').unwrap_err().pos, 6);
+		assert_eq!(unquote_str("\"a\"b", '"').unwrap_err().pos, 3);
+		assert_eq!(unquote_str("\"a\\qb\"", '"').unwrap_err().pos, 2);
+		assert!(unescape_char("'ab'").is_err());
+		assert!(unescape_char("''").is_err());
+	}
+
+
+	#[test]
+	fn parse_int_literal_test()
+	{
+		assert_eq!(parse_int_literal("1_234"), Ok(1234));
+		assert_eq!(parse_int_literal("0xfF"), Ok(255));
+		assert_eq!(parse_int_literal("0O17"), Ok(15));
+		assert_eq!(parse_int_literal("0b1_01"), Ok(5));
+		assert_eq!(parse_int_literal("18446744073709551615"), Ok(u64::MAX));
+
+		// spans of the offending digits, moved past the prefix
+		assert_eq!(parse_int_literal("12a").unwrap_err().span, (2, 3));
+		assert_eq!(parse_int_literal("0b102").unwrap_err().span, (4, 5));
+		assert_eq!(parse_int_literal("0x").unwrap_err().span, (2, 2));
+		assert_eq!(parse_int_literal("_1").unwrap_err().span, (0, 1));
+		assert_eq!(parse_int_literal("18446744073709551616").unwrap_err().span, (0, 20));
+	}
+
+
+	#[test]
+	fn parse_real_literal_test()
+	{
+		assert_eq!(parse_real_literal("1_000.5"), Ok(1000.5));
+		assert_eq!(parse_real_literal("2.5e-3"), Ok(0.0025));
+		assert_eq!(parse_real_literal("0.0e-999"), Ok(0.));
+
+		assert_eq!(parse_real_literal("1.2.3").unwrap_err().span, (0, 5));
+		assert!(parse_real_literal("1e999").unwrap_err().msg.contains("too large"));
+		assert!(parse_real_literal("1e-999").unwrap_err().msg.contains("too small"));
+	}
+
+
+	#[test]
+	fn simulate_shift_test()
+	{
+		let (first, second) = get_term_pair();
+
+		let mut stack : Vec<types::TIndex> = vec![types::lalr1_tables::START];
+		assert!(simulate_shift(&mut stack, first.1));
+		assert!(stack.len() > 1);
+		assert!(simulate_shift(&mut stack, second.1));
+
+		// not every terminal can start the input, an empty stack never shifts
+		assert!(types::lalr1_tables::TERM_IDX.iter()
+			.any(|entry| !simulate_shift(&mut vec![types::lalr1_tables::START], entry.1)));
+		assert!(!simulate_shift(&mut Vec::new(), first.1));
+	}
+
+
+	#[test]
+	fn find_repair_test()
+	{
+		let start : &[types::TIndex] = &[types::lalr1_tables::START];
+		let (first, second) = get_term_pair();
+
+		// a terminal is missing before the second one
+		match find_repair(start, &get_expected_terms(start[0]), Some(second.1), None)
+		{
+			Some(Repair::Insert(term)) =>
+			{
+				let term_idx : types::TIndex = types::lalr1_tables::TERM_IDX.iter()
+					.find(|entry| entry.0 == term.id).unwrap().1;
+				let mut stack : Vec<types::TIndex> = start.to_vec();
+				assert!(simulate_shift(&mut stack, term_idx) && simulate_shift(&mut stack, second.1));
+
+				// equally good insertions are taken in the order of the terminal table
+				assert!(shiftable_terms(start).iter()
+					.take_while(|entry| entry.1 != term_idx)
+					.all(|entry|
+					{
+						let mut stack : Vec<types::TIndex> = start.to_vec();
+						!simulate_shift(&mut stack, entry.1) || !simulate_shift(&mut stack, second.1)
+					}));
+			},
+			repair => panic!("expected an insertion, got {:?}", repair),
+		}
+
+		// the offending token can be deleted if the next one is valid
+		assert!(matches!(find_repair(start, &[], None, Some(first.1)), Some(Repair::Delete)));
+		assert!(find_repair(start, &[], Some(second.1), None).is_none());
+	}
+
+
+	#[test]
+	fn parse_error_incomplete_test()
+	{
+		let start : types::TIndex = types::lalr1_tables::START;
+		let end : Symbol = Symbol { is_term : true, id : types::lalr1_tables::sentinels::END, ..Default::default() };
+		let (first, _) = get_term_pair();
+		let token : Symbol = Symbol { is_term : true, id : first.0, ..Default::default() };
+
+		// the input ended where more tokens could follow
+		assert!(ParseError::new(ParseErrorKind::UnexpectedEnd, Some(start), Some(end.clone()), 0).is_incomplete());
+		assert!(ParseError::<types::TLVal>::new(ParseErrorKind::Suspended, Some(start), None, 0).is_incomplete());
+		assert!(ParseError::<types::TLVal>::new(ParseErrorKind::UnexpectedEnd, None, None, 0).is_incomplete());
+
+		// the offending token is no end of the input
+		assert!(!ParseError::new(ParseErrorKind::NoAction, Some(start), Some(token), 0).is_incomplete());
+		assert!(!ParseError::<types::TLVal>::new(ParseErrorKind::NoAction, Some(start), None, 0).is_incomplete());
+
+		// only the end token is expected, so further input cannot complete it
+		for state in 0 .. types::lalr1_tables::SHIFT.len() as types::TIndex
+		{
+			let expected : Vec<ExpectedTerm> = get_expected_terms(state);
+			if !expected.is_empty() && expected.iter().all(|term| !is_continuation(term.id))
+			{
+				assert!(!ParseError::new(ParseErrorKind::UnexpectedEnd, Some(state), Some(end.clone()), 0).is_incomplete());
+			}
+		}
+	}
 }
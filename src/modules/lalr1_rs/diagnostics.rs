@@ -0,0 +1,171 @@
+/*
+ * conversion of parser and lexer errors to codespan-reporting diagnostics
+ *
+ * @author Tobias Weber (orcid: 0000-0002-7230-1932)
+ * @date 15-oct-2026
+ * @license see 'LICENSE' file
+ *
+ * optional module, needs the codespan_reporting crate, e.g.:
+ *   #[cfg(feature = "codespan")] extern crate codespan_reporting;
+ *   #[cfg(feature = "codespan")] mod diagnostics;
+ */
+
+#![allow(unused)]
+
+use std::ops::Range;
+
+use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::files::SimpleFile;
+use codespan_reporting::term;
+use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
+
+use common::{Symbol, ParseError, ParseErrorKind, Repair, NumberError, UnescapeError};
+
+
+/*
+ * convert a symbol span to a byte range
+ */
+fn to_range(span : (usize, usize)) -> Range<usize>
+{
+	span.0 .. span.1.max(span.0)
+}
+
+
+/*
+ * label the source of a symbol, e.g. to report errors in semantic rules
+ */
+pub fn symbol_label<T, FileId>(sym : &Symbol<T>, file_id : FileId, msg : &str) -> Option<Label<FileId>>
+{
+	sym.span.map(|span| Label::primary(file_id, to_range(span)).with_message(msg))
+}
+
+
+/*
+ * convert a parse error to a diagnostic, the offending token is underlined
+ * and the expected terminals are given as notes
+ */
+pub fn parse_error_diagnostic<T, FileId>(err : &ParseError<T>, file_id : FileId) -> Diagnostic<FileId>
+{
+	let msg : &str = match err.kind
+	{
+		ParseErrorKind::NoAction => "unexpected token",
+		ParseErrorKind::Conflict => "unresolved shift/reduce conflict",
+		ParseErrorKind::InvalidTerminal => "invalid terminal transition",
+		ParseErrorKind::InvalidNonterminal => "invalid non-terminal transition",
+		ParseErrorKind::NoProgress => "internal error: no progress",
+		ParseErrorKind::UnexpectedEnd => "unexpected end of input",
+		ParseErrorKind::Suspended => "parsing suspended",
+		ParseErrorKind::InvalidStart => "invalid start symbol",
+		ParseErrorKind::Semantic => "semantic error",
+		ParseErrorKind::LimitExceeded => "parse limit exceeded",
+		ParseErrorKind::DepthExceeded => "input nested too deeply",
+	};
+
+	let mut diag = Diagnostic::error().with_message(msg);
+
+	// underline the offending token
+	if let Some(token) = &err.token
+	{
+		let label_msg : String = match (&token.strval, err.kind)
+		{
+			(_, ParseErrorKind::UnexpectedEnd) => "input ends here".to_string(),
+			(_, ParseErrorKind::Semantic) => match &err.semantic
+			{
+				Some((rule_id, error)) => format!("{} (rule {})", error, rule_id),
+				None => "semantic error".to_string(),
+			},
+			(Some(strval), _) => format!("unexpected \"{}\"", strval),
+			(None, _) => match err.token_name
+			{
+				Some(name) => format!("unexpected '{}'", name),
+				None => format!("unexpected symbol {}", token.id),
+			},
+		};
+
+		if let Some(label) = symbol_label(token, file_id, &label_msg)
+		{
+			diag = diag.with_labels(vec![label]);
+		}
+	}
+
+	// expected terminals, e.g. "expected ')' or ','"
+	let mut notes : Vec<String> = Vec::new();
+	if !err.expected.is_empty()
+	{
+		let mut expected : String = "expected ".to_string();
+		for (idx, term) in err.expected.iter().enumerate()
+		{
+			if idx > 0
+			{
+				expected += if idx == err.expected.len() - 1 { " or " } else { ", " };
+			}
+			expected += &format!("'{}'", term.name);
+		}
+		notes.push(expected);
+
+		for term in err.expected.iter()
+		{
+			if let Some(doc) = term.doc
+			{
+				notes.push(format!("'{}': {}", term.name, doc));
+			}
+		}
+	}
+
+	// suggested repair
+	match &err.repair
+	{
+		Some(Repair::Insert(term)) => notes.push(format!("help: insert '{}' before this token", term.name)),
+		Some(Repair::Delete) => notes.push("help: remove this token".to_string()),
+		None => {},
+	}
+
+	if let Some(state) = err.state
+	{
+		notes.push(format!("in parser state {}", state));
+	}
+
+	diag.with_notes(notes)
+}
+
+
+/*
+ * convert a number literal error to a diagnostic,
+ * the span has to be moved to the source position before, see NumberError::offset
+ */
+pub fn number_error_diagnostic<FileId>(err : &NumberError, file_id : FileId) -> Diagnostic<FileId>
+{
+	Diagnostic::error()
+		.with_message("invalid number literal")
+		.with_labels(vec![Label::primary(file_id, to_range(err.span)).with_message(err.msg.as_str())])
+}
+
+
+/*
+ * convert a string or char literal error to a diagnostic,
+ * pos is the source position of the literal
+ */
+pub fn unescape_error_diagnostic<FileId>(err : &UnescapeError, file_id : FileId, pos : usize) -> Diagnostic<FileId>
+{
+	Diagnostic::error()
+		.with_message("invalid literal")
+		.with_labels(vec![Label::primary(file_id, pos + err.pos .. pos + err.pos + 1).with_message(err.msg.as_str())])
+}
+
+
+/*
+ * print a diagnostic for a single source to stderr
+ */
+pub fn emit_diagnostic(name : &str, source : &str, diag : &Diagnostic<()>)
+{
+	let file = SimpleFile::new(name, source);
+	let writer = StandardStream::stderr(ColorChoice::Auto);
+	let config = term::Config::default();
+
+	// the lock has to outlive the result of emit, which borrows it
+	let mut lock = writer.lock();
+	if let Err(err) = term::emit(&mut lock, &config, &file, diag)
+	{
+		eprintln!("Error: Cannot emit diagnostic: {}.", err);
+	}
+}
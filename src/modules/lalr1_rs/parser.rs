@@ -11,88 +11,320 @@
  */
 
 
-use std::mem::take;
+#[cfg(not(feature = "no_std"))]
+use std::mem::{replace, swap, take};
+#[cfg(not(feature = "no_std"))]
 use std::collections::HashMap;
 
+#[cfg(feature = "no_std")]
+use core::mem::{replace, swap, take};
+#[cfg(feature = "no_std")]
+use alloc::collections::BTreeMap as HashMap;
+#[cfg(feature = "no_std")]
+use alloc::{vec::Vec, string::ToString, boxed::Box, format};
+
 use types::{*};
 use common::{*};
 
 
-pub struct Parser
+// number of tokens to shift after an error before further errors are reported
+const ERR_SHIFTS : usize = 3;
+
+
+/*
+ * snapshot of the parser state, e.g. for speculative parsing,
+ * with serde it can be stored to continue a suspended parse in another process
+ */
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct Checkpoint<T = TLVal>
+{
+	state : Vec<TIndex>,
+	symbol : Vec<Symbol<T>>,
+	active_rules : HashMap<TSemanticId, Vec<ActiveRule<T>>>,
+	cur_rule_handle : isize,
+
+	lookahead : Option<Symbol<T>>,
+	lookahead_index : TIndex,
+	next_input_index : usize,
+	input_len : Option<usize>,
+
+	num_errors : usize,
+	last_recovery : Option<usize>,
+	err_status : usize,
+	skipped_tokens : usize,
+	suspended : bool,
+}
+
+
+/*
+ * action the parser performs in its next step
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParserAction
+{
+	// shift the lookahead and go to the given state
+	Shift(TIndex),
+
+	// reduce using the rule id, the lhs id and the number of rhs symbols
+	Reduce(TSemanticId, TSymbolId, TIndex),
+
+	Accept,
+	Error,
+	Conflict,
+
+	// the input is exhausted
+	End,
+}
+
+
+pub struct Parser<T = TLVal, C = ()>
 {
 	// parser stacks
 	state : Vec<TIndex>,
-	symbol : Vec<Symbol>,
+	symbol : Vec<Symbol<T>>,
 
 	// index maps
-	map_term_idx : HashMap<TSymbolId, TIndex>,
-	map_nonterm_id : HashMap<TIndex, TSymbolId>,
-	map_semantic_id : HashMap<TIndex, TSemanticId>,
+	map_term_idx : IdIndexMap,
+	map_nonterm_id : Vec<TSymbolId>,
+	map_semantic_id : Vec<TSemanticId>,
 
 	// partial rules
-	active_rules : HashMap<TSemanticId, Vec<ActiveRule>>,
+	active_rules : HashMap<TSemanticId, Vec<ActiveRule<T>>>,
 	cur_rule_handle : isize,
 
 	// lookahead
-	lookahead : Option<Symbol>,
+	lookahead : Option<Symbol<T>>,
 	lookahead_index : TIndex,
 
 	// input tokens
-	input : Vec<Symbol>,
+	input : TokenInput<T>,
 	next_input_index : usize,
 
 	// semantic functions
-	semantics : HashMap<TSemanticId, TSemantics>,
+	semantics : SemanticRules<T, C>,
+	semantic_failure : Option<ParseError<T>>,
+
+	// user context passed to the semantic functions
+	context : C,
+
+	// handler for completed top-level items
+	item_handler : Option<(TSymbolId, TShared<TItemHandler<T>>)>,
+
+	// handler for parse errors
+	error_handler : Option<TShared<TErrorHandler<T>>>,
+
+	// error recovery
+	sync_tokens : Vec<TSymbolId>,
+	errors : Vec<ParseError<T>>,
+	last_recovery : Option<usize>,
+	errtok_index : Option<TIndex>,
+	err_status : usize,
+	max_skipped_tokens : Option<usize>,
+	skipped_tokens : usize,
+	max_errors : Option<usize>,
+
+	// input discarded by the error recovery since the last shift
+	discard_handler : Option<TShared<TDiscardHandler<T>>>,
+	discarded : Vec<Symbol<T>>,
+
+	// suspend parsing when the input is exhausted
+	pausable : bool,
+	suspended : bool,
+
+	// the result is on top of the symbol stack
+	accepted : bool,
+
+	// checkpoints for incremental reparsing
+	checkpoint_interval : Option<usize>,
+	checkpoints : Vec<Checkpoint<T>>,
 
-	debug : bool,
+	// marked positions to rewind to, e.g. for backtracking
+	marks : Vec<Checkpoint<T>>,
+
+	// marker terminal and starting state of a secondary start symbol
+	start : Option<(TSymbolId, TIndex)>,
+
+	// check that input is consumed
+	watchdog : ProgressWatchdog,
+
+	// token, reduction and time limits
+	limits : LimitGuard,
+
+	// time spent in the semantic functions
+	profile : Option<RuleProfile>,
+
+	// counts of the parser actions
+	stats : Option<ParseStats>,
+
+	// observer of the parser actions
+	observer : Option<TShared<Box<dyn ParseObserver<T>>>>,
+
+	// shared strings of the tokens
+	interner : Option<TInterner>,
+
+	// lookahead seen by the semantic functions
+	lookahead_slot : Option<TLookahead<T>>,
+
+	// terminals that are skipped in the input
+	skip_terms : Vec<TSymbolId>,   // sorted
+
+	debug : DebugLevel,
 	use_partials : bool,
+	partials_policy : PartialsPolicy,
+	no_partials : Vec<TSemanticId>,   // sorted
+	recognize_only : bool,
+
+	// release the symbols consumed by partial rules
+	drop_consumed : bool,
+}
+
+
+/*
+ * fork the parser, e.g. to try alternative tokenizations of an ambiguous lexeme,
+ * the copy continues from the same stacks, lookahead and active rules,
+ * the semantic functions, handlers and the observer are shared with the original,
+ * a streamed input is copied up to the already read tokens
+ */
+impl<T : Clone, C : Clone> Clone for Parser<T, C>
+{
+	fn clone(&self) -> Parser<T, C>
+	{
+		Parser
+		{
+			state : self.state.clone(),
+			symbol : self.symbol.clone(),
+
+			map_term_idx : self.map_term_idx.clone(),
+			map_nonterm_id : self.map_nonterm_id.clone(),
+			map_semantic_id : self.map_semantic_id.clone(),
+
+			active_rules : self.active_rules.clone(),
+			cur_rule_handle : self.cur_rule_handle,
+
+			lookahead : self.lookahead.clone(),
+			lookahead_index : self.lookahead_index,
+
+			semantics : self.semantics.clone(),
+			semantic_failure : self.semantic_failure.clone(),
+			context : self.context.clone(),
+			item_handler : self.item_handler.clone(),
+			error_handler : self.error_handler.clone(),
+			sync_tokens : self.sync_tokens.clone(),
+			errors : self.errors.clone(),
+			last_recovery : self.last_recovery,
+			errtok_index : self.errtok_index,
+			err_status : self.err_status,
+			max_skipped_tokens : self.max_skipped_tokens,
+			skipped_tokens : self.skipped_tokens,
+			max_errors : self.max_errors,
+			discard_handler : self.discard_handler.clone(),
+			discarded : self.discarded.clone(),
+			pausable : self.pausable,
+			suspended : self.suspended,
+			accepted : self.accepted,
+			checkpoint_interval : self.checkpoint_interval,
+			checkpoints : self.checkpoints.clone(),
+			marks : self.marks.clone(),
+			start : self.start,
+			watchdog : self.watchdog.clone(),
+			limits : self.limits.clone(),
+			profile : self.profile.clone(),
+			stats : self.stats.clone(),
+			observer : self.observer.clone(),
+			interner : self.interner.clone(),
+			lookahead_slot : self.lookahead_slot.clone(),
+			skip_terms : self.skip_terms.clone(),
+			input : self.input.clone(),
+			next_input_index : self.next_input_index,
+
+			debug : self.debug,
+			use_partials : self.use_partials,
+			partials_policy : self.partials_policy,
+			no_partials : self.no_partials.clone(),
+			recognize_only : self.recognize_only,
+			drop_consumed : self.drop_consumed,
+		}
+	}
 }
 
 
-impl Parser
+impl<T : Clone + Default + 'static, C : 'static> Parser<T, C>
 {
 	/*
 	 * creates a new parser object
 	 */
-	pub fn new() -> Parser
+	pub fn new() -> Parser<T, C> where C : Default
+	{
+		Parser::with_context(C::default())
+	}
+
+
+	/*
+	 * creates a new parser object with a user context for the semantic functions
+	 */
+	pub fn with_context(context : C) -> Parser<T, C>
 	{
-		let mut parser : Parser = Parser
+		let mut parser : Parser<T, C> = Parser
 		{
 			state : Vec::<TIndex>::new(),
-			symbol : Vec::<Symbol>::new(),
+			symbol : Vec::<Symbol<T>>::new(),
 
-			map_term_idx : HashMap::<TSymbolId, TIndex>::new(),
-			map_nonterm_id : HashMap::<TIndex, TSymbolId>::new(),
-			map_semantic_id : HashMap::<TIndex, TSemanticId>::new(),
+			map_term_idx : IdIndexMap::new(&lalr1_tables::TERM_IDX.iter()
+				.map(|entry| (entry.0, entry.1)).collect::<Vec<(TSymbolId, TIndex)>>()),
+			map_nonterm_id : get_index_ids(&lalr1_tables::NONTERM_IDX.iter()
+				.map(|entry| (entry.0, entry.1)).collect::<Vec<(TSymbolId, TIndex)>>()),
+			map_semantic_id : get_index_ids(&lalr1_tables::SEMANTIC_IDX),
 
-			active_rules : HashMap::<TSemanticId, Vec<ActiveRule>>::new(),
+			active_rules : HashMap::<TSemanticId, Vec<ActiveRule<T>>>::new(),
 			cur_rule_handle : 0,
 
 			lookahead : None,
-			lookahead_index : 0,
-
-			semantics : HashMap::<TSemanticId, TSemantics>::new(),
-			input : Vec::<Symbol>::new(),
+			lookahead_index : lalr1_tables::sentinels::ERR,
+
+			semantics : SemanticRules::new(),
+			semantic_failure : None,
+			context,
+			item_handler : None,
+			error_handler : None,
+			sync_tokens : Vec::<TSymbolId>::new(),
+			errors : Vec::<ParseError<T>>::new(),
+			last_recovery : None,
+			errtok_index : None,
+			err_status : 0,
+			max_skipped_tokens : None,
+			skipped_tokens : 0,
+			max_errors : None,
+			discard_handler : None,
+			discarded : Vec::new(),
+			pausable : false,
+			suspended : false,
+			accepted : false,
+			checkpoint_interval : None,
+			checkpoints : Vec::<Checkpoint<T>>::new(),
+			marks : Vec::<Checkpoint<T>>::new(),
+			start : None,
+			watchdog : ProgressWatchdog::new(),
+			limits : LimitGuard::new(ParseLimits::default()),
+			profile : None,
+			stats : None,
+			observer : None,
+			interner : None,
+			lookahead_slot : None,
+			skip_terms : Vec::new(),
+			input : TokenInput::new(),
 			next_input_index : 0,
 
-			debug : false,
+			debug : DebugLevel::Off,
 			use_partials : true,
+			partials_policy : PartialsPolicy::Both,
+			no_partials : Vec::new(),
+			recognize_only : false,
+			drop_consumed : false,
 		};
 
-		for term_idx in lalr1_tables::TERM_IDX
-		{
-			parser.map_term_idx.insert(term_idx.0, term_idx.1);
-		}
-
-		for nonterm_idx in lalr1_tables::NONTERM_IDX
-		{
-			parser.map_nonterm_id.insert(nonterm_idx.1, nonterm_idx.0);
-		}
-
-		for semantic_idx in lalr1_tables::SEMANTIC_IDX
-		{
-			parser.map_semantic_id.insert(semantic_idx.1, semantic_idx.0);
-		}
+		// the grammar has error productions
+		parser.errtok_index = parser.map_term_idx.get(lalr1_tables::sentinels::ERRTOK);
 
 		parser.reset();
 		parser
@@ -104,19 +336,9 @@ impl Parser
 	 */
 	fn get_term_table_index(&self, id : TSymbolId) -> TIndex
 	{
-		let idx : Option<&TIndex> = self.map_term_idx.get(&id);
+		let idx : Option<TIndex> = self.map_term_idx.get(id);
 		//println!("Terminal id={:?} -> idx={:?}", id, idx);
-		*idx.unwrap()
-	}
-
-
-	/**
-	 * get the table id from its index
-	 */
-	fn get_table_id(map : &HashMap::<usize, usize>, idx : TIndex) -> Option<&TSymbolId>
-	{
-		let id : Option<&TSymbolId> = map.get(&idx);
-		id
+		idx.unwrap()
 	}
 
 
@@ -125,8 +347,7 @@ impl Parser
 	 */
 	fn get_semantic_table_id(&self, idx : TIndex) -> TSemanticId
 	{
-		let id : Option<&TSemanticId> = Self::get_table_id(&self.map_semantic_id, idx);
-		*id.unwrap()
+		self.map_semantic_id[idx.idx()]
 	}
 
 
@@ -135,8 +356,7 @@ impl Parser
 	 */
 	fn get_nonterm_table_id(&self, idx : TIndex) -> TSymbolId
 	{
-		let id : Option<&TSymbolId> = Self::get_table_id(&self.map_nonterm_id, idx);
-		*id.unwrap()
+		self.map_nonterm_id[idx.idx()]
 	}
 
 
@@ -145,28 +365,88 @@ impl Parser
 	 */
         fn next_lookahead(&mut self)
         {
-		self.lookahead = Some(self.input[self.next_input_index].clone());
+		self.lookahead = self.input.cursor(&mut self.next_input_index, &self.skip_terms).next();
+
+		if let (Some(interner), Some(lookahead)) = (&self.interner, &mut self.lookahead)
+		{
+			lookahead.intern(&mut lock_shared(interner));
+		}
 		self.lookahead_index = self.get_term_table_index(
 			self.lookahead.as_ref().unwrap().id);
 
-		if self.debug
+		log_msg(self.debug, DebugLevel::Trace, LogTarget::Parser, format_args!(
+			"Lookahead: id {}, {:?}, input index: {}.", self.lookahead.as_ref().unwrap().id,
+			self.lookahead.as_ref().unwrap().strval, self.next_input_index - 1));
+
+		if let Some(stats) = &mut self.stats
 		{
-			println!("Lookahead: {:?}, input index: {}.",
-				self.lookahead, self.next_input_index);
+			stats.add_token();
 		}
-
-		self.next_input_index += 1;
 	}
 
 
 	/*
-	 * push the current lookahead token onto the symbol stack
-	 * and get the next lookahead
+	 * push the current lookahead token onto the symbol stack,
+	 * the next lookahead is only fetched once a state needs it
 	 */
         fn push_lookahead(&mut self)
         {
-		self.symbol.push(take(&mut self.lookahead).unwrap());
-		self.next_lookahead();
+		let mut token : Symbol<T> = take(&mut self.lookahead).unwrap();
+		self.flush_discarded(&mut token);
+		self.symbol.push(token);
+	}
+
+
+	/*
+	 * pass the input discarded by the error recovery on to the symbol shifted after it
+	 */
+	fn flush_discarded(&mut self, symbol : &mut Symbol<T>)
+	{
+		if self.discarded.is_empty()
+		{
+			return;
+		}
+
+		if let Some(handler) = &self.discard_handler
+		{
+			(*lock_shared(handler))(symbol, take(&mut self.discarded));
+		}
+	}
+
+
+	/*
+	 * pop the symbols above the given depth during error recovery,
+	 * keeping them before the discarded tokens if they are passed on
+	 */
+	fn discard_symbols(&mut self, depth : usize)
+	{
+		if self.discard_handler.is_some()
+		{
+			let popped : Vec<Symbol<T>> = self.symbol.split_off(depth);
+			self.discarded.splice(0 .. 0, popped);
+		}
+		else
+		{
+			self.symbol.truncate(depth);
+		}
+	}
+
+
+	/*
+	 * write the token following the reduced phrase into the lookahead slot,
+	 * peeking at the input if the lookahead hasn't been fetched yet
+	 */
+	fn update_lookahead_slot(&mut self)
+	{
+		if let Some(slot) = &self.lookahead_slot
+		{
+			let lookahead : Option<Symbol<T>> = match &self.lookahead
+			{
+				Some(lookahead) => Some(lookahead.clone()),
+				None => self.input.cursor(&mut self.next_input_index, &self.skip_terms).peek().cloned(),
+			};
+			*lock_shared(slot) = lookahead;
+		}
 	}
 
 
@@ -176,44 +456,63 @@ impl Parser
 	fn apply_rule(&mut self, rule_id : TSemanticId, num_rhs : TIndex, lhs_id : TSymbolId)
 	{
 		// remove fully reduced rule from active rule stack and get return value
-		let mut retval : TLVal = 0 as TLVal;
+		let mut retval : T = T::default();
 		let mut handle : isize = -1;
+		let mut complete : bool = false;
 		if self.use_partials
 		{
-			let rulestack : Option<&mut Vec<ActiveRule>> = self.active_rules.get_mut(&rule_id);
-			if rulestack.is_some() && !rulestack.as_ref().unwrap().is_empty()
+			let active_rule : Option<ActiveRule<T>> = self.active_rules.get_mut(&rule_id)
+				.and_then(|rulestack| rulestack.pop());
+			if let Some(active_rule) = active_rule
 			{
-				let active_rule = rulestack.unwrap().pop();
-				retval = active_rule.as_ref().unwrap().retval;
-				handle = active_rule.as_ref().unwrap().handle as isize;
+				// move the partial result instead of copying it
+				retval = active_rule.retval;
+				handle = active_rule.handle;
+				complete = active_rule.complete;
 			}
 		}
 
-		if self.debug
+		// the value has already been calculated by a partial match
+		let skip_semantics : bool = self.recognize_only ||
+			(complete && self.partials_policy == PartialsPolicy::Incremental);
+
+		if handle >= 0
 		{
-			print!("Applying rule {} with {} arguments", rule_id, num_rhs);
-			if handle >= 0
-			{
-				print!(" (handle {})", handle);
-			}
-			println!(".");
+			log_msg(self.debug, DebugLevel::Actions, LogTarget::Rule(rule_id), format_args!(
+				"Applying rule {} with {} arguments (handle {}).", rule_id, num_rhs, handle));
+		}
+		else
+		{
+			log_msg(self.debug, DebugLevel::Actions, LogTarget::Rule(rule_id), format_args!(
+				"Applying rule {} with {} arguments.", rule_id, num_rhs));
 		}
 
-		// get arguments
-		let mut args : Vec<Symbol> = Vec::<Symbol>::new();
-		args.reserve(num_rhs);
+		// get arguments by moving the top of the symbol stack
+		let top_state : TIndex = *self.state.last().unwrap();
+		let args : TArgs<T> = self.symbol.drain(self.symbol.len() - num_rhs.idx() ..).collect();
+		self.state.truncate(self.state.len() - num_rhs.idx());
 
-		for _i in 0..num_rhs
+		if let Some(observer) = &self.observer
 		{
-			args.insert(0, self.symbol.pop().unwrap());
-			self.state.pop();
+			lock_shared(observer).on_reduce(top_state, rule_id, lhs_id, &args);
 		}
 
 		// call semantic function
-		let semantics : Option<&TSemantics> = self.semantics.get(&rule_id);
-		if semantics != None
+		let span : Option<(usize, usize)> = get_span(&args);
+		if !skip_semantics
 		{
-			retval = (*semantics.unwrap())(args, true, retval as TLVal);
+			self.update_lookahead_slot();
+			match self.semantics.apply(&mut self.context,
+				SemanticCall { rule_id, lhs_id, debug : self.debug, profile : self.profile.as_mut() },
+				args, retval)
+			{
+				Ok(val) => retval = val,
+				Err(error) =>
+				{
+					retval = T::default();
+					self.semantic_error(rule_id, lhs_id, span, error);
+				},
+			}
 		}
 
 		// push result
@@ -222,15 +521,55 @@ impl Parser
 			id : lhs_id,
 			val : retval,
 			strval : None,
+			strid : None,
+			attachment : None,
+			span,
 		});
+
+		// pass on completed top-level items, the state below the item is on top of the stack
+		if let Some((item_id, handler)) = &self.item_handler
+		{
+			if lhs_id == *item_id && is_top_level_item(&self.state, *item_id)
+			{
+				let item : Symbol<T> = self.symbol.last_mut().unwrap().take_data();
+				(*lock_shared(handler))(item);
+			}
+		}
+	}
+
+
+	/*
+	 * remove all partial matches, keeping the allocated rule stacks for the next parse
+	 */
+	fn clear_active_rules(&mut self)
+	{
+		for rulestack in self.active_rules.values_mut()
+		{
+			rulestack.clear();
+		}
 	}
 
 
 	/*
-	 * partially apply a semantic rule with given id
+	 * partially apply a semantic rule with given id and number of right-hand side symbols
 	 */
-	fn apply_partial_rule(&mut self, rule_id : TSemanticId, arg_len : TIndex, before_shift : bool)
+	fn apply_partial_rule(&mut self, rule_id : TSemanticId, arg_len : TIndex, num_rhs : TIndex,
+		lhs_id : TSymbolId, before_shift : bool)
 	{
+		if self.recognize_only || self.no_partials.binary_search(&rule_id).is_ok()
+		{
+			return;
+		}
+
+		let (arg_len, num_rhs) : (usize, usize) = (arg_len.idx(), num_rhs.idx());
+
+		// partial matches covering the whole rule are followed by the full match
+		let policy : PartialsPolicy = self.partials_policy;
+		let run_partial = |len : usize| -> bool
+		{
+			len < num_rhs || policy != PartialsPolicy::FinalValue
+		};
+
 		let mut rule_len = arg_len;
 		if before_shift
 		{
@@ -243,7 +582,7 @@ impl Parser
 		let mut insert_new_active_rule : bool = false;
 		let mut seen_tokens_old : isize = -1;
 
-		let mut rulestack : Option<&mut Vec<ActiveRule>> = self.active_rules.get_mut(&rule_id);
+		let mut rulestack : Option<&mut Vec<ActiveRule<T>>> = self.active_rules.get_mut(&rule_id);
 		if rulestack.is_some()
 		{
 			if !rulestack.as_ref().unwrap().is_empty()
@@ -284,7 +623,7 @@ impl Parser
 		else
 		{
 			// no active rule yet
-			self.active_rules.insert(rule_id, Vec::<ActiveRule>::new());
+			self.active_rules.insert(rule_id, Vec::<ActiveRule<T>>::new());
 			rulestack = self.active_rules.get_mut(&rule_id);
 			insert_new_active_rule = true;
 		}
@@ -304,246 +643,1619 @@ impl Parser
 		if !already_seen_active_rule
 		{
 			// get semantic function
-			let semantics : Option<&TSemantics> = self.semantics.get(&rule_id);
-			if semantics == None
+			let semantics : TShared<TFallibleAction<T, C>> = match self.semantics.get(rule_id)
 			{
-				self.error(&format!("Semantic rule {0} is not defined.", rule_id));
-				return;
-			}
+				Some(semantics) => semantics.clone(),
+				None =>
+				{
+					// the default function is only run for the full match
+					if !self.semantics.has_default()
+					{
+						self.error(&format!("Semantic rule {0} is not defined.", rule_id));
+					}
+					return;
+				}
+			};
 
 			let active_rule = rulestack.as_mut().unwrap().last_mut().unwrap();
 
 			// get arguments for semantic rule
-			let mut args : Vec<Symbol> = Vec::<Symbol>::new();
-			args.reserve(rule_len);
+			let mut args : TArgs<T> = TArgs::from(&self.symbol[self.symbol.len() - arg_len ..]);
 
-			for _i in 0..arg_len
-			{
-				args.insert(0, self.symbol[self.symbol.len() - arg_len + _i].clone());
-			}
+			// source span and error of a failed semantic function
+			let mut failure : Option<(Option<(usize, usize)>, SemanticError)> = None;
+			let top_state : TIndex = *self.state.last().unwrap();
+			let mut consumed : bool = false;
 
-			if !before_shift || seen_tokens_old < (rule_len as isize - 1)
+			if (!before_shift || seen_tokens_old < (rule_len as isize - 1)) && run_partial(arg_len)
 			{
 				// run the semantic rule
-				if self.debug
+				log_msg(self.debug, DebugLevel::Actions, LogTarget::Rule(rule_id), format_args!(
+					"Partially applying rule {} with {} arguments (handle {}). Before shift: {}.",
+					rule_id, arg_len, active_rule.handle, before_shift));
+
+				if let Some(observer) = &self.observer
 				{
-					println!("Partially applying rule {} with {} arguments (handle {}). Before shift: {}.",
-						rule_id, arg_len, active_rule.handle, before_shift);
+					lock_shared(observer).on_partial(top_state, rule_id, lhs_id, &args);
 				}
 
-				active_rule.retval = (*semantics.unwrap())(
-					args.clone(), false, active_rule.retval as TLVal);
+				match call_semantics(&mut lock_shared(&semantics), &mut self.context,
+					SemanticCall { rule_id, lhs_id, debug : self.debug, profile : self.profile.as_mut() },
+					args.clone(), RuleMatch::Partial { seen : arg_len, before_shift : false },
+					take(&mut active_rule.retval))
+				{
+					Ok(retval) => active_rule.retval = retval,
+					Err(error) => failure = Some((get_span(&args), error)),
+				}
+				active_rule.complete = arg_len >= num_rhs;
+				consumed = true;
 			}
 
-			if before_shift
+			if before_shift && run_partial(rule_len)
 			{
 				// since we already know the next terminal in a shift, include it directly
 				args.push(self.lookahead.as_ref().unwrap().clone());
 
 				// run the semantic rule again
-				if self.debug
+				log_msg(self.debug, DebugLevel::Actions, LogTarget::Rule(rule_id), format_args!(
+					"Partially applying rule {} with {} arguments (handle {}). Before shift: {}.",
+					rule_id, rule_len, active_rule.handle, before_shift));
+
+				if let Some(observer) = &self.observer
 				{
-					println!("Partially applying rule {} with {} arguments (handle {}). Before shift: {}.",
-						rule_id, rule_len, active_rule.handle, before_shift);
+					lock_shared(observer).on_partial(top_state, rule_id, lhs_id, &args);
 				}
 
-				active_rule.retval = (*semantics.unwrap())(
-					args, false, active_rule.retval as TLVal);
+				let span : Option<(usize, usize)> = get_span(&args);
+				match call_semantics(&mut lock_shared(&semantics), &mut self.context,
+					SemanticCall { rule_id, lhs_id, debug : self.debug, profile : self.profile.as_mut() },
+					args, RuleMatch::Partial { seen : rule_len, before_shift : true },
+					take(&mut active_rule.retval))
+				{
+					Ok(retval) => active_rule.retval = retval,
+					Err(error) => failure = Some((span, error)),
+				}
+				active_rule.complete = rule_len >= num_rhs;
+				consumed = true;
 			}
-		}
-	}
 
+			if let Some((span, error)) = failure
+			{
+				self.semantic_error(rule_id, lhs_id, span, error);
+			}
 
-	fn error(&mut self, str : &str)
-	{
-		println!("Error: {}", str);
+			// the partial rule has consumed the data of the matched symbols
+			if self.drop_consumed && consumed
+			{
+				let len : usize = self.symbol.len();
+				for symbol in self.symbol[len - arg_len ..].iter_mut()
+				{
+					symbol.drop_data();
+				}
+
+				if let (true, Some(lookahead)) = (before_shift, &mut self.lookahead)
+				{
+					lookahead.drop_data();
+				}
+			}
+		}
 	}
-}
 
 
-impl Parsable for Parser
-{
 	/*
-	 * set the input tokens
+	 * report a failed semantic function, parsing stops unless error recovery is enabled
 	 */
-	fn set_input(&mut self, input: &[Symbol])
+	fn semantic_error(&mut self, rule_id : TSemanticId, lhs_id : TSymbolId,
+		span : Option<(usize, usize)>, error : SemanticError)
 	{
-		self.input = (*input).to_vec();
-	}
+		let position : usize = match self.lookahead
+		{
+			Some(_) => self.next_input_index - 1,
+			None => self.next_input_index,
+		};
 
+		let symbol : Symbol<T> = Symbol { is_term : false, id : lhs_id,
+			val : T::default(), strval : None, strid : None, attachment : None, span };
+		let err : ParseError<T> = ParseError::semantic(rule_id, symbol, position, error);
 
-	/*
-	 * enable debugging
-	 */
-	fn set_debug(&mut self, debug : bool)
-	{
-		self.debug = debug;
-	}
+		match &self.error_handler
+		{
+			Some(handler) => (*lock_shared(handler))(&err),
+			None => self.error(&format!("{}.", err)),
+		}
 
+		if let Some(observer) = &self.observer
+		{
+			lock_shared(observer).on_error(&err);
+		}
 
-	/**
-	 * enable application of partial semantic rules
-	 */
-	fn set_partials(&mut self, use_partials : bool)
-	{
-		self.use_partials = use_partials;
+		self.errors.push(err.clone());
+		if self.sync_tokens.is_empty() || self.too_many_errors()
+		{
+			self.semantic_failure = Some(err);
+		}
 	}
 
 
 	/*
-	 * set the semantic functions for the rules
+	 * create an error for the current lookahead
 	 */
-	fn set_semantics(&mut self, sema : &[(TSemanticId, TSemantics)])
+	fn parse_error(&mut self, kind : ParseErrorKind, state : TIndex) -> ParseError<T>
 	{
-		self.semantics.clear();
+		let position : usize = match self.lookahead
+		{
+			Some(_) => self.next_input_index - 1,
+			None => self.next_input_index,
+		};
 
-		for _i in 0..(*sema).len()
+		let mut err = ParseError::new(kind, Some(state), self.lookahead.clone(), position);
+
+		// suggest inserting or deleting a token
+		if kind == ParseErrorKind::NoAction && self.lookahead.is_some()
 		{
-			self.semantics.insert((*sema)[_i].0, (*sema)[_i].1);
+			let next_id : Option<TSymbolId> = self.input
+				.cursor(&mut self.next_input_index, &self.skip_terms)
+				.peek()
+				.map(|tok| tok.id);
+			let next_idx : Option<TIndex> = next_id
+				.and_then(|id| self.map_term_idx.get(id));
+			err.repair = find_repair(&self.state, &err.expected,
+				Some(self.lookahead_index), next_idx);
 		}
-	}
-
 
-	fn get_top_symbol(&self) -> Option<&Symbol>
-	{
-		if self.symbol.len() == 0
+		// check that the premature end could really be continued from the current stack
+		if err.incomplete && err.state.is_some()
 		{
-			None
+			err.incomplete = has_continuation(&self.state, &err.expected);
 		}
-		else
+
+		match &self.error_handler
 		{
-			self.symbol.last()
+			Some(handler) => (*lock_shared(handler))(&err),
+			None => self.error(&format!("{}.", err)),
 		}
-	}
 
+		if let Some(observer) = &self.observer
+		{
+			lock_shared(observer).on_error(&err);
+		}
 
-	fn get_end_id(&self) -> TSymbolId
-	{
-		lalr1_tables::END
+		self.errors.push(err.clone());
+		err
 	}
 
 
-	fn reset(&mut self)
+	/*
+	 * does the state have an action for the terminal with the given table index?
+	 */
+	fn has_action(state : TIndex, term_idx : TIndex) -> bool
 	{
-		self.next_input_index = 0;
-
-		self.lookahead = None;
-		self.lookahead_index = 0;
-
-		self.active_rules.clear();
-		self.cur_rule_handle = 0;
+		let err = lalr1_tables::sentinels::ERR;
 
-		self.symbol.clear();
-		self.state.clear();
-		self.state.push(lalr1_tables::START);
+		lalr1_tables::SHIFT[state.idx()][term_idx.idx()] != err
+			|| lalr1_tables::REDUCE[state.idx()][term_idx.idx()] != err
+			|| lalr1_tables::DEFAULT_REDUCE[state.idx()] != err
 	}
 
 
-	fn parse(&mut self) -> bool
+	/*
+	 * discard the lookahead during error recovery, either fetching the next one
+	 * directly or once a state needs it
+	 * @return false if the input ends or too many tokens were skipped
+	 */
+	fn skip_lookahead(&mut self, fetch_next : bool) -> bool
 	{
-		self.reset();
-		self.next_lookahead();
+		if self.input.cursor(&mut self.next_input_index, &self.skip_terms).peek().is_none()
+		{
+			return false;
+		}
 
-		while self.next_input_index <= self.input.len()
+		self.skipped_tokens += 1;
+		if let Some(max_skipped) = self.max_skipped_tokens
 		{
-			let top_state : TIndex = *self.state.last().unwrap();
+			if self.skipped_tokens > max_skipped
+			{
+				log_msg(self.debug, DebugLevel::Actions, LogTarget::Parser, format_args!(
+					"Recovery: giving up after skipping {} token(s).", max_skipped));
+				return false;
+			}
+		}
+
+		log_msg(self.debug, DebugLevel::Actions, LogTarget::Parser, format_args!(
+			"Recovery: discarding token {:?}.", self.lookahead.as_ref().unwrap().strval));
 
-			// main parsing tables
-			let shift = &lalr1_tables::SHIFT[top_state];
-			let reduce = &lalr1_tables::REDUCE[top_state];
+		let token : Option<Symbol<T>> = take(&mut self.lookahead);
+		if self.discard_handler.is_some()
+		{
+			self.discarded.extend(token);
+		}
 
-			// partial rule tables
-			let part_term = &lalr1_tables::PARTIALS_RULE_TERM;
-			let part_nonterm = &lalr1_tables::PARTIALS_RULE_NONTERM;
-			let part_term_len = &lalr1_tables::PARTIALS_MATCHLEN_TERM;
-			let part_nonterm_len = &lalr1_tables::PARTIALS_MATCHLEN_NONTERM;
+		if fetch_next
+		{
+			self.next_lookahead();
+		}
+		true
+	}
 
-			// constants
-			let err = lalr1_tables::ERR;
-			let acc = lalr1_tables::ACC;
 
-			let new_state : TIndex = shift[self.lookahead_index];
-			let rule_index : TIndex = reduce[self.lookahead_index];
+	/*
+	 * panic-mode error recovery: discard input tokens until a synchronisation token
+	 * and pop states until one of them has an action for it
+	 */
+	fn recover(&mut self) -> bool
+	{
+		if self.sync_tokens.is_empty() || self.too_many_errors()
+		{
+			return false;
+		}
 
-			if self.debug
+		loop
+		{
+			// discard tokens until a synchronisation token
+			while !self.sync_tokens.contains(&self.lookahead.as_ref().unwrap().id)
 			{
-				println!("Top state {}, new state {}, rule index {}, lookahead index {}.",
-					top_state, new_state, rule_index, self.lookahead_index);
+				if !self.skip_lookahead(true)
+				{
+					return false;
+				}
 			}
 
-			if new_state == err && rule_index == err
+			// a repeated error at the same synchronisation token: also discard it
+			let position : usize = self.next_input_index - 1;
+			if self.last_recovery != Some(position)
 			{
-				self.error(&format!("No shift or reduce action defined for state {0} and lookahead {1}.",
-					top_state, self.lookahead_index));
-				return false;
+				self.last_recovery = Some(position);
+				break;
 			}
-			else if new_state != err && rule_index != err
+
+			if !self.skip_lookahead(true)
 			{
-				self.error(&format!("Shift/reduce conflict for state {0} and lookahead {1}.",
-					top_state, self.lookahead_index));
 				return false;
 			}
+		}
 
-			// accept
-			else if rule_index == acc
+		// partial matches of the discarded rules are invalid
+		self.clear_active_rules();
+
+		// pop states until the synchronisation token can be parsed
+		let sync_idx : TIndex = self.lookahead_index;
+		match self.state.iter().rposition(|state| Self::has_action(*state, sync_idx))
+		{
+			Some(depth) =>
 			{
-				if self.debug
-				{
-					println!("Accepted.");
-				}
-				return true;
+				log_msg(self.debug, DebugLevel::Actions, LogTarget::Parser, format_args!(
+					"Recovery: resuming in state {} at token {:?}.",
+					self.state[depth], self.lookahead.as_ref().unwrap().strval));
+
+				self.state.truncate(depth + 1);
+				self.discard_symbols(depth);
 			}
 
-			// shift
-			if new_state != err
+			None =>
 			{
-				// partial rules
-				if self.use_partials
+				// no state can use the token, skip it and keep the stack
+				if !self.skip_lookahead(false)
 				{
-					let partial_idx = part_term[top_state][self.lookahead_index];
-					if partial_idx != err
-					{
-						let partial_id = self.get_semantic_table_id(partial_idx);
-						let partial_len = part_term_len[top_state][self.lookahead_index];
-
-						self.apply_partial_rule(partial_id, partial_len, true);
-					}
+					return false;
 				}
-
-				self.state.push(new_state);
-				self.push_lookahead();
 			}
+		}
+
+		true
+	}
+
+
+	/*
+	 * has the maximum number of errors been reached?
+	 */
+	fn too_many_errors(&self) -> bool
+	{
+		match self.max_errors
+		{
+			Some(max_errors) if self.errors.len() >= max_errors =>
+			{
+				log_msg(self.debug, DebugLevel::Errors, LogTarget::Parser, format_args!(
+					"Too many errors ({}), giving up.", self.errors.len()));
+				true
+			},
+			_ => false,
+		}
+	}
+
+
+	/*
+	 * yacc-style error recovery: pop states until the error token can be shifted
+	 */
+	fn recover_errtok(&mut self, errtok_idx : TIndex) -> bool
+	{
+		if self.too_many_errors()
+		{
+			return false;
+		}
+
+		// the error token covers the offending token
+		let span = self.lookahead.as_ref().and_then(|tok| tok.span);
+
+		// error directly after shifting the error token: discard the lookahead
+		if self.err_status == ERR_SHIFTS && !self.skip_lookahead(false)
+		{
+			return false;
+		}
+		self.err_status = ERR_SHIFTS;
+
+		// partial matches of the discarded rules are invalid
+		self.clear_active_rules();
+
+		let err = lalr1_tables::sentinels::ERR;
+		let depth = match self.state.iter().rposition(
+			|state| lalr1_tables::SHIFT[state.idx()][errtok_idx.idx()] != err)
+		{
+			Some(depth) => depth,
+			None => return false,
+		};
+
+		self.state.truncate(depth + 1);
+		self.discard_symbols(depth);
+
+		let new_state = lalr1_tables::SHIFT[self.state[depth].idx()][errtok_idx.idx()];
+		log_msg(self.debug, DebugLevel::Actions, LogTarget::Parser, format_args!(
+			"Recovery: shifting error token from state {} to state {}.",
+			self.state[depth], new_state));
+
+		let mut errtok : Symbol<T> = Symbol { is_term : true, id : lalr1_tables::sentinels::ERRTOK,
+			strval : Some("error".to_string()), span, ..Default::default() };
+		self.flush_discarded(&mut errtok);
+		self.symbol.push(errtok);
+		self.state.push(new_state);
+
+		true
+	}
+
+
+	/*
+	 * save the stacks, active rules and input position,
+	 * the input from this position on is kept until the next parse
+	 */
+	pub fn save_checkpoint(&mut self) -> Checkpoint<T>
+	{
+		// the lookahead is part of the checkpoint
+		self.input.pin(self.next_input_index);
+
+		Checkpoint
+		{
+			state : self.state.clone(),
+			symbol : self.symbol.clone(),
+			active_rules : self.active_rules.clone(),
+			cur_rule_handle : self.cur_rule_handle,
+
+			lookahead : self.lookahead.clone(),
+			lookahead_index : self.lookahead_index,
+			next_input_index : self.next_input_index,
+			input_len : self.input.len(),
+
+			num_errors : self.errors.len(),
+			last_recovery : self.last_recovery,
+			err_status : self.err_status,
+			skipped_tokens : self.skipped_tokens,
+			suspended : self.suspended,
+		}
+	}
+
+
+	/*
+	 * go back to a checkpoint of the current parse, the tokens appended
+	 * to a complete input after the checkpoint are dropped
+	 */
+	pub fn restore_checkpoint(&mut self, checkpoint : &Checkpoint<T>)
+	{
+		if let Some(input_len) = checkpoint.input_len
+		{
+			self.input.truncate(input_len);
+		}
+
+		self.restore_state(checkpoint);
+	}
+
+
+	/*
+	 * continue from a checkpoint saved by another parser, e.g. a deserialized one,
+	 * the current input holds the tokens from the checkpoint's input position on,
+	 * a suspended parse is then continued by resume()
+	 */
+	pub fn load_checkpoint(&mut self, checkpoint : &Checkpoint<T>)
+	{
+		self.reset();
+		self.input.start_at(checkpoint.next_input_index);
+		self.restore_state(checkpoint);
+	}
+
+
+	/*
+	 * mark the current input position, the tokens from there on are kept,
+	 * marks are nested, so that rewind() goes back to the last one
+	 * @return the input index of the mark
+	 */
+	pub fn mark(&mut self) -> usize
+	{
+		let checkpoint = self.save_checkpoint();
+		self.marks.push(checkpoint);
+		self.next_input_index
+	}
 
-			// reduce
-			else if rule_index != err
+
+	/*
+	 * go back to the last mark and remove it, the parser state is restored,
+	 * the tokens after the mark are read again, e.g. with another tokenization
+	 * @return false if there is no mark
+	 */
+	pub fn rewind(&mut self) -> bool
+	{
+		let mark : Checkpoint<T> = match self.marks.pop()
+		{
+			Some(mark) => mark,
+			None => return false,
+		};
+
+		self.restore_state(&mark);
+		self.semantic_failure = None;
+		self.discarded.clear();
+		self.accepted = false;
+		self.release_input();
+		true
+	}
+
+
+	/*
+	 * remove the last mark without going back to it, e.g. if the alternative succeeded
+	 * @return false if there is no mark
+	 */
+	pub fn release_mark(&mut self) -> bool
+	{
+		if self.marks.pop().is_none()
+		{
+			return false;
+		}
+
+		self.release_input();
+		true
+	}
+
+
+	/*
+	 * number of marks that can be rewound to
+	 */
+	pub fn num_marks(&self) -> usize
+	{
+		self.marks.len()
+	}
+
+
+	/*
+	 * stop keeping the input of a stream when no mark needs it anymore
+	 */
+	fn release_input(&mut self)
+	{
+		if !self.marks.is_empty() || self.checkpoint_interval.is_some()
+		{
+			return;
+		}
+
+		self.input.unpin();
+		self.input.release(self.next_input_index);
+	}
+
+
+	/*
+	 * parse the input as the given nonterminal, which needs a start rule in the grammar
+	 */
+	pub fn parse_as(&mut self, nonterm_id : TSymbolId) -> Result<Symbol<T>, Box<ParseError<T>>>
+	{
+		match lalr1_tables::START_STATES.iter().find(|start| start.0 == nonterm_id)
+		{
+			Some(start) => self.start = Some((start.1, start.2)),
+			None =>
+			{
+				self.error(&format!("No start rule for nonterminal {}.", nonterm_id));
+				return Err(Box::new(ParseError::new(ParseErrorKind::InvalidStart, None, None, 0)));
+			},
+		}
+
+		self.reset();
+		self.run()
+	}
+
+
+	/*
+	 * scan a host input for the longest regions that can be parsed as the given nonterminal,
+	 * which needs a start rule in the grammar, the tokens between the regions are skipped,
+	 * e.g. for code embedded in templates or comments
+	 */
+	pub fn parse_islands(&mut self, nonterm_id : TSymbolId, input : &[Symbol<T>])
+		-> Result<Vec<Island<T>>, Box<ParseError<T>>>
+	{
+		let (marker_id, start_state) : (TSymbolId, TIndex) =
+			match lalr1_tables::START_STATES.iter().find(|start| start.0 == nonterm_id)
+		{
+			Some(start) => (start.1, start.2),
+			None =>
+			{
+				self.error(&format!("No start rule for nonterminal {}.", nonterm_id));
+				return Err(Box::new(ParseError::new(ParseErrorKind::InvalidStart, None, None, 0)));
+			},
+		};
+
+		self.start = Some((marker_id, start_state));
+		let stack : [TIndex; 2] = [lalr1_tables::START, start_state];
+
+		let mut islands : Vec<Island<T>> = Vec::new();
+		let mut position : usize = 0;
+		while position < input.len()
+		{
+			// an island starts with a token of the nonterminal
+			let len : usize = match self.skip_terms.binary_search(&input[position].id)
 			{
-				let num_syms = lalr1_tables::NUM_RHS_SYMS[rule_index];
-				let lhs_index = lalr1_tables::LHS_IDX[rule_index];
-				let rule_id = self.get_semantic_table_id(rule_index);
-				let lhs_id = self.get_nonterm_table_id(lhs_index);
+				Ok(_) => 0,
+				Err(_) => longest_match(&input[position ..], &stack, &self.skip_terms).unwrap_or(0),
+			};
 
-				self.apply_rule(rule_id, num_syms, lhs_id);
-				let new_top_state = *self.state.last().unwrap();
+			if len == 0
+			{
+				position += 1;
+				continue;
+			}
 
-				// partial rules
-				if self.use_partials && self.symbol.len() > 0
+			// run the semantic functions on the matched region
+			self.input.assign(&input[position .. position + len]);
+			self.reset();
+			match self.run()
+			{
+				Ok(result) =>
 				{
-					let partial_idx = part_nonterm[new_top_state][lhs_index];
-					if partial_idx != err
-					{
-						let partial_id = self.get_semantic_table_id(partial_idx);
-						let partial_len = part_nonterm_len[new_top_state][lhs_index];
+					islands.push(Island { start : position, end : position + len, result });
+					position += len;
+				},
+
+				// a failed semantic function makes the region part of the gap
+				Err(_) => position += 1,
+			}
+		}
+
+		Ok(islands)
+	}
 
-						self.apply_partial_rule(partial_id, partial_len, false);
-					}
-				}		
 
-				let jump = &lalr1_tables::JUMP[new_top_state];
-				let jump_state : TIndex = jump[lhs_index];
-				self.state.push(jump_state);
+	/*
+	 * release the values, strings and attachments of the symbols on the stack once a
+	 * partial rule has seen them, so that the memory only depends on the stack depth
+	 * if the results are consumed by the partial rules, e.g. for streaming evaluation,
+	 * the full match of a rule then only gets the symbols not passed to a partial rule
+	 */
+	pub fn set_drop_consumed(&mut self, drop_consumed : bool)
+	{
+		self.drop_consumed = drop_consumed;
+	}
+
+
+	/*
+	 * record a checkpoint every given number of input tokens while parsing,
+	 * so that reparse() can continue from the last one before an edit
+	 */
+	pub fn set_incremental(&mut self, interval : Option<usize>)
+	{
+		self.checkpoint_interval = interval.filter(|interval| *interval > 0);
+	}
+
+
+	/*
+	 * replace the input tokens in the index range [start, end) and parse again,
+	 * starting from the last checkpoint before the edit, the semantic values
+	 * of the following symbols depend on the edit, so the rest of the input is parsed again
+	 */
+	pub fn reparse(&mut self, start : usize, end : usize, tokens : &[Symbol<T>]) -> Result<Symbol<T>, Box<ParseError<T>>>
+	{
+		if !self.input.replace(start, end, tokens)
+		{
+			self.error("Cannot edit the already consumed input.");
+			self.reset();
+			return self.run();
+		}
+
+		// the checkpoints after the edit are invalid
+		while let Some(checkpoint) = self.checkpoints.last()
+		{
+			if checkpoint.next_input_index <= start
+			{
+				break;
+			}
+			self.checkpoints.pop();
+		}
+
+		match self.checkpoints.pop()
+		{
+			Some(checkpoint) =>
+			{
+				self.restore_state(&checkpoint);
+				self.checkpoints.push(checkpoint);
+				self.run()
+			},
+			None =>
+			{
+				self.reset();
+				self.run()
+			},
+		}
+	}
+
+
+	/*
+	 * set the semantic functions which get the user context as first argument
+	 */
+	pub fn set_context_actions(&mut self, sema : Vec<(TSemanticId, TContextAction<T, C>)>)
+	{
+		self.semantics.clear();
+
+		for (rule_id, action) in sema
+		{
+			self.semantics.insert(rule_id, to_fallible_action(action));
+		}
+	}
+
+
+	pub fn set_context_action(&mut self, rule_id : TSemanticId, action : TContextAction<T, C>)
+	{
+		self.semantics.insert(rule_id, to_fallible_action(action));
+	}
+
+
+	/*
+	 * set a semantic function which gets the user context and can fail
+	 */
+	pub fn set_fallible_action(&mut self, rule_id : TSemanticId, action : TFallibleAction<T, C>)
+	{
+		self.semantics.insert(rule_id, action);
+	}
+
+
+	pub fn get_context(&self) -> &C
+	{
+		&self.context
+	}
+
+
+	pub fn get_context_mut(&mut self) -> &mut C
+	{
+		&mut self.context
+	}
+
+
+	/*
+	 * replace the user context, returning the previous one
+	 */
+	pub fn set_context(&mut self, context : C) -> C
+	{
+		replace(&mut self.context, context)
+	}
+
+
+	/*
+	 * parse with the given user context, it is only lent to the parser during the parse
+	 */
+	pub fn parse_with_context(&mut self, context : &mut C) -> Result<Symbol<T>, Box<ParseError<T>>>
+	{
+		swap(&mut self.context, context);
+		let result : Result<Symbol<T>, Box<ParseError<T>>> = self.try_parse();
+		swap(&mut self.context, context);
+		result
+	}
+
+
+	/*
+	 * get the terminals that can follow the input parsed so far
+	 */
+	pub fn get_expected_terminals(&self) -> Vec<(TSymbolId, &'static str)>
+	{
+		expected_terminals_on_stack(&self.state)
+	}
+
+
+	/*
+	 * start a new parse which is performed one action at a time
+	 */
+	pub fn stepper(&mut self) -> ParserStepper<'_, T, C>
+	{
+		self.start = None;
+		self.reset();
+		ParserStepper { parser : self, result : None }
+	}
+
+
+	/*
+	 * set the parser state to the one of the checkpoint, the input is not changed
+	 */
+	fn restore_state(&mut self, checkpoint : &Checkpoint<T>)
+	{
+		self.state = checkpoint.state.clone();
+		self.symbol = checkpoint.symbol.clone();
+		self.active_rules = checkpoint.active_rules.clone();
+		self.cur_rule_handle = checkpoint.cur_rule_handle;
+
+		self.lookahead = checkpoint.lookahead.clone();
+		self.lookahead_index = checkpoint.lookahead_index;
+		self.next_input_index = checkpoint.next_input_index;
+
+		self.errors.truncate(checkpoint.num_errors);
+		self.last_recovery = checkpoint.last_recovery;
+		self.err_status = checkpoint.err_status;
+		self.skipped_tokens = checkpoint.skipped_tokens;
+		self.suspended = checkpoint.suspended;
+		self.watchdog.reset();
+
+		log_msg(self.debug, DebugLevel::Actions, LogTarget::Parser, format_args!(
+			"Restored checkpoint at input index {}.", self.next_input_index));
+	}
+
+
+	fn error(&mut self, str : &str)
+	{
+		log_msg(self.debug, DebugLevel::Errors, LogTarget::Parser, format_args!("{}", str));
+	}
+
+
+	/*
+	 * run the parser and move the result out of the symbol stack
+	 */
+	fn run(&mut self) -> Result<Symbol<T>, Box<ParseError<T>>>
+	{
+		self.run_steps()?;
+		Ok(self.take_result().unwrap_or_default())
+	}
+
+
+	/*
+	 * run the parser until the input is accepted, an error occurs or the input is exhausted,
+	 * the accepted result is left on top of the symbol stack
+	 */
+	fn run_steps(&mut self) -> Result<(), Box<ParseError<T>>>
+	{
+		loop
+		{
+			if let Some(result) = self.step()
+			{
+				return result.map_err(Box::new);
+			}
+		}
+	}
+
+
+	/*
+	 * look up the new state and the rule index for the top state,
+	 * fetches the lookahead if needed, returns None if the input is exhausted
+	 */
+	fn lookup_action(&mut self, top_state : TIndex) -> Option<(TIndex, TIndex)>
+	{
+		let err = lalr1_tables::sentinels::ERR;
+
+		// states with a default reduction don't need the lookahead
+		let mut new_state : TIndex = err;
+		let mut rule_index : TIndex = lalr1_tables::DEFAULT_REDUCE[top_state.idx()];
+		if rule_index == err
+		{
+			if self.lookahead.is_none()
+			{
+				self.input.cursor(&mut self.next_input_index, &self.skip_terms).peek()?;
+				self.next_lookahead();
 			}
+
+			new_state = lalr1_tables::SHIFT[top_state.idx()][self.lookahead_index.idx()];
+			rule_index = lalr1_tables::REDUCE[top_state.idx()][self.lookahead_index.idx()];
 		}
 
-		false
+		log_msg(self.debug, DebugLevel::Trace, LogTarget::State(top_state), format_args!(
+			"Top state {}, new state {}, rule index {}, lookahead index {}.",
+			top_state, new_state, rule_index, self.lookahead_index));
+
+		Some((new_state, rule_index))
+	}
+
+
+	/*
+	 * get the action the next step will perform
+	 */
+	fn get_pending_action(&mut self) -> ParserAction
+	{
+		let err = lalr1_tables::sentinels::ERR;
+		let acc = lalr1_tables::sentinels::ACC;
+
+		let top_state : TIndex = *self.state.last().unwrap();
+		let (new_state, rule_index) : (TIndex, TIndex) = match self.lookup_action(top_state)
+		{
+			Some(action) => action,
+			None => return ParserAction::End,
+		};
+
+		if new_state == err && rule_index == err
+		{
+			ParserAction::Error
+		}
+		else if new_state != err && rule_index != err
+		{
+			ParserAction::Conflict
+		}
+		else if rule_index == acc
+		{
+			ParserAction::Accept
+		}
+		else if new_state != err
+		{
+			ParserAction::Shift(new_state)
+		}
+		else
+		{
+			ParserAction::Reduce(self.get_semantic_table_id(rule_index),
+				self.get_nonterm_table_id(lalr1_tables::LHS_IDX[rule_index.idx()]),
+				lalr1_tables::NUM_RHS_SYMS[rule_index.idx()])
+		}
+	}
+
+
+	/*
+	 * perform a single parser action,
+	 * returns the result once the input is accepted or parsing stops
+	 */
+	fn step(&mut self) -> Option<Result<(), ParseError<T>>>
+	{
+		// a semantic function failed without error recovery
+		if let Some(err) = self.semantic_failure.take()
+		{
+			return Some(Err(err));
+		}
+
+		let top_state : TIndex = *self.state.last().unwrap();
+
+		// partial rule tables
+		let part_term = &lalr1_tables::PARTIALS_RULE_TERM;
+		let part_nonterm = &lalr1_tables::PARTIALS_RULE_NONTERM;
+		let part_term_len = &lalr1_tables::PARTIALS_MATCHLEN_TERM;
+		let part_nonterm_len = &lalr1_tables::PARTIALS_MATCHLEN_NONTERM;
+
+		// constants
+		let err = lalr1_tables::sentinels::ERR;
+		let acc = lalr1_tables::sentinels::ACC;
+
+		let (new_state, rule_index) : (TIndex, TIndex) = match self.lookup_action(top_state)
+		{
+			Some(action) => action,
+
+			// the input is exhausted
+			None =>
+			{
+				// wait for more input, keeping the stacks and active rules
+				if self.pausable
+				{
+					log_msg(self.debug, DebugLevel::Actions, LogTarget::Parser, format_args!(
+						"Suspending at input index {}.", self.next_input_index));
+
+					self.suspended = true;
+					return Some(Err(ParseError::new(ParseErrorKind::Suspended, Some(top_state),
+						None, self.next_input_index)));
+				}
+
+				return Some(Err(self.parse_error(ParseErrorKind::UnexpectedEnd, top_state)));
+			},
+		};
+
+		if new_state == err && rule_index == err
+		{
+			// error productions take precedence over panic-mode recovery
+			if let Some(errtok_idx) = self.errtok_index
+			{
+				if self.err_status == 0
+				{
+					self.parse_error(ParseErrorKind::NoAction, top_state);
+				}
+
+				if !self.recover_errtok(errtok_idx)
+				{
+					return Some(Err(match self.errors.first()
+					{
+						Some(error) => error.clone(),
+						None => self.parse_error(ParseErrorKind::NoAction, top_state),
+					}));
+				}
+				return None;
+			}
+
+			let error = self.parse_error(ParseErrorKind::NoAction, top_state);
+			if !self.recover()
+			{
+				return Some(Err(self.errors.first().cloned().unwrap_or(error)));
+			}
+			return None;
+		}
+		else if new_state != err && rule_index != err
+		{
+			return Some(Err(self.parse_error(ParseErrorKind::Conflict, top_state)));
+		}
+
+		// accept
+		else if rule_index == acc
+		{
+			log_msg(self.debug, DebugLevel::Actions, LogTarget::Parser, format_args!("Accepted."));
+			self.accepted = true;
+
+			// input discarded before the end token goes to the result
+			if let Some(mut result) = self.symbol.pop()
+			{
+				self.flush_discarded(&mut result);
+				self.symbol.push(result);
+			}
+
+			// the result is incomplete if errors had to be recovered
+			if let Some(err) = self.errors.first()
+			{
+				return Some(Err(err.clone()));
+			}
+			return Some(Ok(()));
+		}
+
+		// shift
+		if new_state != err
+		{
+			if !self.limits.check_tokens(self.next_input_index)
+			{
+				return Some(Err(self.parse_error(ParseErrorKind::LimitExceeded, top_state)));
+			}
+
+			if !self.limits.check_depth(self.state.len() + 1)
+			{
+				return Some(Err(self.parse_error(ParseErrorKind::DepthExceeded, top_state)));
+			}
+
+			// partial rules
+			if self.use_partials
+			{
+				let partial_idx = part_term[top_state.idx()][self.lookahead_index.idx()];
+				if partial_idx != err
+				{
+					let partial_id = self.get_semantic_table_id(partial_idx);
+					let partial_len = part_term_len[top_state.idx()][self.lookahead_index.idx()];
+					let partial_lhs_id = self.get_nonterm_table_id(lalr1_tables::LHS_IDX[partial_idx.idx()]);
+
+					let partial_num_rhs = lalr1_tables::NUM_RHS_SYMS[partial_idx.idx()];
+
+					self.apply_partial_rule(partial_id, partial_len, partial_num_rhs,
+						partial_lhs_id, true);
+				}
+			}
+
+			if self.err_status > 0
+			{
+				self.err_status -= 1;
+			}
+			self.skipped_tokens = 0;
+
+			self.state.push(new_state);
+			self.push_lookahead();
+
+			if let Some(observer) = &self.observer
+			{
+				lock_shared(observer).on_shift(new_state, self.symbol.last().unwrap());
+			}
+
+			if let Some(stats) = &mut self.stats
+			{
+				stats.add_shift(self.state.len(), self.symbol.len());
+			}
+
+			if let Some(interval) = self.checkpoint_interval
+			{
+				if self.next_input_index.is_multiple_of(interval)
+				{
+					let checkpoint = self.save_checkpoint();
+					self.checkpoints.push(checkpoint);
+				}
+			}
+		}
+
+		// reduce
+		else if rule_index != err
+		{
+			if !self.watchdog.step(self.next_input_index, self.state.len())
+			{
+				self.error(&format!("No progress after {0} steps, state stack: {1:?}.",
+					self.watchdog.get_steps(), self.state));
+				return Some(Err(self.parse_error(ParseErrorKind::NoProgress, top_state)));
+			}
+
+			if !self.limits.add_reduction()
+			{
+				return Some(Err(self.parse_error(ParseErrorKind::LimitExceeded, top_state)));
+			}
+
+			let num_syms = lalr1_tables::NUM_RHS_SYMS[rule_index.idx()];
+			let lhs_index = lalr1_tables::LHS_IDX[rule_index.idx()];
+
+			// rules without rhs symbols grow the stack
+			if !self.limits.check_depth(self.state.len() - num_syms.idx() + 1)
+			{
+				return Some(Err(self.parse_error(ParseErrorKind::DepthExceeded, top_state)));
+			}
+			let rule_id = self.get_semantic_table_id(rule_index);
+			let lhs_id = self.get_nonterm_table_id(lhs_index);
+
+			self.apply_rule(rule_id, num_syms, lhs_id);
+			let new_top_state = *self.state.last().unwrap();
+
+			// partial rules
+			if self.use_partials && !self.symbol.is_empty()
+			{
+				let partial_idx = part_nonterm[new_top_state.idx()][lhs_index.idx()];
+				if partial_idx != err
+				{
+					let partial_id = self.get_semantic_table_id(partial_idx);
+					let partial_len = part_nonterm_len[new_top_state.idx()][lhs_index.idx()];
+					let partial_lhs_id = self.get_nonterm_table_id(lalr1_tables::LHS_IDX[partial_idx.idx()]);
+
+					let partial_num_rhs = lalr1_tables::NUM_RHS_SYMS[partial_idx.idx()];
+
+					self.apply_partial_rule(partial_id, partial_len, partial_num_rhs,
+						partial_lhs_id, false);
+				}
+			}		
+
+			let jump = &lalr1_tables::JUMP[new_top_state.idx()];
+			let jump_state : TIndex = jump[lhs_index.idx()];
+			self.state.push(jump_state);
+
+			if let Some(stats) = &mut self.stats
+			{
+				stats.add_reduction(rule_id, self.state.len(), self.symbol.len());
+			}
+		}
+
+		None
+	}
+}
+
+
+impl<T : Clone + Default + 'static, C : 'static> Parsable<T> for Parser<T, C>
+{
+	/*
+	 * set the input tokens
+	 */
+	fn set_input(&mut self, input: &[Symbol<T>])
+	{
+		self.input.assign(input);
+	}
+
+
+	/*
+	 * move the input tokens into the parser
+	 */
+	fn set_input_vec(&mut self, input : Vec<Symbol<T>>)
+	{
+		self.input = TokenInput::from_vec(input);
+	}
+
+
+	/*
+	 * set an interner for the token strings, it can also be captured by the semantic functions
+	 */
+	fn set_interner(&mut self, interner : Option<TInterner>)
+	{
+		self.interner = interner;
+	}
+
+
+	/*
+	 * set a slot that receives the token following the phrase before each full reduction,
+	 * the semantic functions can capture it, e.g. for disambiguation or error messages
+	 */
+	fn set_lookahead_slot(&mut self, slot : Option<TLookahead<T>>)
+	{
+		self.lookahead_slot = slot;
+	}
+
+
+	/*
+	 * set an iterator producing the input tokens on demand
+	 */
+	fn set_input_stream(&mut self, input : Box<dyn Iterator<Item = Symbol<T>> + Send>)
+	{
+		self.input = TokenInput::from_iter(input);
+	}
+
+
+	/*
+	 * add tokens to the input, e.g. to resume a suspended parse
+	 */
+	fn append_input(&mut self, input : &[Symbol<T>])
+	{
+		self.input.append(input);
+	}
+
+
+	/*
+	 * skip the given terminals in the input, e.g. comments, so that the grammar needn't mention them
+	 */
+	fn set_skip_terms(&mut self, terms : &[TSymbolId])
+	{
+		self.skip_terms = terms.to_vec();
+		self.skip_terms.sort();
+		self.skip_terms.dedup();
+	}
+
+
+	/*
+	 * get the terminals that can follow a prefix of the input, e.g. for editor completion,
+	 * the prefix is only run through the tables, so no semantic functions are called
+	 */
+	fn complete(&self, prefix : &[Symbol<T>]) -> Result<Vec<ExpectedTerm>, Box<ParseError<T>>>
+	{
+		complete_prefix(prefix, &self.skip_terms)
+	}
+
+
+	/*
+	 * set the verbosity of the parser output
+	 */
+	fn set_debug(&mut self, debug : DebugLevel)
+	{
+		self.debug = debug;
+	}
+
+
+	/**
+	 * enable application of partial semantic rules
+	 */
+	fn set_partials(&mut self, use_partials : bool)
+	{
+		self.use_partials = use_partials;
+	}
+
+
+	/*
+	 * set which semantic function invocations to run for partial matches
+	 */
+	fn set_partials_policy(&mut self, policy : PartialsPolicy)
+	{
+		self.partials_policy = policy;
+	}
+
+
+	/*
+	 * only apply the given semantic rules for full matches
+	 */
+	fn set_no_partials(&mut self, rules : &[TSemanticId])
+	{
+		self.no_partials = rules.to_vec();
+		self.no_partials.sort();
+		self.no_partials.dedup();
+	}
+
+
+	/*
+	 * skip all semantic functions, e.g. for validation-only parsing
+	 */
+	fn set_recognize_only(&mut self, recognize_only : bool)
+	{
+		self.recognize_only = recognize_only;
+	}
+
+
+	/*
+	 * set a handler that is called for every completed top-level item,
+	 * e.g. for each statement of a grammar whose start symbol is a list
+	 */
+	fn set_item_handler(&mut self, handler : Option<(TSymbolId, TItemHandler<T>)>)
+	{
+		self.item_handler = handler.map(|(item_id, handler)| (item_id, new_shared(handler)));
+	}
+
+
+	/*
+	 * set a handler that receives the parse errors instead of printing them
+	 */
+	fn set_error_handler(&mut self, handler : TErrorHandler<T>)
+	{
+		self.error_handler = Some(new_shared(handler));
+	}
+
+
+	/*
+	 * set an observer of the shifts, reductions, partial rules and errors
+	 */
+	fn set_observer(&mut self, observer : Option<Box<dyn ParseObserver<T>>>)
+	{
+		self.observer = observer.map(new_shared);
+	}
+
+
+	/*
+	 * set the terminals at which parsing resumes after an error
+	 */
+	fn set_sync_tokens(&mut self, sync : &[TSymbolId])
+	{
+		self.sync_tokens = sync.to_vec();
+	}
+
+
+	/*
+	 * limit the number of tokens skipped by the error recovery
+	 */
+	fn set_max_skipped_tokens(&mut self, max_skipped : Option<usize>)
+	{
+		self.max_skipped_tokens = max_skipped;
+	}
+
+
+	/*
+	 * limit the number of errors, e.g. for tolerant parsing of pathological inputs
+	 */
+	fn set_max_errors(&mut self, max_errors : Option<usize>)
+	{
+		self.max_errors = max_errors;
+	}
+
+
+	/*
+	 * keep the input discarded by the error recovery and pass it to the handler
+	 * together with the next shifted symbol
+	 */
+	fn set_discard_handler(&mut self, handler : Option<TDiscardHandler<T>>)
+	{
+		self.discard_handler = handler.map(new_shared);
+	}
+
+
+	/*
+	 * limit the number of tokens and reductions and the duration of a parse
+	 */
+	fn set_limits(&mut self, limits : ParseLimits)
+	{
+		self.limits = LimitGuard::new(limits);
+	}
+
+
+	/*
+	 * suspend parsing instead of failing when the input is exhausted
+	 */
+	fn set_pausable(&mut self, pausable : bool)
+	{
+		self.pausable = pausable;
+	}
+
+
+	/*
+	 * enable timing of the semantic functions
+	 */
+	fn set_profiling(&mut self, profile : bool)
+	{
+		self.profile = if profile { Some(RuleProfile::new()) } else { None };
+	}
+
+
+	/*
+	 * count the shifts, reductions and stack depths
+	 */
+	fn set_statistics(&mut self, stats : bool)
+	{
+		self.stats = if stats { Some(ParseStats::new()) } else { None };
+	}
+
+
+	/*
+	 * set the semantic functions for the rules
+	 */
+	fn set_semantics(&mut self, sema : &[(TSemanticId, TSemantics<T>)])
+	{
+		self.semantics.clear();
+
+		for _i in 0..(*sema).len()
+		{
+			self.semantics.insert((*sema)[_i].0, to_fallible_action(to_context_action(Box::new((*sema)[_i].1))));
+		}
+	}
+
+
+	fn set_semantic_actions(&mut self, sema : Vec<(TSemanticId, TSemanticAction<T>)>)
+	{
+		self.semantics.clear();
+
+		for (rule_id, action) in sema
+		{
+			self.semantics.insert(rule_id, to_fallible_action(to_context_action(action)));
+		}
+	}
+
+
+	fn set_semantic_action(&mut self, rule_id : TSemanticId, action : TSemanticAction<T>)
+	{
+		self.semantics.insert(rule_id, to_fallible_action(to_context_action(action)));
+	}
+
+
+	fn set_match_action(&mut self, rule_id : TSemanticId, action : TMatchAction<T>)
+	{
+		self.semantics.insert(rule_id, from_match_action(action));
+	}
+
+
+	fn set_fallible_semantics(&mut self, sema : &[(TSemanticId, TFallibleSemantics<T>)])
+	{
+		for _i in 0..(*sema).len()
+		{
+			self.semantics.insert((*sema)[_i].0, from_fallible_fn((*sema)[_i].1));
+		}
+	}
+
+
+	fn set_default_action(&mut self, action : Option<TDefaultAction<T>>)
+	{
+		self.semantics.set_default(action);
+	}
+
+
+	fn get_top_symbol(&self) -> Option<&Symbol<T>>
+	{
+		if self.symbol.len() == 0
+		{
+			None
+		}
+		else
+		{
+			self.symbol.last()
+		}
+	}
+
+
+	fn get_end_id(&self) -> TSymbolId
+	{
+		lalr1_tables::sentinels::END
+	}
+
+
+	/*
+	 * get the time spent in the semantic functions during the last parse
+	 */
+	fn get_profile(&self) -> Option<&RuleProfile>
+	{
+		self.profile.as_ref()
+	}
+
+
+	fn get_statistics(&self) -> Option<&ParseStats>
+	{
+		self.stats.as_ref()
+	}
+
+
+	/*
+	 * get all errors of the last parse, including the recovered ones
+	 */
+	fn get_errors(&self) -> &[ParseError<T>]
+	{
+		&self.errors
+	}
+
+
+	fn reset(&mut self)
+	{
+		self.input.rewind();
+		self.next_input_index = 0;
+
+		self.lookahead = None;
+		self.lookahead_index = lalr1_tables::sentinels::ERR;
+
+		self.clear_active_rules();
+		self.cur_rule_handle = 0;
+		self.watchdog.reset();
+		self.limits.reset();
+
+		self.errors.clear();
+		self.semantic_failure = None;
+		self.last_recovery = None;
+		self.err_status = 0;
+		self.skipped_tokens = 0;
+		self.discarded.clear();
+		self.suspended = false;
+		self.accepted = false;
+
+		// keep the whole input for editing it
+		self.checkpoints.clear();
+		self.marks.clear();
+		if self.checkpoint_interval.is_some()
+		{
+			self.input.pin(0);
+		}
+
+		if let Some(profile) = &mut self.profile
+		{
+			profile.reset();
+		}
+
+		if let Some(stats) = &mut self.stats
+		{
+			stats.reset();
+		}
+
+		self.symbol.clear();
+		self.state.clear();
+		self.state.push(lalr1_tables::START);
+
+		// the marker of a secondary start symbol counts as shifted
+		if let Some((marker_id, start_state)) = self.start
+		{
+			self.symbol.push(Symbol { is_term : true, id : marker_id, ..Default::default() });
+			self.state.push(start_state);
+		}
+	}
+
+
+	fn try_parse(&mut self) -> Result<Symbol<T>, Box<ParseError<T>>>
+	{
+		self.start = None;
+		self.reset();
+		self.run()
+	}
+
+
+	/*
+	 * parse and keep the result on the symbol stack
+	 */
+	fn parse(&mut self) -> bool
+	{
+		self.start = None;
+		self.reset();
+		self.run_steps().is_ok()
+	}
+
+
+	/*
+	 * move the accepted symbol out of the symbol stack
+	 */
+	fn take_result(&mut self) -> Option<Symbol<T>>
+	{
+		if !self.accepted
+		{
+			return None;
+		}
+
+		self.accepted = false;
+		self.symbol.pop()
+	}
+
+
+	/*
+	 * continue a suspended parse with the appended input, or start a new one
+	 */
+	fn resume(&mut self) -> Result<Symbol<T>, Box<ParseError<T>>>
+	{
+		if !self.suspended
+		{
+			self.reset();
+			return self.run();
+		}
+
+		self.suspended = false;
+		self.run()
+	}
+}
+
+
+impl<C : 'static> Parser<ParseTree, C>
+{
+	/*
+	 * build a concrete syntax tree instead of running semantic functions,
+	 * the parse result is the root node, input discarded by the error recovery
+	 * is kept in error nodes
+	 */
+	pub fn set_tree_mode(&mut self)
+	{
+		self.semantics.clear();
+		self.semantics.set_default(Some(Box::new(ParseTree::node)));
+		self.discard_handler = Some(new_shared(Box::new(ParseTree::discard)));
+	}
+
+	/*
+	 * parse in tree mode, the trivia of the end token becomes the trailing trivia of the root
+	 */
+	pub fn parse_tree(&mut self) -> Result<ParseTree, Box<ParseError<ParseTree>>>
+	{
+		let mut tree : ParseTree = self.try_parse()?.val;
+
+		let end : Option<Symbol<ParseTree>> = match &self.lookahead
+		{
+			Some(lookahead) => Some(lookahead.clone()),
+			None => self.input.cursor(&mut self.next_input_index, &self.skip_terms).peek().cloned(),
+		};
+		if let Some(end) = end
+		{
+			tree.trailing = end.val.leading;
+		}
+
+		Ok(tree)
+	}
+}
+
+
+/*
+ * runs a parser one shift or reduce action at a time, e.g. for debugging
+ */
+pub struct ParserStepper<'p, T : Clone + Default + 'static = TLVal, C : 'static = ()>
+{
+	parser : &'p mut Parser<T, C>,
+
+	// set once the input is accepted or parsing stops
+	result : Option<Result<(), ParseError<T>>>,
+}
+
+
+impl<'p, T : Clone + Default + 'static, C : 'static> ParserStepper<'p, T, C>
+{
+	/*
+	 * perform the next action, returns false once parsing has finished
+	 */
+	pub fn step(&mut self) -> bool
+	{
+		if self.result.is_some()
+		{
+			return false;
+		}
+
+		self.result = self.parser.step();
+		self.result.is_none()
+	}
+
+
+	pub fn is_finished(&self) -> bool
+	{
+		self.result.is_some()
+	}
+
+
+	/*
+	 * get the action the next step will perform, this may read the lookahead token
+	 */
+	pub fn get_pending_action(&mut self) -> ParserAction
+	{
+		self.parser.get_pending_action()
+	}
+
+
+	pub fn get_states(&self) -> &[TIndex]
+	{
+		&self.parser.state
+	}
+
+
+	pub fn get_symbols(&self) -> &[Symbol<T>]
+	{
+		&self.parser.symbol
+	}
+
+
+	pub fn get_lookahead(&self) -> Option<&Symbol<T>>
+	{
+		self.parser.lookahead.as_ref()
+	}
+
+
+	pub fn get_expected_terminals(&self) -> Vec<(TSymbolId, &'static str)>
+	{
+		self.parser.get_expected_terminals()
+	}
+
+
+	/*
+	 * get the accepted symbol or the error once parsing has finished
+	 */
+	pub fn take_result(&mut self) -> Option<Result<Symbol<T>, Box<ParseError<T>>>>
+	{
+		match self.result.take()?
+		{
+			Ok(()) => self.parser.take_result().map(Ok),
+			Err(err) => Some(Err(Box::new(err))),
+		}
 	}
 }
@@ -11,9 +11,14 @@
  *      - "Übersetzerbau", ISBN: 978-3540653899 (1999, 2013)
  */
 
-use std::collections::HashMap;
+use std::collections::{HashMap, BTreeMap};
+use std::convert::TryInto;
+use std::env;
+use std::fs;
 use std::fs::File;
 use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process;
 
 mod expr;
 mod types;
@@ -22,412 +27,1513 @@ mod idents;
 use types::*;
 
 
-const CODE : &str = r#"/*
- * Parser created using liblalr1 by Tobias Weber, 2020-2022.
- * DOI: https://doi.org/10.5281/zenodo.6987396
- */
-
-use std::collections::HashMap;
-use std::mem::take;
+// built-in skeleton of the parser, the markers are replaced by the generated code
+const CODE : &str = include_str!("templates/parser.rs.in");
 
-use types::{*};
-use common::{*};
 
-pub struct Parser
-{
-	symbol : Vec<Symbol>,
+// built-in skeleton of the parse tree visitor
+const VISITOR_CODE : &str = include_str!("templates/visitor.rs.in");
 
-	dist_to_jump : usize,
 
-	failed : bool,
-	accepted : bool,
+// table-driven parser, copied with the tables by the table backend
+const TABLE_CODE : &str = include_str!("parser.rs");
 
-	lookahead : Option<Symbol>,
 
-	input : Vec<Symbol>,
-	next_input_index : usize,
+/*
+ * kind of parser to generate
+ */
+#[derive(PartialEq, Eq)]
+enum Backend
+{
+	Ascent,  // recursive-ascent parser with one function per state
+	Table,   // table-driven parser with the tables baked in
+}
 
-	semantics : HashMap<TSemanticId, TSemantics>,
 
-	debug : bool,
-	use_partials : bool,
-	end : TSymbolId,
+/*
+ * settings of the code generation, given on the command line
+ */
+struct GenOptions
+{
+	backend : Backend,         // kind of parser to generate
+	outfile : String,          // file name of the parser
+	visitor_outfile : String,  // file name of the visitor
+	semantics_outfile : Option<String>, // file name of the semantic function stubs
+	template : Option<String>, // file name of a custom parser skeleton
+	visitor_template : Option<String>, // file name of a custom visitor skeleton
+	common_module : String,    // path of the module with the parser runtime
+	types_module : String,     // path of the module with the tables and their types
+	value_type : String,       // default type of the symbols' values, e.g. an ast node
+	gen_partials : bool,       // emit the code running the partial rules
+	gen_debug : bool,          // emit the trace output of the states
+	debug_cfg : Option<String>, // configuration predicate under which the debug output is compiled in
+	embed_tables : bool,       // copy the tables into the parser instead of using the tables module
+	dedup_states : bool,       // share the code of states which only differ in their index
+	states_per_module : Option<usize>, // split the state functions into modules of this size
+	trampoline : bool,         // run the states in a loop instead of recursively
+	inline_jumps : bool,       // run the reducing jump states in the loop of the calling state
+	narrow_types : bool,       // use the smallest integer types for the embedded tables
 }
 
-impl Parser
+
+impl GenOptions
 {
-	pub fn new() -> Parser
+	fn new() -> GenOptions
 	{
-		let mut parser : Parser = Parser
+		GenOptions
 		{
-			symbol : Vec::<Symbol>::new(),
-			dist_to_jump : 0,
-
-			failed : false,
-			accepted : false,
-
-			lookahead : None,
-
-			semantics : HashMap::<TSemanticId, TSemantics>::new(),
-			input : Vec::<Symbol>::new(),
-			next_input_index : 0,
+			backend : Backend::Ascent,
+			outfile : "generated_parser.rs".to_string(),
+			visitor_outfile : "generated_visitor.rs".to_string(),
+			semantics_outfile : None,
+			template : None,
+			visitor_template : None,
+			common_module : "common".to_string(),
+			types_module : "types".to_string(),
+			value_type : "TLVal".to_string(),
+			gen_partials : true,
+			gen_debug : true,
+			debug_cfg : None,
+			embed_tables : false,
+			dedup_states : true,
+			states_per_module : None,
+			trampoline : true,
+			inline_jumps : true,
+			narrow_types : false,
+		}
+	}
 
-			debug : false,
-			use_partials : true,
-			end : lalr1_tables::END,
-		};
 
-		parser.reset();
-		parser
+	fn get_table_types(&self) -> TableTypes
+	{
+		match self.narrow_types
+		{
+			true => TableTypes::narrow(),
+			false => TableTypes::new(),
+		}
 	}
 
-	fn next_lookahead(&mut self)
+
+	/*
+	 * get the options from the command-line arguments
+	 * @return None if the arguments are invalid or the usage was requested
+	 */
+	fn from_args(args : &[String]) -> Option<GenOptions>
 	{
-		self.lookahead = Some(self.input[self.next_input_index].clone());
+		let mut opts : GenOptions = GenOptions::new();
 
-		if self.debug
+		let mut iter = args.iter();
+		while let Some(arg) = iter.next()
 		{
-			println!("Lookahead: {:?}, input index: {}.",
-				self.lookahead, self.next_input_index);
+			// options with a value
+			let value : Option<&mut String> = match arg.as_str()
+			{
+				"-o" | "--output" => Some(&mut opts.outfile),
+				"--visitor-output" => Some(&mut opts.visitor_outfile),
+				"--semantics-output" => Some(opts.semantics_outfile.get_or_insert_with(String::new)),
+				"--common-module" => Some(&mut opts.common_module),
+				"--types-module" => Some(&mut opts.types_module),
+				"--value-type" => Some(&mut opts.value_type),
+				"--debug-cfg" => Some(opts.debug_cfg.get_or_insert_with(String::new)),
+				"--template" => Some(opts.template.get_or_insert_with(String::new)),
+				"--visitor-template" => Some(opts.visitor_template.get_or_insert_with(String::new)),
+				_ => None,
+			};
+
+			if let Some(value) = value
+			{
+				match iter.next()
+				{
+					Some(val) => *value = val.clone(),
+					None =>
+					{
+						println!("Error: Missing value for option \"{arg}\".");
+						return None;
+					},
+				}
+				continue;
+			}
+
+			if arg == "--split-states"
+			{
+				match iter.next().map(|val| val.parse::<usize>())
+				{
+					Some(Ok(num)) if num > 0 => opts.states_per_module = Some(num),
+					_ =>
+					{
+						println!("Error: Expected a positive number for option \"{arg}\".");
+						return None;
+					},
+				}
+				continue;
+			}
+
+			if arg == "--backend"
+			{
+				opts.backend = match iter.next().map(|val| val.as_str())
+				{
+					Some("ascent") => Backend::Ascent,
+					Some("table") => Backend::Table,
+					_ =>
+					{
+						println!("Error: Expected \"ascent\" or \"table\" for option \"{arg}\".");
+						return None;
+					},
+				};
+				continue;
+			}
+
+			// flags
+			match arg.as_str()
+			{
+				"--partials" => opts.gen_partials = true,
+				"--no-partials" => opts.gen_partials = false,
+				"--debug" => opts.gen_debug = true,
+				"--no-debug" => opts.gen_debug = false,
+				"--embed-tables" => opts.embed_tables = true,
+				"--no-embed-tables" => opts.embed_tables = false,
+				"--dedup-states" => opts.dedup_states = true,
+				"--no-dedup-states" => opts.dedup_states = false,
+				"--trampoline" => opts.trampoline = true,
+				"--no-trampoline" => opts.trampoline = false,
+				"--inline-jumps" => opts.inline_jumps = true,
+				"--no-inline-jumps" => opts.inline_jumps = false,
+				"--narrow-types" => opts.narrow_types = true,
+				"--no-narrow-types" => opts.narrow_types = false,
+				"-h" | "--help" => return None,
+				_ =>
+				{
+					println!("Error: Unknown option \"{arg}\".");
+					return None;
+				},
+			}
 		}
 
-		self.next_input_index += 1;
-        }
+		Some(opts)
+	}
+
 
-	fn push_lookahead(&mut self)
+	fn print_usage(program : &str)
 	{
-		self.symbol.push(take(&mut self.lookahead).unwrap());
-		self.next_lookahead();
+		println!("Usage: {program} [options]");
+		println!("\t--backend <ascent|table>     recursive-ascent or table-driven parser, default: ascent");
+		println!("\t-o, --output <file>          file name of the parser, default: generated_parser.rs");
+		println!("\t--visitor-output <file>      file name of the visitor, default: generated_visitor.rs");
+		println!("\t--semantics-output <file>    write stubs of the semantic functions to fill in, default: off");
+		println!("\t--common-module <path>       module with the parser runtime, default: common");
+		println!("\t--types-module <path>        module with the tables and their types, default: types");
+		println!("\t--value-type <type>          default value type of the parser, needs Clone + Default, default: TLVal");
+		println!("\t--template <file>            custom parser skeleton, default: built-in");
+		println!("\t--visitor-template <file>    custom visitor skeleton, default: built-in");
+		println!("\t--partials, --no-partials    emit the code running the partial rules, default: on");
+		println!("\t--debug, --no-debug          emit the trace output of the states, default: on");
+		println!("\t--debug-cfg <predicate>      only compile in the debug output if the predicate holds,");
+		println!("\t                             e.g. debug_assertions or 'feature = \"trace\"', default: always");
+		println!("\t--embed-tables, --no-embed-tables");
+		println!("\t                             copy the tables into the parser, default: off, on for the table backend");
+		println!("\t--dedup-states, --no-dedup-states");
+		println!("\t                             share the code of equivalent states, default: on");
+		println!("\t--trampoline, --no-trampoline");
+		println!("\t                             run the states in a loop instead of recursively, default: on");
+		println!("\t--inline-jumps, --no-inline-jumps");
+		println!("\t                             without trampoline: run reducing jump states in a loop, default: on");
+		println!("\t--narrow-types, --no-narrow-types");
+		println!("\t                             embed the tables using the smallest integer types, default: off");
+		println!("\t                             (its driver then needs the narrow_types feature, see types.rs)");
+		println!("\t--split-states <num>         write the parser into a directory with modules of <num> states, default: off");
+		println!("\t-h, --help                   show this help");
 	}
+}
+
+
+/*
+ * convert a table entry to usize, the tables module can have narrow types
+ */
+fn to_usize<X : TryInto<usize>>(val : X) -> usize
+{
+	val.try_into().unwrap_or(usize::MAX)
+}
+
 
-	fn apply_rule(&mut self, rule_id : TSemanticId, num_rhs : TIndex, lhs_id : TSymbolId)
+fn has_table_entry(tab : &[TIndex]) -> bool
+{
+	for iter in tab.iter()
 	{
-		if self.debug
+		if *iter != lalr1_tables::sentinels::ERR
 		{
-			println!("Applying rule {} with {} arguments.", rule_id, num_rhs);
+			return true;
 		}
+	}
 
-		self.dist_to_jump = num_rhs;
+	false
+}
 
-		let mut args : Vec<Symbol> = Vec::<Symbol>::new();
-		args.reserve(num_rhs);
 
-		for _i in 0..num_rhs
+fn get_table_id(tab : &[(TSymbolId, TIndex, &str)], idx : TIndex) -> Result<TSymbolId, String>
+{
+	for entry in tab
+	{
+		if entry.1 == idx
 		{
-			args.insert(0, self.symbol.pop().unwrap());
+			return Ok(entry.0);
 		}
+	}
 
-		let mut retval : TLVal = 0 as TLVal;
-		let semantics : Option<&TSemantics> = self.semantics.get(&rule_id);
-		if semantics != None
-		{
-			retval = (*semantics.unwrap())(args, true, 0 as TLVal);
-		}
+	Err(format!("table index {idx} was not found"))
+}
 
-		self.symbol.push(Symbol{
-			is_term : false,
-			id : lhs_id,
-			val : retval,
-			strval : None,
-		});
-        }
 
-	fn error_term(&mut self, state_idx : usize, sym_id : TSymbolId)
+fn get_table_id_str(tab : &[(TSymbolId, TIndex, &str)], idx : TIndex) -> Result<(TSymbolId, String), String>
+{
+	for entry in tab
 	{
-		println!("Error: Invalid terminal transition {sym_id} in state {state_idx}.");
-		self.failed = true;
+		if entry.1 == idx
+		{
+			return Ok((entry.0, entry.2.to_string()));
+		}
 	}
 
-	fn error_nonterm(&mut self, state_idx : usize, sym_id : TSymbolId)
+	Err(format!("table index {idx} was not found"))
+}
+
+
+fn get_semantic_table_id(tab : &[(TSemanticId, TIndex)], idx : TIndex) -> Result<TSemanticId, String>
+{
+	for entry in tab
 	{
-		println!("Error: Invalid non-terminal transition {sym_id} in state {state_idx}.");
-		self.failed = true;
+		if entry.1 == idx
+		{
+			return Ok(entry.0);
+		}
 	}
 
-%%STATES%%
+	Err(format!("semantic table index {idx} was not found"))
 }
 
-impl Parsable for Parser
+
+/*
+ * get the semantic id, the number of symbols and the left-hand side id of a rule
+ */
+fn get_rule_ids(rule_idx : TIndex) -> Result<(TSemanticId, TIndex, TSymbolId), String>
 {
-	fn set_debug(&mut self, debug : bool)
+	let (num_rhs, lhs_idx) : (TIndex, TIndex) = match (lalr1_tables::NUM_RHS_SYMS.get(rule_idx),
+		lalr1_tables::LHS_IDX.get(rule_idx))
 	{
-		self.debug = debug;
-	}
+		(Some(num_rhs), Some(lhs_idx)) => (*num_rhs, *lhs_idx),
+		_ => return Err(format!("rule index {rule_idx} is out of range")),
+	};
+
+	let rule_id : TSemanticId = get_semantic_table_id(&lalr1_tables::SEMANTIC_IDX, rule_idx)
+		.map_err(|err| format!("rule {rule_idx}: {err}"))?;
+	let lhs_id : TSymbolId = get_table_id(&lalr1_tables::NONTERM_IDX, lhs_idx)
+		.map_err(|err| format!("left-hand side of rule {rule_idx}: nonterminal {err}"))?;
+
+	Ok((rule_id, num_rhs, lhs_id))
+}
 
-	fn set_partials(&mut self, use_partials : bool)
+
+/*
+ * check that a shift or jump table entry leads to an existing state
+ */
+fn check_state_idx(state_idx : TIndex) -> Result<TIndex, String>
+{
+	match state_idx < lalr1_tables::SHIFT.len()
 	{
-		self.use_partials = use_partials;
+		true => Ok(state_idx),
+		false => Err(format!("next state {state_idx} is out of range")),
 	}
+}
 
-	fn get_end_id(&self) -> TSymbolId
+
+/*
+ * write the special terminals by their names, their values depend on the table types
+ */
+fn create_term_pattern(term_id : TSymbolId) -> String
+{
+	match term_id
 	{
-		self.end
+		_ if term_id == lalr1_tables::sentinels::END => "lalr1_tables::sentinels::END".to_string(),
+		_ if term_id == lalr1_tables::sentinels::ERRTOK => "lalr1_tables::sentinels::ERRTOK".to_string(),
+		_ => term_id.to_string(),
 	}
+}
 
-	fn set_input(&mut self, input: &[Symbol])
+
+/*
+ * create the visitor methods and their dispatch, e.g. "visit_expr_200"
+ */
+fn create_visitor() -> Result<(String, String), String>
+{
+	let mut methods : String = String::new();
+	let mut dispatch : String = String::new();
+
+	let mut rules : Vec<(TSemanticId, TIndex)> = lalr1_tables::SEMANTIC_IDX.to_vec();
+	rules.sort();
+
+	for (rule_id, rule_idx) in rules.iter()
 	{
-		self.input = (*input).to_vec();
+		let (lhs_id, lhs_name) : (TSymbolId, String) = get_table_id_str(
+			&lalr1_tables::NONTERM_IDX, lalr1_tables::LHS_IDX[*rule_idx])
+			.map_err(|err| format!("Visitor of rule {rule_id}: nonterminal {err}"))?;
+		let ident : String = lhs_name.chars()
+			.map(|ch| if ch.is_ascii_alphanumeric() { ch.to_ascii_lowercase() } else { '_' })
+			.collect();
+		let num_rhs : TIndex = lalr1_tables::NUM_RHS_SYMS[*rule_idx];
+
+		methods += &format!("\t// rule {rule_id} of nonterminal \"{lhs_name}\" ({lhs_id}) with {num_rhs} symbol(s)\n");
+		methods += &format!("\tfn visit_{ident}_{rule_id}(&mut self, node : &ParseTree)\n\t{{\n");
+		methods += "\t\twalk_children(self, node);\n\t}\n\n";
+
+		dispatch += &format!("\t\t\tSome({rule_id}) => self.visit_{ident}_{rule_id}(node),\n");
 	}
 
-	fn set_semantics(&mut self, sema : &[(TSemanticId, TSemantics)])
+	// remove the last newline
+	dispatch.pop();
+	Ok((methods, dispatch))
+}
+
+
+/*
+ * create a skeleton with a stub of the semantic function for each rule,
+ * the comments tell which argument corresponds to which symbol of the rule
+ */
+fn create_semantics(opts : &GenOptions) -> String
+{
+	let value_type : &str = &opts.value_type;
+	let mut rules : Vec<(TSemanticId, TIndex)> = lalr1_tables::SEMANTIC_IDX.to_vec();
+	rules.sort();
+
+	let mut code : String = String::new();
+	code += "/*\n * Semantic functions created using liblalr1 by Tobias Weber, 2020-2022.\n";
+	code += " * DOI: https://doi.org/10.5281/zenodo.6987396\n */\n\n";
+	code += &format!("use {}::{{*}};\nuse {}::{{*}};\n\n", opts.types_module, opts.common_module);
+	code += "/*\n * semantic functions of the rules, e.g. for parser.set_semantics(&SEMANTICS),\n";
+	code += " * partial matches of a rule have _done == false and get the partial result in _retval\n */\n";
+	code += &format!("pub const SEMANTICS : [(TSemanticId, TSemantics<{value_type}>); {}] =\n[\n", rules.len());
+
+	for (rule_id, rule_idx) in rules.iter()
 	{
-		self.semantics.clear();
+		let rule_str : &str = lalr1_tables::RULE_STRS.get(*rule_idx).copied().unwrap_or("");
+		let num_rhs : TIndex = lalr1_tables::NUM_RHS_SYMS[*rule_idx];
+		let args : &str = if num_rhs > 0 { "args" } else { "_args" };
+
+		code += &format!("\t// rule {rule_id}: {rule_str}\n");
+		code += &format!("\t( {rule_id}, |{args} : TArgs<{value_type}>, _done : bool, _retval : {value_type}| -> {value_type}\n");
+		code += "\t{\n";
+		code += "\t\tif !_done { return _retval; }\n";
 
-		for _i in 0..(*sema).len()
+		// name the arguments by the rule's right-hand side symbols
+		let rhs : Vec<&str> = match rule_str.split_once("->")
 		{
-			self.semantics.insert((*sema)[_i].0, (*sema)[_i].1);
+			Some((_, rhs)) => rhs.split_whitespace().collect(),
+			None => Vec::new(),
+		};
+		for arg_idx in 0..num_rhs
+		{
+			let sym : &str = rhs.get(arg_idx).copied().unwrap_or("?");
+			code += &format!("\t\tlet _arg{arg_idx} : &Symbol<{value_type}> = &args[{arg_idx}];  // {sym}\n");
 		}
+
+		code += &format!("\t\t{value_type}::default()\n");
+		code += "\t} ),\n\n";
+	}
+
+	// remove the last empty line
+	code.pop();
+	code += "];\n";
+	code
+}
+
+
+/*
+ * code of the generated functions, keeps track of the indentation and of the nested
+ * blocks, so that the generators only need to deal with the statements
+ */
+struct CodeWriter
+{
+	code : String,   // code written so far
+	indent : usize,  // indentation of the next line in tabs
+}
+
+
+impl CodeWriter
+{
+	fn new(indent : usize) -> CodeWriter
+	{
+		CodeWriter { code : String::new(), indent }
 	}
 
-	fn get_top_symbol(&self) -> Option<&Symbol>
+
+	/*
+	 * write a line at the current indentation, empty lines are not indented
+	 */
+	fn line(&mut self, line : &str)
 	{
-		if self.symbol.len() == 0
+		if !line.is_empty()
 		{
-			None
+			self.code += &"\t".repeat(self.indent);
+			self.code += line;
 		}
-		else
+		self.code.push('\n');
+	}
+
+
+	fn comment(&mut self, comment : &str)
+	{
+		self.line(&create_comment(comment));
+	}
+
+
+	fn block_comment<S : AsRef<str>>(&mut self, lines : &[S])
+	{
+		self.line("/*");
+		for line in lines
 		{
-			self.symbol.last()
+			self.line(&format!(" * {}", escape_comment(line.as_ref())));
 		}
+		self.line(" */");
+	}
+
+
+	/*
+	 * write the head of a block, e.g. of a function, a loop or a match arm, and indent its body
+	 */
+	fn open(&mut self, head : &str)
+	{
+		self.line(head);
+		self.line("{");
+		self.indent += 1;
 	}
 
-	fn reset(&mut self)
+
+	/*
+	 * close the innermost block, "tail" follows the brace, e.g. the comma after a match arm
+	 */
+	fn close(&mut self, tail : &str)
 	{
-		self.next_input_index = 0;
-		self.lookahead = None;
-		self.symbol.clear();
-		self.dist_to_jump = 0;
+		self.indent -= 1;
+		self.line(&format!("}}{tail}"));
+	}
+
 
-		self.failed = false;
-		self.accepted = false;
+	/*
+	 * append the code of another writer, which has to be created at this writer's indentation
+	 */
+	fn append(&mut self, code : &str)
+	{
+		self.code += code;
 	}
 
-	fn parse(&mut self) -> bool
+
+	fn get_indent(&self) -> usize
 	{
-		self.reset();
-		self.next_lookahead();
-		self.state_%%START_IDX%%();
+		self.indent
+	}
 
-		self.accepted
+
+	fn finish(self) -> String
+	{
+		self.code
 	}
 }
-"#;
 
 
-fn has_table_entry(tab : &[TIndex]) -> bool
+/*
+ * keep grammar strings from ending or breaking the comments they are written in
+ */
+fn escape_comment(comment : &str) -> String
 {
-	for iter in tab.iter()
+	comment.replace("*/", "* /").replace('\r', "\\r").replace('\n', "\\n")
+}
+
+
+fn create_comment(comment : &str) -> String
+{
+	format!("// {}", escape_comment(comment))
+}
+
+
+/*
+ * create the call of the partial rule for a table entry, if it has one
+ */
+fn create_partial_call(rule_idx : TIndex, match_len : TIndex, state : &str,
+	before_shift : bool) -> Result<Option<String>, String>
+{
+	if rule_idx == lalr1_tables::sentinels::ERR
 	{
-		if *iter != lalr1_tables::ERR
-		{
-			return true;
-		}
+		return Ok(None);
 	}
 
-	false
+	let (rule_id, num_rhs, lhs_id) : (TSemanticId, TIndex, TSymbolId) = get_rule_ids(rule_idx)
+		.map_err(|err| format!("partial {err}"))?;
+
+	Ok(Some(format!("self.apply_partial_rule({state}, {rule_id}, {match_len}, {num_rhs}, {lhs_id}, {before_shift});")))
 }
 
 
-fn get_table_id(tab : &[(TSymbolId, TIndex, &str)], idx : TIndex) -> TSymbolId
+/*
+ * create the code matching the lookahead terminal in a state,
+ * "state" is the expression of the state index used in the code
+ */
+fn create_state_match(state_idx : TIndex, state : &str, opts : &GenOptions,
+	code : &mut CodeWriter) -> Result<(), String>
 {
-	for entry in tab
+	let shift = &lalr1_tables::SHIFT[state_idx];
+	let reduce = &lalr1_tables::REDUCE[state_idx];
+	let num_terms = shift.len();
+	let has_shift_entry = has_table_entry(shift);
+
+	// the trampoline only needs the index of the next state
+	let next_state = |newstate_idx : TIndex| -> String
 	{
-		if entry.1 == idx
+		match opts.trampoline
+		{
+			true => format!("Some({newstate_idx})"),
+			false => format!("Some((Parser::state_{newstate_idx}, {newstate_idx}))"),
+		}
+	};
+
+	if has_shift_entry
+	{
+		match opts.trampoline
 		{
-			return entry.0;
+			true => code.line("let mut next_state : Option<TIndex> = None;"),
+			false => code.line("let mut next_state : Option<(fn(&mut Parser<T, C>), TIndex)> = None;"),
 		}
 	}
 
-	println!("Error: Table index {idx} was not found.");
-	lalr1_tables::ERR
-}
+	code.line("let sym_id : TSymbolId = self.get_lookahead_id();");
+	code.open("match sym_id");
 
+	let mut rules_term_id : BTreeMap<TIndex, Vec<(String, String)>>
+		= BTreeMap::<TIndex, Vec<(String, String)>>::new();
+	let mut acc_term_id : Vec<(String, String)> = Vec::<(String, String)>::new();
 
-fn get_table_id_str(tab : &[(TSymbolId, TIndex, &str)], idx : TIndex) -> (TSymbolId, String)
-{
-	for entry in tab
+	for term_idx in 0..num_terms
 	{
-		if entry.1 == idx
+		let newstate_idx = shift[term_idx];
+		let rule_idx = reduce[term_idx];
+		let (term_id, term_str) : (TSymbolId, String) = get_table_id_str(&lalr1_tables::TERM_IDX, term_idx)
+			.map_err(|err| format!("terminal {err}"))?;
+		let term_id : String = create_term_pattern(term_id);
+
+		let partial : Option<String> = match opts.gen_partials
 		{
-			return (entry.0, entry.2.to_string());
+			true => create_partial_call(lalr1_tables::PARTIALS_RULE_TERM[state_idx][term_idx],
+				lalr1_tables::PARTIALS_MATCHLEN_TERM[state_idx][term_idx], state, true)
+				.map_err(|err| format!("lookahead {term_str:?}: {err}"))?,
+			false => None,
+		};
+
+		if newstate_idx != lalr1_tables::sentinels::ERR
+		{
+			check_state_idx(newstate_idx)
+				.map_err(|err| format!("shift entry for lookahead {term_str:?}: {err}"))?;
+
+			match partial
+			{
+				// the partial rule is run before the terminal is shifted
+				Some(partial) =>
+				{
+					code.open(&format!("{term_id} => {}", create_comment(&term_str)));
+					code.line(&partial);
+					code.line(&format!("next_state = {};", next_state(newstate_idx)));
+					code.close(",");
+				},
+				None => code.line(&format!("{term_id} => next_state = {}, {}",
+					next_state(newstate_idx), create_comment(&term_str))),
+			}
+		}
+		else if rule_idx != lalr1_tables::sentinels::ERR
+		{
+			if rule_idx == lalr1_tables::sentinels::ACC
+			{
+				acc_term_id.push((term_id, term_str));
+			}
+			else
+			{
+				rules_term_id.entry(rule_idx).or_default().push((term_id, term_str));
+			}
 		}
 	}
 
-	println!("Error: Table index {idx} was not found.");
-	(lalr1_tables::ERR, "err".to_string())
+	for (rule_idx, sym_ids) in &rules_term_id
+	{
+		let cases : String = sym_ids.iter().map(|elem| elem.0.clone()).
+			collect::<Vec<String>>().join(" | ");
+		let comment : String = sym_ids.iter().map(|elem| elem.1.clone()).
+			collect::<Vec<String>>().join(" | ");
+
+		let (rule_id, num_rhs, lhs_id) : (TSemanticId, TIndex, TSymbolId) = get_rule_ids(*rule_idx)
+			.map_err(|err| format!("reduce entry for lookahead {comment:?}: {err}"))?;
+
+		code.comment(&comment);
+		code.line(&format!("{cases} => self.apply_rule({state}, {rule_id}, {num_rhs}, {lhs_id}),"));
+	}
+
+	if !acc_term_id.is_empty()
+	{
+		let acc_cases : String = acc_term_id.iter().map(|elem| elem.0.clone()).
+			collect::<Vec<String>>().join(" | ");
+		let acc_comment : String = acc_term_id.iter().map(|elem| elem.1.clone()).
+			collect::<Vec<String>>().join(" | ");
+
+		code.comment(&acc_comment);
+		code.line(&format!("{acc_cases} => self.accepted = true,"));
+	}
+	code.line(&format!("_ => self.error_term({state}, sym_id),"));
+	code.close("");  // end match
+
+	if has_shift_entry && opts.trampoline
+	{
+		// continue with the next state in the trampoline
+		code.open("if let Some(next_state) = next_state");
+		code.line("self.push_lookahead(next_state);");
+		code.open("if !self.failed");
+		code.line("return Some(next_state);");
+		code.close("");
+		code.close("");
+	}
+	else if has_shift_entry
+	{
+		code.open("if let Some((state_func, state_idx)) = next_state");
+		code.line("self.push_lookahead(state_idx);");
+		code.open("if !self.failed");
+		code.line("state_func(self);");
+		code.close("");
+		code.close("");
+	}
+
+	Ok(())
 }
 
 
-fn get_semantic_table_id(tab : &[(TSemanticId, TIndex)], idx : TIndex) -> TSemanticId
+/*
+ * a state which neither shifts nor jumps only reduces and returns,
+ * so its code can be run in the jump loop of the previous state
+ */
+fn is_leaf_state(state_idx : TIndex) -> bool
 {
-	for entry in tab
+	!has_table_entry(&lalr1_tables::SHIFT[state_idx]) &&
+		!has_table_entry(&lalr1_tables::JUMP[state_idx])
+}
+
+
+/*
+ * create the body of a state function, split into the code running when the state is entered
+ * and the code running after returning from the next state, i.e. the jumps after reductions,
+ * "state" is the expression of the state index used in the code and "indent" the body's indentation
+ */
+fn create_state_body(state_idx : TIndex, state : &str, opts : &GenOptions,
+	indent : usize) -> Result<(String, String), String>
+{
+	let mut enter : CodeWriter = CodeWriter::new(indent);
+	let mut resume : CodeWriter = CodeWriter::new(indent);
+
+	let jump = &lalr1_tables::JUMP[state_idx];
+	let has_jump_entry = has_table_entry(jump);
+
+	if opts.gen_debug
 	{
-		if entry.1 == idx
+		enter.line(&format!("debug_msg(self.debug, DebugLevel::Trace, LogTarget::State({state}), \
+			format_args!(\"Entering state {{}}.\", {state}));"));
+		enter.line("");
+	}
+
+	let default_rule_idx = lalr1_tables::DEFAULT_REDUCE[state_idx];
+	if default_rule_idx != lalr1_tables::sentinels::ERR
+	{
+		// default reduction, no need to look at the lookahead
+		let (rule_id, num_rhs, lhs_id) : (TSemanticId, TIndex, TSymbolId) = get_rule_ids(default_rule_idx)
+			.map_err(|err| format!("default reduction: {err}"))?;
+
+		enter.comment("default reduction");
+		enter.line(&format!("self.apply_rule({state}, {rule_id}, {num_rhs}, {lhs_id});"));
+	}
+	else
+	{
+		create_state_match(state_idx, state, opts, &mut enter)?;
+	}
+
+	if has_jump_entry
+	{
+		// the trampoline returns to this state after each jump
+		match opts.trampoline
+		{
+			true => resume.open("if self.dist_to_jump == 0 && self.symbol.len() > 0 && !self.accepted && !self.failed"),
+			false => resume.open("while self.dist_to_jump == 0 && self.symbol.len() > 0 && !self.accepted && !self.failed"),
+		}
+
+		resume.line("let top_sym : &Symbol<T> = self.get_top_symbol().unwrap();");
+		resume.open("if top_sym.is_term");
+		match opts.trampoline
+		{
+			true => resume.line("return self.leave_state();"),
+			false => resume.line("break;"),
+		}
+		resume.close("");  // end if
+
+		resume.open("match top_sym.id");
+
+		for (nonterm_idx, &jump_state_idx) in jump.iter().enumerate()
 		{
-			return entry.0;
+			if jump_state_idx != lalr1_tables::sentinels::ERR
+			{
+				let (nonterm_id, nonterm_str) : (TSymbolId, String) = get_table_id_str(
+					&lalr1_tables::NONTERM_IDX, nonterm_idx)
+					.map_err(|err| format!("nonterminal {err}"))?;
+				check_state_idx(jump_state_idx)
+					.map_err(|err| format!("jump entry for nonterminal {nonterm_str:?}: {err}"))?;
+
+				let partial : Option<String> = match opts.gen_partials
+				{
+					true => create_partial_call(lalr1_tables::PARTIALS_RULE_NONTERM[state_idx][nonterm_idx],
+						lalr1_tables::PARTIALS_MATCHLEN_NONTERM[state_idx][nonterm_idx], state, false)
+						.map_err(|err| format!("nonterminal {nonterm_str:?}: {err}"))?,
+					false => None,
+				};
+
+				let jump_call : String = match opts.trampoline
+				{
+					true => format!("return Some({jump_state_idx})"),
+					false => format!("self.state_{jump_state_idx}()"),
+				};
+
+				// run the jump state in the loop of this state instead of calling it
+				if !opts.trampoline && opts.inline_jumps && is_leaf_state(jump_state_idx)
+				{
+					resume.open(&format!("{nonterm_id} => {}", create_comment(&nonterm_str)));
+					if let Some(partial) = partial
+					{
+						resume.line(&partial);
+					}
+					resume.comment(&format!("state {jump_state_idx}"));
+					let (jump_enter, jump_resume) : (String, String) = create_state_body(
+						jump_state_idx, &jump_state_idx.to_string(), opts, resume.get_indent())
+						.map_err(|err| format!("inlined state {jump_state_idx}: {err}"))?;
+					resume.append(&jump_enter);
+					resume.append(&jump_resume);
+					resume.close(",");
+					continue;
+				}
+
+				match partial
+				{
+					// the partial rule is run before the jump
+					Some(partial) =>
+					{
+						resume.open(&format!("{nonterm_id} => {}", create_comment(&nonterm_str)));
+						resume.line(&partial);
+						resume.line(&format!("{jump_call};"));
+						resume.close(",");
+					},
+					None => resume.line(&format!("{nonterm_id} => {jump_call}, {}",
+						create_comment(&nonterm_str))),
+				}
+			}
 		}
+
+		resume.line(&format!("_ => self.error_nonterm({state}, top_sym.id),"));
+
+		resume.close("");  // end match
+		resume.close("");  // end while
+	}
+
+	match opts.trampoline
+	{
+		true => resume.line("self.leave_state()"),
+		false =>
+		{
+			resume.open("if !self.accepted && !self.failed");
+			resume.line("self.dist_to_jump -= 1;");
+			resume.close("");  // end if
+		},
 	}
 
-	println!("Error: Semantic table index {idx} was not found.");
-	lalr1_tables::ERR
+	Ok((enter.finish(), resume.finish()))
 }
 
 
-fn create_states() -> String
+/*
+ * lines with the kernel items of a state for the comment of its function
+ */
+fn get_items_comment(state_idx : TIndex) -> Vec<&'static str>
+{
+	lalr1_tables::STATE_ITEMS[state_idx].lines().collect()
+}
+
+
+/*
+ * create the state functions and the functions shared by equivalent states,
+ * "vis" is the visibility of the functions, which is needed if they are moved to sub-modules
+ */
+fn create_states(opts : &GenOptions, vis : &str) -> Result<Vec<String>, String>
 {
-	let mut states : String = String::new();
+	let mut funcs : Vec<String> = Vec::new();
 	let num_states = lalr1_tables::SHIFT.len();
 
-	for state_idx in 0..num_states
+	// states whose code only differs in the state index share a function
+	let mut shared_bodies : Vec<((String, String), Vec<TIndex>)> = Vec::new();
+	let mut state_shared : Vec<Option<usize>> = vec![None; num_states];
+	if opts.dedup_states
 	{
-		let shift = &lalr1_tables::SHIFT[state_idx];
-		let reduce = &lalr1_tables::REDUCE[state_idx];
-		let jump = &lalr1_tables::JUMP[state_idx];
+		let mut body_states : HashMap<(String, String), Vec<TIndex>> = HashMap::new();
+		let mut bodies : Vec<(String, String)> = Vec::new();
+		for state_idx in 0..num_states
+		{
+			let body : (String, String) = create_state_body(state_idx, "state_idx", opts, 2)
+				.map_err(|err| format!("State {state_idx}: {err}"))?;
+			if !body_states.contains_key(&body)
+			{
+				bodies.push(body.clone());
+			}
+			body_states.entry(body).or_default().push(state_idx);
+		}
 
-		let num_terms = shift.len();
-		let num_nonterms = jump.len();
+		for body in bodies
+		{
+			let equiv_states : Vec<TIndex> = body_states.remove(&body).unwrap_or_default();
+			if equiv_states.len() < 2
+			{
+				continue;
+			}
 
-		let has_shift_entry = has_table_entry(shift);
-		let has_jump_entry = has_table_entry(jump);
+			for state_idx in &equiv_states
+			{
+				state_shared[*state_idx] = Some(shared_bodies.len());
+			}
+			shared_bodies.push((body, equiv_states));
+		}
+	}
 
-		states += &format!("\tfn state_{state_idx}(&mut self)\n");
-		states += "\t{\n";
+	if opts.trampoline
+	{
+		funcs.append(&mut create_trampoline(&state_shared, vis));
+	}
 
-		if has_shift_entry
+	for (state_idx, shared) in state_shared.iter().enumerate()
+	{
+		let mut states : CodeWriter = CodeWriter::new(1);
+
+		if opts.trampoline
 		{
-			states += "\t\tlet mut next_state : Option<fn(&mut Parser)> = None;\n"
+			// equivalent states are directly dispatched to their shared functions
+			if shared.is_some()
+			{
+				continue;
+			}
+
+			let (enter, resume) : (String, String) = create_state_body(state_idx, &state_idx.to_string(), opts, 2)
+				.map_err(|err| format!("State {state_idx}: {err}"))?;
+			states.block_comment(&get_items_comment(state_idx));
+			states.open(&format!("{vis}fn enter_{state_idx}(&mut self) -> Option<TIndex>"));
+			states.append(&enter);
+			states.line(&format!("self.resume_{state_idx}()"));
+			states.close("");  // end state function
+			states.line("");
+
+			states.open(&format!("{vis}fn resume_{state_idx}(&mut self) -> Option<TIndex>"));
+			states.append(&resume);
+			states.close("");  // end state function
+			funcs.push(states.finish());
+			continue;
 		}
 
-		states += "\t\tlet sym_id : TSymbolId = self.lookahead.as_ref().unwrap().id;\n";
-		states += "\t\tmatch sym_id\n\t\t{\n";
+		// kernel items of the state
+		states.block_comment(&get_items_comment(state_idx));
+		if opts.inline_jumps && is_leaf_state(state_idx)
+		{
+			// the state's code is possibly only run in the jump loops of other states
+			states.line("#[allow(dead_code)]");
+		}
+		states.open(&format!("{vis}fn state_{state_idx}(&mut self)"));
+		match *shared
+		{
+			Some(shared_idx) => states.line(&format!("self.shared_state_{shared_idx}({state_idx});")),
+			None =>
+			{
+				let (enter, resume) : (String, String) = create_state_body(state_idx, &state_idx.to_string(), opts, 2)
+					.map_err(|err| format!("State {state_idx}: {err}"))?;
+				states.append(&enter);
+				states.append(&resume);
+			},
+		}
+		states.close("");  // end state function
+		funcs.push(states.finish());
+	}
 
-		let mut rules_term_id : HashMap<TIndex, Vec<(TSymbolId, String)>>
-			= HashMap::<TIndex, Vec<(TSymbolId, String)>>::new();
-		let mut acc_term_id : Vec<(TSymbolId, String)> = Vec::<(TSymbolId, String)>::new();
+	for (shared_idx, ((enter, resume), equiv_states)) in shared_bodies.iter().enumerate()
+	{
+		let mut states : CodeWriter = CodeWriter::new(1);
 
-		for term_idx in 0..num_terms
+		if opts.trampoline
 		{
-			let newstate_idx = shift[term_idx];
-			let rule_idx = reduce[term_idx];
-			let (term_id, term_str) : (TSymbolId, String) = get_table_id_str(&lalr1_tables::TERM_IDX, term_idx);
-
-			if newstate_idx != lalr1_tables::ERR
+			let mut comment : Vec<String> = Vec::new();
+			for state_idx in equiv_states
 			{
-				states += &format!("\t\t\t{term_id} => next_state = Some(Parser::state_{newstate_idx}), // {term_str}\n");
+				comment.push(format!("state {state_idx}:"));
+				comment.extend(get_items_comment(*state_idx).iter().map(|item| item.to_string()));
 			}
-			else if rule_idx != lalr1_tables::ERR
+			states.block_comment(&comment);
+			states.open(&format!("{vis}fn shared_enter_{shared_idx}(&mut self, state_idx : TIndex) -> Option<TIndex>"));
+			states.append(enter);
+			states.line(&format!("self.shared_resume_{shared_idx}(state_idx)"));
+			states.close("");  // end state function
+			states.line("");
+
+			states.open(&format!("{vis}fn shared_resume_{shared_idx}(&mut self, state_idx : TIndex) -> Option<TIndex>"));
+			states.append(resume);
+			states.close("");  // end state function
+			funcs.push(states.finish());
+			continue;
+		}
+
+		states.comment("code of the equivalent states");
+		states.open(&format!("{vis}fn shared_state_{shared_idx}(&mut self, state_idx : TIndex)"));
+		states.append(enter);
+		states.append(resume);
+		states.close("");  // end state function
+		funcs.push(states.finish());
+	}
+
+	Ok(funcs)
+}
+
+
+/*
+ * create the loop running the state functions without recursion,
+ * the states return the next state to enter or None to return to the previous state
+ */
+fn create_trampoline(state_shared : &[Option<usize>], vis : &str) -> Vec<String>
+{
+	let mut funcs : Vec<String> = Vec::new();
+	let start_idx : TIndex = lalr1_tables::START;
+
+	// entry point of the parser
+	let mut run : CodeWriter = CodeWriter::new(1);
+	run.open(&format!("{vis}fn state_{start_idx}(&mut self)"));
+	run.comment("the active states, these would be on the call stack of a recursive parser");
+	run.line(&format!("let mut states : Vec<TIndex> = vec![{start_idx}];"));
+	run.line(&format!("let mut next_state : Option<TIndex> = self.enter_state({start_idx});"));
+	run.line("");
+	run.open("loop");
+	run.comment("continue after a syntax error in the state left on top of the stack");
+	run.open("if self.failed");
+	run.open("if !self.recover(&mut states)");
+	run.line("break;");
+	run.close("");
+	run.line("next_state = self.enter_state(*states.last().unwrap());");
+	run.line("continue;");
+	run.close("");
+	run.line("");
+	run.open("match next_state");
+	run.open("Some(state_idx) =>");
+	run.line("states.push(state_idx);");
+	run.line("next_state = self.enter_state(state_idx);");
+	run.close(",");
+	run.open("None =>");
+	run.comment("return to the previous state");
+	run.line("states.pop();");
+	run.open("match states.last()");
+	run.line("Some(state_idx) => next_state = self.resume_state(*state_idx),");
+	run.line("None => break,");
+	run.close("");  // end match
+	run.close(",");
+	run.close("");  // end match
+	run.close("");  // end loop
+	run.close("");  // end function
+	funcs.push(run.finish());
+
+	// leaving a state counts down the distance to the state with the jump
+	let mut leave : CodeWriter = CodeWriter::new(1);
+	leave.open(&format!("{vis}fn leave_state(&mut self) -> Option<TIndex>"));
+	leave.open("if !self.accepted && !self.failed");
+	leave.line("self.dist_to_jump -= 1;");
+	leave.close("");  // end if
+	leave.line("None");
+	leave.close("");  // end function
+	funcs.push(leave.finish());
+
+	// dispatch to the state functions
+	for (kind, shared_kind) in [("enter", "shared_enter"), ("resume", "shared_resume")]
+	{
+		let mut dispatch : CodeWriter = CodeWriter::new(1);
+		dispatch.open(&format!("{vis}fn {kind}_state(&mut self, state_idx : TIndex) -> Option<TIndex>"));
+		dispatch.open("match state_idx");
+
+		let mut shared_cases : Vec<Vec<TIndex>> = Vec::new();
+		for (state_idx, shared_idx) in state_shared.iter().enumerate()
+		{
+			match shared_idx
 			{
-				if rule_idx == lalr1_tables::ACC
-				{
-					acc_term_id.push((term_id, term_str));
-				}
-				else
+				Some(shared_idx) =>
 				{
-					let elem = rules_term_id.get_mut(&rule_idx);
-					if elem.is_some()
+					if shared_cases.len() <= *shared_idx
 					{
-						elem.unwrap().push((term_id, term_str));
+						shared_cases.resize(*shared_idx + 1, Vec::new());
 					}
-					else
-					{
-						rules_term_id.insert(rule_idx, [(term_id, term_str)].to_vec());
-					}
-				}
+					shared_cases[*shared_idx].push(state_idx);
+				},
+				None => dispatch.line(&format!("{state_idx} => self.{kind}_{state_idx}(),")),
 			}
 		}
 
-		for (rule_idx, sym_ids) in &rules_term_id
+		for (shared_idx, states) in shared_cases.iter().enumerate()
 		{
-			let cases : String = sym_ids.iter().map(|elem| elem.0.to_string()).
-				collect::<Vec<String>>().join(" | ");
-			let comment : String = sym_ids.iter().map(|elem| elem.1.clone()).
-				collect::<Vec<String>>().join(" | ");
+			let cases : String = states.iter().map(|state_idx| state_idx.to_string())
+				.collect::<Vec<String>>().join(" | ");
+			dispatch.line(&format!("{cases} => self.{shared_kind}_{shared_idx}(state_idx),"));
+		}
+
+		dispatch.line("_ => None,");
+		dispatch.close("");  // end match
+		dispatch.close("");  // end function
+		funcs.push(dispatch.finish());
+	}
+
+	funcs
+}
 
-			let rule_id : TSemanticId = get_semantic_table_id(
-				&lalr1_tables::SEMANTIC_IDX, *rule_idx);
-			let num_rhs : TIndex = lalr1_tables::NUM_RHS_SYMS[*rule_idx];
-			let lhs_id : TSymbolId = get_table_id(
-				&lalr1_tables::NONTERM_IDX, lalr1_tables::LHS_IDX[*rule_idx]);
 
-			states += &format!("\t\t\t// {comment}\n");
-			states += &format!("\t\t\t{cases} => self.apply_rule({rule_id}, {num_rhs}, {lhs_id}),\n");
+/*
+ * write the state functions into sub-modules of the parser module, e.g. for huge grammars,
+ * the parser is written to "<dir>/mod.rs" and the states to "<dir>/states_<n>.rs"
+ */
+fn write_state_modules(code : &str, funcs : &[String], dir : &Path, states_per_module : usize) -> Result<(), String>
+{
+	fs::create_dir_all(dir).map_err(|err| format!("Cannot create directory {dir:?}: {err}"))?;
+
+	let modules : Vec<&[String]> = funcs.chunks(states_per_module.max(1)).collect();
+	let mut parser : String = code.to_string();
+	parser += "\n\n// state functions\n";
+	for module_idx in 0..modules.len()
+	{
+		parser += &format!("mod states_{module_idx};\n");
+	}
+	write_code(&dir.join("mod.rs"), &parser, "parser")?;
+
+	for (module_idx, module) in modules.iter().enumerate()
+	{
+		let mut states : String = String::new();
+		states += "/*\n * State functions created using liblalr1 by Tobias Weber, 2020-2022.\n";
+		states += " * DOI: https://doi.org/10.5281/zenodo.6987396\n */\n\n";
+		states += "use super::*;\n\n";
+		states += "impl<T : Clone + Default + 'static, C : 'static> Parser<T, C>\n{\n";
+		states += &module.join("\n");
+		states += "}\n";
+
+		write_code(&dir.join(format!("states_{module_idx}.rs")), &states, "states")?;
+	}
+
+	Ok(())
+}
+
+
+/*
+ * write generated code to a file
+ */
+fn write_code(filename : &Path, code : &str, descr : &str) -> Result<(), String>
+{
+	let mut outfile = File::create(filename)
+		.map_err(|err| format!("Cannot create file {filename:?}: {err}"))?;
+
+	match outfile.write(code.as_bytes())
+	{
+		Ok(res) =>
+		{
+			println!("Successfully wrote {descr} {filename:?} with {res:?} bytes.");
+			Ok(())
+		},
+		Err(res) => Err(format!("Failed to write {descr} {filename:?}: {res:?}")),
+	}
+}
+
+
+/*
+ * integer types and sentinel values of the written tables
+ */
+struct TableTypes
+{
+	index : &'static str,      // type of the state, symbol and rule indices
+	symbol : &'static str,     // type of the symbol ids
+	semantic : &'static str,   // type of the rule ids
+	err : TIndex,              // sentinels, the index ones have the type of the indices
+	acc : TIndex,
+	eps : TSymbolId,           // the symbol ones have the type of the symbol ids
+	end : TSymbolId,
+	errtok : TSymbolId,
+}
+
+
+impl TableTypes
+{
+	/*
+	 * keep the types and sentinels of the tables
+	 */
+	fn new() -> TableTypes
+	{
+		TableTypes
+		{
+			index : std::any::type_name::<TIndex>(),
+			symbol : std::any::type_name::<TSymbolId>(),
+			semantic : std::any::type_name::<TSemanticId>(),
+			err : lalr1_tables::sentinels::ERR,
+			acc : lalr1_tables::sentinels::ACC,
+			eps : lalr1_tables::sentinels::EPS,
+			end : lalr1_tables::sentinels::END,
+			errtok : lalr1_tables::sentinels::ERRTOK,
 		}
+	}
+
+
+	/*
+	 * use the smallest unsigned types the grammar fits into,
+	 * the sentinels are moved to the largest values of these types
+	 */
+	fn narrow() -> TableTypes
+	{
+		let is_sentinel = |id : TSymbolId| -> bool
+		{
+			id == lalr1_tables::sentinels::EPS || id == lalr1_tables::sentinels::END
+				|| id == lalr1_tables::sentinels::ERRTOK
+		};
 
-		if acc_term_id.len() > 0
+		// the partial lhs table stores nonterminal ids as indices
+		let max_nonterm_id : usize = lalr1_tables::NONTERM_IDX.iter()
+			.map(|entry| to_usize(entry.0)).max().unwrap_or(0);
+		let max_index : usize = [ lalr1_tables::SHIFT.len(), lalr1_tables::TERM_IDX.len(),
+			lalr1_tables::NONTERM_IDX.len(), lalr1_tables::NUM_RHS_SYMS.len(),
+			lalr1_tables::NUM_RHS_SYMS.iter().map(|num| to_usize(*num) + 1).max().unwrap_or(0),
+			max_nonterm_id ].iter().copied().max().unwrap_or(0);
+		let max_symbol : usize = lalr1_tables::TERM_IDX.iter().map(|entry| entry.0)
+			.chain(lalr1_tables::NONTERM_IDX.iter().map(|entry| entry.0))
+			.filter(|id| !is_sentinel(*id))
+			.map(to_usize).max().unwrap_or(0);
+		let max_semantic : usize = lalr1_tables::SEMANTIC_IDX.iter()
+			.map(|entry| to_usize(entry.0)).max().unwrap_or(0);
+
+		// the type and its largest value, leaving space for the sentinels
+		let get_type = |max_val : usize, num_sentinels : usize| -> (&'static str, usize)
 		{
-			let acc_cases : String = acc_term_id.iter().map(|elem| elem.0.to_string()).
-				collect::<Vec<String>>().join(" | ");
-			let acc_comment : String = acc_term_id.iter().map(|elem| elem.1.clone()).
-				collect::<Vec<String>>().join(" | ");
+			match max_val + num_sentinels
+			{
+				val if val <= u8::MAX as usize => ("u8", u8::MAX as usize),
+				val if val <= u16::MAX as usize => ("u16", u16::MAX as usize),
+				val if val as u64 <= u32::MAX as u64 => ("u32", u32::MAX as usize),
+				_ => ("usize", usize::MAX),
+			}
+		};
+
+		let (index, max_index) : (&'static str, usize) = get_type(max_index, 2);
+		let (symbol, max_symbol) : (&'static str, usize) = get_type(max_symbol, 3);
+		let (semantic, _) : (&'static str, usize) = get_type(max_semantic, 0);
 
-			states += &format!("\t\t\t// {acc_comment}\n");
-			states += &format!("\t\t\t{acc_cases} => self.accepted = true,\n");
+		TableTypes
+		{
+			index,
+			symbol,
+			semantic,
+			err : max_index as TIndex,
+			acc : (max_index - 1) as TIndex,
+			eps : (max_symbol - 2) as TSymbolId,
+			end : (max_symbol - 1) as TSymbolId,
+			errtok : max_symbol as TSymbolId,
 		}
-		states += &format!("\t\t\t_ => self.error_term({}, sym_id),\n", state_idx);
-		states += "\t\t}\n";  // end match
+	}
+
 
-		if has_shift_entry
+	fn map_index(&self, idx : TIndex) -> TIndex
+	{
+		match idx
 		{
-			states += "\t\tif next_state.is_some()\n\t\t{\n";
-			states += "\t\t\tself.push_lookahead();\n";
-			states += "\t\t\tnext_state.unwrap()(self);\n";
-			states += "\t\t}\n";
+			_ if idx == lalr1_tables::sentinels::ERR => self.err,
+			_ if idx == lalr1_tables::sentinels::ACC => self.acc,
+			_ => idx,
 		}
+	}
 
-		if has_jump_entry
+
+	fn map_symbol(&self, id : TSymbolId) -> TSymbolId
+	{
+		match id
 		{
-			states += "\t\twhile self.dist_to_jump == 0 && self.symbol.len() > 0 && !self.accepted && !self.failed\n\t\t{\n";
+			_ if id == lalr1_tables::sentinels::EPS => self.eps,
+			_ if id == lalr1_tables::sentinels::END => self.end,
+			_ if id == lalr1_tables::sentinels::ERRTOK => self.errtok,
+			_ => id,
+		}
+	}
+}
 
-			states += "\t\t\tlet top_sym : &Symbol = self.get_top_symbol().unwrap();\n";
-			states += "\t\t\tif top_sym.is_term\n\t\t\t{\n";
-			states += "\t\t\t\tbreak;\n";
-			states += "\t\t\t}\n";  // end if
 
-			states += "\t\t\tmatch top_sym.id\n\t\t\t{\n";
+/*
+ * write a list of table entries
+ */
+fn create_list<I : Iterator<Item = String>>(entries : I) -> String
+{
+	format!("[{}]", entries.collect::<Vec<String>>().join(", "))
+}
 
-			for nonterm_idx in 0..num_nonterms
-			{
-				let jump_state_idx = jump[nonterm_idx];
-				if jump_state_idx != lalr1_tables::ERR
-				{
-					let (nonterm_id, nonterm_str) : (TSymbolId, String) = get_table_id_str(
-						&lalr1_tables::NONTERM_IDX, nonterm_idx);
 
-					states += &format!("\t\t\t\t{nonterm_id} => self.state_{jump_state_idx}(), // {nonterm_str}\n");
-				}
-			}
+/*
+ * write a table as a rust constant
+ */
+fn create_table<const N : usize>(name : &str, table : &[[TIndex; N]], types : &TableTypes) -> String
+{
+	let rows : String = create_list(table.iter().map(|row|
+		create_list(row.iter().map(|idx| types.map_index(*idx).to_string()))));
+	format!("pub const {name} : [[TIndex; {}]; {}] = {};\n", N, table.len(), rows)
+}
+
+
+/*
+ * copy the tables module, so that the generated parser doesn't depend on it
+ */
+fn create_tables(types : &TableTypes) -> String
+{
+	let mut tables : String = String::new();
+	tables += "// parsing tables\n";
+	tables += "#[allow(unused)]\n";
+	tables += "pub mod lalr1_tables\n{\n";
+
+	tables += &format!("pub type TIndex = {};\n", types.index);
+	tables += &format!("pub type TSymbolId = {};\n", types.symbol);
+	tables += &format!("pub type TSemanticId = {};\n\n", types.semantic);
+
+	tables += "pub mod sentinels\n{\n";
+	tables += "use super::{TIndex, TSymbolId};\n";
+	tables += &format!("pub const ERR : TIndex = {:#x};\n", types.err);
+	tables += &format!("pub const ACC : TIndex = {:#x};\n", types.acc);
+	tables += &format!("pub const EPS : TSymbolId = {:#x};\n", types.eps);
+	tables += &format!("pub const END : TSymbolId = {:#x};\n", types.end);
+	tables += &format!("pub const ERRTOK : TSymbolId = {:#x};\n", types.errtok);
+	tables += "}\n\n";
+
+	tables += &format!("pub const START : TIndex = {};\n", lalr1_tables::START);
+	tables += &format!("pub const ACCEPT : TIndex = {};\n", lalr1_tables::ACCEPT);
+	tables += &format!("pub const START_STATES : [(TSymbolId, TSymbolId, TIndex); {}] = {};\n\n",
+		lalr1_tables::START_STATES.len(), create_list(lalr1_tables::START_STATES.iter().map(
+			|entry| format!("({}, {}, {})", entry.0, entry.1, types.map_index(entry.2)))));
+
+	// the runtime functions of the common module still look at the lalr(1) tables
+	tables += &create_table("SHIFT", &lalr1_tables::SHIFT, types);
+	tables += &create_table("REDUCE", &lalr1_tables::REDUCE, types);
+	tables += &create_table("JUMP", &lalr1_tables::JUMP, types);
+	tables += &create_table("PARTIALS_RULE_TERM", &lalr1_tables::PARTIALS_RULE_TERM, types);
+	tables += &create_table("PARTIALS_MATCHLEN_TERM", &lalr1_tables::PARTIALS_MATCHLEN_TERM, types);
+	tables += &create_table("PARTIALS_RULE_NONTERM", &lalr1_tables::PARTIALS_RULE_NONTERM, types);
+	tables += &create_table("PARTIALS_MATCHLEN_NONTERM", &lalr1_tables::PARTIALS_MATCHLEN_NONTERM, types);
+	tables += &create_table("PARTIALS_LHS_NONTERM", &lalr1_tables::PARTIALS_LHS_NONTERM, types);
+	tables += "\n";
+
+	// symbol names and rule infos
+	tables += &format!("pub const TERM_IDX : [(TSymbolId, TIndex, &str); {}] = {};\n",
+		lalr1_tables::TERM_IDX.len(), create_list(lalr1_tables::TERM_IDX.iter().map(
+			|entry| format!("({}, {}, {:?})", types.map_symbol(entry.0), entry.1, entry.2))));
+	tables += &format!("pub const NONTERM_IDX : [(TSymbolId, TIndex, &str); {}] = {:?};\n",
+		lalr1_tables::NONTERM_IDX.len(), lalr1_tables::NONTERM_IDX);
+	tables += &format!("pub const TERM_DOC : [(TSymbolId, &str); {}] = {};\n",
+		lalr1_tables::TERM_DOC.len(), create_list(lalr1_tables::TERM_DOC.iter().map(
+			|entry| format!("({}, {:?})", types.map_symbol(entry.0), entry.1))));
+	tables += &format!("pub const NONTERM_DOC : [(TSymbolId, &str); {}] = {:?};\n",
+		lalr1_tables::NONTERM_DOC.len(), lalr1_tables::NONTERM_DOC);
+	tables += &format!("pub const SEMANTIC_IDX : [(TSemanticId, TIndex); {}] = {:?};\n",
+		lalr1_tables::SEMANTIC_IDX.len(), lalr1_tables::SEMANTIC_IDX);
+	tables += &format!("pub const NUM_RHS_SYMS : [TIndex; {}] = {:?};\n",
+		lalr1_tables::NUM_RHS_SYMS.len(), lalr1_tables::NUM_RHS_SYMS);
+	tables += &format!("pub const LHS_IDX : [TIndex; {}] = {:?};\n",
+		lalr1_tables::LHS_IDX.len(), lalr1_tables::LHS_IDX);
+	tables += &format!("pub const DEFAULT_REDUCE : [TIndex; {}] = {};\n",
+		lalr1_tables::DEFAULT_REDUCE.len(), create_list(lalr1_tables::DEFAULT_REDUCE.iter().map(
+			|idx| types.map_index(*idx).to_string())));
+	tables += &format!("pub const RULE_STRS : [&str; {}] = {:?};\n",
+		lalr1_tables::RULE_STRS.len(), lalr1_tables::RULE_STRS);
+
+	tables += "}\n";  // end of module
+	tables
+}
+
+
+/*
+ * type aliases for the runtime, if the embedded tables have other types than the tables module,
+ * the types module has to re-export them, so that the runtime works on the same tables
+ */
+fn create_table_types(opts : &GenOptions) -> String
+{
+	if !opts.narrow_types
+	{
+		return "".to_string();
+	}
+
+	let mut types : String = String::new();
+	types += &format!("\n// types of the embedded tables, the types module re-exports them with its narrow_types feature: \"pub use {}::lalr1_types::*;\"\n",
+		get_module_name(opts));
+	types += "#[allow(unused)]\n";
+	types += "pub mod lalr1_types\n{\n";
+	types += "pub use super::lalr1_tables;\n";
+	types += "pub type TIndex = lalr1_tables::TIndex;\n";
+	types += "pub type TSymbolId = lalr1_tables::TSymbolId;\n";
+	types += "pub type TSemanticId = lalr1_tables::TSemanticId;\n";
+	types += "}\n";  // end of module
+	types
+}
+
+
+/*
+ * get the name of the generated parser's module from its file name
+ */
+fn get_module_name(opts : &GenOptions) -> String
+{
+	Path::new(&opts.outfile).file_stem()
+		.map_or("generated_parser".to_string(), |name| name.to_string_lossy().to_string())
+}
+
 
-			states += &format!("\t\t\t\t_ => self.error_nonterm({}, top_sym.id),\n", state_idx);
+/*
+ * copy the table-driven parser, using the given modules and the tables
+ */
+fn create_table_parser(opts : &GenOptions) -> String
+{
+	let header : &str = "/*\n * Parser created using liblalr1 by Tobias Weber, 2020-2022.\n \
+		* DOI: https://doi.org/10.5281/zenodo.6987396\n */\n";
+
+	// skip the comment of the source file
+	let code : &str = match TABLE_CODE.find("*/\n")
+	{
+		Some(pos) => &TABLE_CODE[pos + 3 ..],
+		None => TABLE_CODE,
+	};
+
+	let modules : String = format!("use {}::{{*}};\nuse {}::{{*}};\n\n{}",
+		opts.types_module, opts.common_module,
+		create_tables(&opts.get_table_types()) + &create_table_types(opts));
+
+	// the value type is the default argument of the parser's generic types
+	let value_type : String = format!("= {}", opts.value_type);
+	header.to_string() + &code.replacen("use types::{*};\nuse common::{*};\n", &modules, 1)
+		.replace("= TLVal", &value_type)
+}
+
+
+/*
+ * read a skeleton with the %%MARKERS%% to replace from a file or use the built-in one
+ */
+fn load_template(filename : &Option<String>, builtin : &str) -> Result<String, String>
+{
+	let filename : &str = match filename
+	{
+		Some(filename) => filename,
+		None => return Ok(builtin.to_string()),
+	};
+
+	fs::read_to_string(filename).map_err(|err| format!("Cannot read template \"{filename}\": {err}"))
+}
 
-			states += "\t\t\t}\n";  // end match
-			states += "\t\t}\n";  // end while
+
+/*
+ * write the parser, its visitor and the semantic function stubs,
+ * inconsistent tables are reported with the state and the table entry
+ */
+fn generate(opts : &GenOptions) -> Result<(), String>
+{
+	let code : String = if opts.backend == Backend::Table
+	{
+		create_table_parser(opts)
+	}
+	else
+	{
+		// the error token needs the states on an explicit stack
+		if !opts.trampoline && lalr1_tables::TERM_IDX.iter().any(|entry| entry.0 == lalr1_tables::sentinels::ERRTOK)
+		{
+			println!("Warning: Error productions are not supported by the recursive ascent parser without trampoline.");
 		}
 
-		states += "\t\tif !self.accepted && !self.failed\n\t\t{\n";
-		states += "\t\t\tself.dist_to_jump -= 1;\n";
-		states += "\t\t}\n";  // end if
+		let code : String = load_template(&opts.template, CODE)?;
+		// the states are moved into sub-modules if they are split
+		let states : String = match opts.states_per_module
+		{
+			Some(_) => "".to_string(),
+			None => create_states(opts, "")?.join("\n"),
+		};
+		// the debug messages are dropped at compile time if the predicate doesn't hold
+		let debug_output : String = match &opts.debug_cfg
+		{
+			Some(debug_cfg) => format!("cfg!({debug_cfg})"),
+			None => "true".to_string(),
+		};
+		// narrow types need their own tables
+		let tables : String = match opts.embed_tables || opts.narrow_types
+		{
+			true => create_tables(&opts.get_table_types()) + &create_table_types(opts),
+			false => "".to_string(),
+		};
+		code
+			.replace("%%STATES%%", &states)
+			.replace("%%START_IDX%%", &lalr1_tables::START.to_string())
+			.replace("%%USE_PARTIALS%%", &opts.gen_partials.to_string())
+			.replace("%%RECOVERY%%", &opts.trampoline.to_string())
+			.replace("%%DEBUG_OUTPUT%%", &debug_output)
+			.replace("%%VALUE_TYPE%%", &opts.value_type)
+			.replace("%%TABLES%%", &tables)
+			.replace("%%TYPES_MODULE%%", &opts.types_module)
+			.replace("%%COMMON_MODULE%%", &opts.common_module)
+	};
+
+	match opts.states_per_module
+	{
+		Some(states_per_module) if opts.backend == Backend::Ascent =>
+		{
+			// e.g. "generated_parser.rs" -> "generated_parser/mod.rs"
+			let dir : PathBuf = Path::new(&opts.outfile).with_extension("");
+			let funcs : Vec<String> = create_states(opts, "pub(super) ")?;
+			write_state_modules(&code, &funcs, &dir, states_per_module)?;
+		},
+		_ => write_code(Path::new(&opts.outfile), &code, "parser")?,
+	}
 
-		states += "\t}\n";  // end state function
-		if state_idx < num_states-1 { states += "\n"; }
+	if opts.narrow_types
+	{
+		println!("Note: Build the parser's driver with the narrow_types feature, so that its types module uses \"{}::lalr1_types\".",
+			get_module_name(opts));
 	}
 
-	states
+	// visitor for the parse trees of both parsers
+	let (methods, dispatch) : (String, String) = create_visitor()?;
+	let visitor : String = load_template(&opts.visitor_template, VISITOR_CODE)?
+		.replace("%%METHODS%%", &methods)
+		.replace("%%DISPATCH%%", &dispatch)
+		.replace("%%COMMON_MODULE%%", &opts.common_module);
+
+	write_code(Path::new(&opts.visitor_outfile), &visitor, "visitor")?;
+
+	if let Some(semantics_outfile) = &opts.semantics_outfile
+	{
+		write_code(Path::new(semantics_outfile), &create_semantics(opts), "semantics")?;
+	}
+
+	Ok(())
 }
 
 
 fn main()
 {
-	let mut code = CODE.to_string();
-	let states : String = create_states();
-	code = code
-		.replace("%%STATES%%", &states)
-		.replace("%%START_IDX%%", &lalr1_tables::START.to_string());
+	let args : Vec<String> = env::args().collect();
+	let opts : GenOptions = match GenOptions::from_args(&args[1 ..])
+	{
+		Some(opts) => opts,
+		None =>
+		{
+			GenOptions::print_usage(&args[0]);
 
-	let outfilename : &str = &"generated_parser.rs";
-	let mut outfile = File::create(outfilename).expect("Cannot create file.");
-	match outfile.write(code.as_bytes())
+			// only an explicitly requested usage is no error
+			let help : bool = args[1 ..].iter().any(|arg| arg == "-h" || arg == "--help");
+			process::exit(if help { 0 } else { 1 });
+		},
+	};
+
+	if let Err(err) = generate(&opts)
 	{
-		Ok(res) => println!("Successfully wrote parser \"{outfilename}\" with {res:?} bytes."),
-		Err(res) => println!("Failed to write parser \"{outfilename}\": {res:?}."),
+		println!("Error: {err}.");
+		process::exit(1);
 	}
 }
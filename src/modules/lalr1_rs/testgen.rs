@@ -0,0 +1,753 @@
+/*
+ * generates test sentences covering all state transitions and reductions
+ *
+ * @author Tobias Weber (orcid: 0000-0002-7230-1932)
+ * @date 15-oct-2026
+ * @license see 'LICENSE' file
+ *
+ * References:
+ *      - "Compilerbau Teil 1", ISBN: 3-486-25294-1 (1999)
+ *      - "Übersetzerbau", ISBN: 978-3540653899 (1999, 2013)
+ */
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::env;
+use std::fs::File;
+use std::io::Write;
+use std::process;
+
+mod expr;
+mod types;
+mod idents;
+
+use types::*;
+
+
+// maximum number of parser configurations to visit in a search
+const MAX_CONFIGS : usize = 100000;
+
+
+/*
+ * parser actions that should be covered by the test sentences
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Coverage
+{
+	Shift(TIndex, TIndex),     // state, terminal index
+	Jump(TIndex, TIndex),      // state, nonterminal index
+	Reduce(TIndex),            // rule index
+	Accept,
+}
+
+
+enum Step
+{
+	Shifted,
+	Accepted,
+	Failed,
+}
+
+
+/*
+ * a parser configuration reached by the breadth-first search
+ */
+struct Config
+{
+	stack : Vec<TIndex>,
+	parent : Option<usize>,
+	term_idx : TIndex,
+	covered : Vec<Coverage>,
+}
+
+
+/*
+ * run the parsing tables on one lookahead terminal,
+ * performing all reductions until the terminal is shifted
+ */
+fn step(stack : &mut Vec<TIndex>, term_idx : TIndex, covered : &mut Vec<Coverage>) -> Step
+{
+	let err = lalr1_tables::sentinels::ERR;
+	let acc = lalr1_tables::sentinels::ACC;
+
+	loop
+	{
+		let top_state : TIndex = *stack.last().unwrap();
+		let mut new_state : TIndex = err;
+		let mut rule_idx : TIndex = lalr1_tables::DEFAULT_REDUCE[top_state];
+		if rule_idx == err
+		{
+			new_state = lalr1_tables::SHIFT[top_state][term_idx];
+			rule_idx = lalr1_tables::REDUCE[top_state][term_idx];
+		}
+
+		if (new_state == err && rule_idx == err) || (new_state != err && rule_idx != err)
+		{
+			return Step::Failed;
+		}
+		else if rule_idx == acc
+		{
+			covered.push(Coverage::Accept);
+			return Step::Accepted;
+		}
+		else if new_state != err
+		{
+			covered.push(Coverage::Shift(top_state, term_idx));
+			stack.push(new_state);
+			return Step::Shifted;
+		}
+
+		// reduce
+		let num_rhs = lalr1_tables::NUM_RHS_SYMS[rule_idx];
+		let lhs_idx = lalr1_tables::LHS_IDX[rule_idx];
+		if stack.len() <= num_rhs
+		{
+			return Step::Failed;
+		}
+		stack.truncate(stack.len() - num_rhs);
+
+		let jump_from : TIndex = *stack.last().unwrap();
+		let jump_state : TIndex = lalr1_tables::JUMP[jump_from][lhs_idx];
+		if jump_state == err
+		{
+			return Step::Failed;
+		}
+
+		covered.push(Coverage::Reduce(rule_idx));
+		covered.push(Coverage::Jump(jump_from, lhs_idx));
+		stack.push(jump_state);
+	}
+}
+
+
+/*
+ * get the table index of a terminal, e.g. the end or error token
+ */
+fn get_term_index(id : TSymbolId) -> TIndex
+{
+	for entry in lalr1_tables::TERM_IDX.iter()
+	{
+		if entry.0 == id
+		{
+			return entry.1;
+		}
+	}
+
+	lalr1_tables::sentinels::ERR
+}
+
+
+/*
+ * get the string identifier of a terminal table index
+ */
+fn get_term_str(term_idx : TIndex) -> String
+{
+	for entry in lalr1_tables::TERM_IDX.iter()
+	{
+		if entry.1 == term_idx
+		{
+			return entry.2.to_string();
+		}
+	}
+
+	format!("<{term_idx}>")
+}
+
+
+/*
+ * get the id of a terminal table index
+ */
+fn get_term_id(term_idx : TIndex) -> TSymbolId
+{
+	for entry in lalr1_tables::TERM_IDX.iter()
+	{
+		if entry.1 == term_idx
+		{
+			return entry.0;
+		}
+	}
+
+	lalr1_tables::sentinels::ERR as TSymbolId
+}
+
+
+/*
+ * get the string identifier of a nonterminal table index
+ */
+fn get_nonterm_str(nonterm_idx : TIndex) -> String
+{
+	for entry in lalr1_tables::NONTERM_IDX.iter()
+	{
+		if entry.1 == nonterm_idx
+		{
+			return entry.2.to_string();
+		}
+	}
+
+	format!("<{nonterm_idx}>")
+}
+
+
+/*
+ * run the parsing tables on a sentence, the end terminal is appended
+ */
+fn parse_sentence(sentence : &[TIndex], covered : &mut Vec<Coverage>) -> bool
+{
+	let end_idx = get_term_index(lalr1_tables::sentinels::END);
+	let mut stack = vec![lalr1_tables::START];
+
+	for term_idx in sentence.iter()
+	{
+		if let Step::Failed | Step::Accepted = step(&mut stack, *term_idx, covered)
+		{
+			return false;
+		}
+	}
+
+	matches!(step(&mut stack, end_idx, covered), Step::Accepted)
+}
+
+
+/*
+ * get the concrete syntax tree of an accepted sentence as s-expression,
+ * in the format the parse tree is written, e.g. "(expr (expr 1) + (expr 2))"
+ */
+fn get_tree(sentence : &[TIndex]) -> Option<String>
+{
+	let mut actions : Vec<Coverage> = Vec::new();
+	if !parse_sentence(sentence, &mut actions)
+	{
+		return None;
+	}
+
+	// replay the shifts and reductions on a stack of subtrees
+	let mut trees : Vec<String> = Vec::new();
+	for action in actions.iter()
+	{
+		match action
+		{
+			Coverage::Shift(_, term_idx) => trees.push(get_term_str(*term_idx)),
+			Coverage::Reduce(rule_idx) =>
+			{
+				let num_rhs = lalr1_tables::NUM_RHS_SYMS[*rule_idx];
+				let args : Vec<String> = trees.split_off(trees.len() - num_rhs);
+				let mut tree : String = format!("({}", get_nonterm_str(lalr1_tables::LHS_IDX[*rule_idx]));
+				for arg in args.iter()
+				{
+					tree += " ";
+					tree += arg;
+				}
+				tree += ")";
+				trees.push(tree);
+			},
+			_ => {},
+		}
+	}
+
+	trees.pop()
+}
+
+
+/*
+ * derive rejected sentences from the accepted ones, either by cutting off
+ * the last terminal or by replacing it with one that has no action
+ */
+fn create_rejected(sentences : &[Vec<TIndex>]) -> Vec<Vec<TIndex>>
+{
+	let num_terms = lalr1_tables::SHIFT[0].len();
+	let end_idx = get_term_index(lalr1_tables::sentinels::END);
+	let errtok_idx = get_term_index(lalr1_tables::sentinels::ERRTOK);
+
+	let mut rejected : Vec<Vec<TIndex>> = Vec::new();
+	let mut add = |sentence : Vec<TIndex>|
+	{
+		if !rejected.contains(&sentence) && !parse_sentence(&sentence, &mut Vec::new())
+		{
+			rejected.push(sentence);
+		}
+	};
+
+	for sentence in sentences.iter()
+	{
+		if sentence.is_empty()
+		{
+			continue;
+		}
+
+		let prefix : &[TIndex] = &sentence[0 .. sentence.len() - 1];
+		add(prefix.to_vec());
+
+		for term_idx in 0..num_terms
+		{
+			if term_idx == end_idx || term_idx == errtok_idx
+			{
+				continue;
+			}
+
+			let mut stack = vec![lalr1_tables::START];
+			let prefix_ok : bool = prefix.iter().all(|term|
+				matches!(step(&mut stack, *term, &mut Vec::new()), Step::Shifted));
+			if prefix_ok && matches!(step(&mut stack, term_idx, &mut Vec::new()), Step::Failed)
+			{
+				let mut wrong : Vec<TIndex> = prefix.to_vec();
+				wrong.push(term_idx);
+				add(wrong);
+				break;
+			}
+		}
+	}
+
+	rejected
+}
+
+
+/*
+ * write a test module for the parser, it has helpers to parse terminal ids
+ * and to check the resulting values and syntax trees, the test cases are
+ * the given sentences with their syntax trees and the rejected ones
+ */
+fn create_test_module(sentences : &[Vec<TIndex>], rejected : &[Vec<TIndex>],
+	parser : &str, types_module : &str, common_module : &str) -> String
+{
+	let mut code : String = String::new();
+	code += "/*\n * Parser tests created using liblalr1 by Tobias Weber, 2020-2022.\n";
+	code += " * DOI: https://doi.org/10.5281/zenodo.6987396\n */\n\n";
+	code += "#![cfg(test)]\n#![allow(dead_code)]\n\n";
+	code += &format!("use {types_module}::{{*}};\nuse {common_module}::{{*}};\nuse {parser};\n\n");
+
+	// helpers
+	code += "/*\n * create terminal tokens with the given ids and values, followed by the end token\n */\n";
+	code += "pub fn to_tokens<T : Clone + Default>(ids : &[TSymbolId], vals : &[T]) -> Vec<Symbol<T>>\n{\n";
+	code += "\tlet mut tokens : Vec<Symbol<T>> = ids.iter().enumerate().map(|(idx, id)| Symbol { is_term : true,\n";
+	code += "\t\tid : *id, val : vals.get(idx).cloned().unwrap_or_default(), ..Default::default() }).collect();\n";
+	code += "\ttokens.push(Symbol { is_term : true, id : lalr1_tables::sentinels::END, ..Default::default() });\n";
+	code += "\ttokens\n";
+	code += "}\n\n";
+
+	code += "/*\n * parse the terminals into a syntax tree, written as s-expression,\n";
+	code += " * the tokens are either given as a whole or read lazily from a stream\n */\n";
+	code += "pub fn parse_tree(ids : &[TSymbolId], streamed : bool) -> Result<String, Box<ParseError<ParseTree>>>\n{\n";
+	code += "\tlet mut parser : Parser<ParseTree> = Parser::new();\n";
+	code += "\tparser.set_tree_mode();\n";
+	code += "\tlet tokens : Vec<Symbol<ParseTree>> = to_tokens(ids, &[]);\n";
+	code += "\tmatch streamed\n\t{\n";
+	code += "\t\ttrue => parser.set_input_iter(tokens.into_iter()),\n";
+	code += "\t\tfalse => parser.set_input_vec(tokens),\n";
+	code += "\t}\n";
+	code += "\tparser.parse_tree().map(|tree| tree.to_string())\n";
+	code += "}\n\n";
+
+	code += "/*\n * parse the tokens using the parser's semantic functions\n */\n";
+	code += "pub fn parse_value<T>(parser : &mut dyn Parsable<T>, tokens : Vec<Symbol<T>>) -> Result<T, Box<ParseError<T>>>\n{\n";
+	code += "\tparser.set_input_vec(tokens);\n";
+	code += "\tparser.try_parse().map(|result| result.val)\n";
+	code += "}\n\n";
+
+	code += "pub fn assert_tree(ids : &[TSymbolId], expected : &str)\n{\n";
+	code += "\tfor streamed in [false, true]\n\t{\n";
+	code += "\t\tmatch parse_tree(ids, streamed)\n\t\t{\n";
+	code += "\t\t\tOk(tree) => assert_eq!(tree, expected, \"wrong syntax tree for input {:?}\", ids),\n";
+	code += "\t\t\tErr(err) => panic!(\"input {:?} was rejected: {}\", ids, err),\n";
+	code += "\t\t}\n";
+	code += "\t}\n";
+	code += "}\n\n";
+
+	code += "pub fn assert_rejects(ids : &[TSymbolId])\n{\n";
+	code += "\tfor streamed in [false, true]\n\t{\n";
+	code += "\t\tif let Ok(tree) = parse_tree(ids, streamed)\n\t\t{\n";
+	code += "\t\t\tpanic!(\"input {:?} was accepted as {}\", ids, tree);\n";
+	code += "\t\t}\n";
+	code += "\t}\n";
+	code += "}\n\n";
+
+	code += "pub fn assert_value<T : PartialEq + std::fmt::Debug>(parser : &mut dyn Parsable<T>,\n";
+	code += "\ttokens : Vec<Symbol<T>>, expected : T)\n{\n";
+	code += "\tmatch parse_value(parser, tokens)\n\t{\n";
+	code += "\t\tOk(val) => assert_eq!(val, expected),\n";
+	code += "\t\tErr(err) => panic!(\"input was rejected: {}\", err),\n";
+	code += "\t}\n";
+	code += "}\n";
+
+	let get_ids = |sentence : &Vec<TIndex>| -> (String, String)
+	{
+		let ids : String = sentence.iter().map(|term_idx| get_term_id(*term_idx).to_string())
+			.collect::<Vec<String>>().join(", ");
+		let strs : String = match sentence.is_empty()
+		{
+			true => "empty input".to_string(),
+			false => sentence.iter().map(|term_idx| get_term_str(*term_idx))
+				.collect::<Vec<String>>().join(" "),
+		};
+		(ids, strs)
+	};
+
+	// test cases
+	for (idx, sentence) in sentences.iter().enumerate()
+	{
+		let tree : String = match get_tree(sentence)
+		{
+			Some(tree) => tree,
+			None => continue,
+		};
+		let (ids, strs) : (String, String) = get_ids(sentence);
+
+		code += &format!("\n// {strs}\n#[test]\nfn accept_{idx}()\n{{\n");
+		code += &format!("\tassert_tree(&[{ids}], {tree:?});\n");
+		code += "}\n";
+	}
+
+	for (idx, sentence) in rejected.iter().enumerate()
+	{
+		let (ids, strs) : (String, String) = get_ids(sentence);
+
+		code += &format!("\n// {strs}\n#[test]\nfn reject_{idx}()\n{{\n");
+		code += &format!("\tassert_rejects(&[{ids}]);\n");
+		code += "}\n";
+	}
+
+	code
+}
+
+
+/*
+ * find the shortest terminal sequence leading from the given stack to acceptance
+ */
+fn find_completion(stack : &[TIndex], end_idx : TIndex, max_depth : usize) -> Option<Vec<TIndex>>
+{
+	let num_terms = lalr1_tables::SHIFT[0].len();
+	let errtok_idx = get_term_index(lalr1_tables::sentinels::ERRTOK);
+
+	let mut seen : HashSet<Vec<TIndex>> = HashSet::new();
+	let mut queue : VecDeque<(Vec<TIndex>, Vec<TIndex>)> = VecDeque::new();
+	queue.push_back((stack.to_vec(), Vec::new()));
+	seen.insert(stack.to_vec());
+
+	while let Some((cur_stack, terms)) = queue.pop_front()
+	{
+		// can the input end here?
+		let mut end_stack = cur_stack.clone();
+		if let Step::Accepted = step(&mut end_stack, end_idx, &mut Vec::new())
+		{
+			return Some(terms);
+		}
+
+		if seen.len() >= MAX_CONFIGS
+		{
+			continue;
+		}
+
+		for term_idx in 0..num_terms
+		{
+			if term_idx == end_idx || term_idx == errtok_idx
+			{
+				continue;
+			}
+
+			let mut new_stack = cur_stack.clone();
+			if let Step::Shifted = step(&mut new_stack, term_idx, &mut Vec::new())
+			{
+				if new_stack.len() > max_depth || seen.contains(&new_stack)
+				{
+					continue;
+				}
+
+				let mut new_terms = terms.clone();
+				new_terms.push(term_idx);
+				seen.insert(new_stack.clone());
+				queue.push_back((new_stack, new_terms));
+			}
+		}
+	}
+
+	None
+}
+
+
+/*
+ * get the states that can be reached without shifting the error token
+ */
+fn get_reachable_states(errtok_idx : TIndex) -> Vec<bool>
+{
+	let err = lalr1_tables::sentinels::ERR;
+	let mut reachable : Vec<bool> = vec![false; lalr1_tables::SHIFT.len()];
+	let mut todo : Vec<TIndex> = vec![lalr1_tables::START];
+
+	while let Some(state_idx) = todo.pop()
+	{
+		if reachable[state_idx]
+		{
+			continue;
+		}
+		reachable[state_idx] = true;
+
+		for (term_idx, new_state) in lalr1_tables::SHIFT[state_idx].iter().enumerate()
+		{
+			if *new_state != err && term_idx != errtok_idx
+			{
+				todo.push(*new_state);
+			}
+		}
+
+		for jump_state in lalr1_tables::JUMP[state_idx].iter()
+		{
+			if *jump_state != err
+			{
+				todo.push(*jump_state);
+			}
+		}
+	}
+
+	reachable
+}
+
+
+/*
+ * get all table entries that are to be covered
+ */
+fn get_all_coverage() -> HashSet<Coverage>
+{
+	let err = lalr1_tables::sentinels::ERR;
+	let acc = lalr1_tables::sentinels::ACC;
+	let errtok_idx = get_term_index(lalr1_tables::sentinels::ERRTOK);
+	let mut all : HashSet<Coverage> = HashSet::new();
+
+	// the error token is never part of the input,
+	// so the states only reachable by it can't be covered
+	let reachable = get_reachable_states(errtok_idx);
+
+	for (state_idx, is_reachable) in reachable.iter().enumerate()
+	{
+		if !*is_reachable
+		{
+			continue;
+		}
+
+		for (term_idx, new_state) in lalr1_tables::SHIFT[state_idx].iter().enumerate()
+		{
+			if *new_state != err && term_idx != errtok_idx
+			{
+				all.insert(Coverage::Shift(state_idx, term_idx));
+			}
+		}
+
+		for rule_idx in lalr1_tables::REDUCE[state_idx].iter()
+		{
+			if *rule_idx == acc
+			{
+				all.insert(Coverage::Accept);
+			}
+			else if *rule_idx != err
+			{
+				all.insert(Coverage::Reduce(*rule_idx));
+			}
+		}
+
+		if lalr1_tables::DEFAULT_REDUCE[state_idx] != err
+		{
+			all.insert(Coverage::Reduce(lalr1_tables::DEFAULT_REDUCE[state_idx]));
+		}
+
+		for (nonterm_idx, jump_state) in lalr1_tables::JUMP[state_idx].iter().enumerate()
+		{
+			if *jump_state != err
+			{
+				all.insert(Coverage::Jump(state_idx, nonterm_idx));
+			}
+		}
+	}
+
+	all
+}
+
+
+/*
+ * search the parser configurations breadth-first and emit a sentence
+ * whenever a configuration covers a not yet seen table entry
+ */
+fn create_sentences(max_depth : usize) -> (Vec<Vec<TIndex>>, HashSet<Coverage>)
+{
+	let num_terms = lalr1_tables::SHIFT[0].len();
+	let end_idx = get_term_index(lalr1_tables::sentinels::END);
+	let errtok_idx = get_term_index(lalr1_tables::sentinels::ERRTOK);
+
+	let mut sentences : Vec<Vec<TIndex>> = Vec::new();
+	let mut covered : HashSet<Coverage> = HashSet::new();
+
+	let mut configs : Vec<Config> = Vec::new();
+	let mut seen : HashSet<Vec<TIndex>> = HashSet::new();
+	let mut completions : HashMap<Vec<TIndex>, Option<Vec<TIndex>>> = HashMap::new();
+
+	configs.push(Config { stack : vec![lalr1_tables::START], parent : None,
+		term_idx : end_idx, covered : Vec::new() });
+	seen.insert(configs[0].stack.clone());
+
+	let mut cur_idx : usize = 0;
+	while cur_idx < configs.len()
+	{
+		// expand the configuration
+		if configs.len() < MAX_CONFIGS
+		{
+			for term_idx in 0..num_terms
+			{
+				if term_idx == end_idx || term_idx == errtok_idx
+				{
+					continue;
+				}
+
+				let mut new_stack = configs[cur_idx].stack.clone();
+				let mut new_covered : Vec<Coverage> = Vec::new();
+				if let Step::Shifted = step(&mut new_stack, term_idx, &mut new_covered)
+				{
+					if new_stack.len() > max_depth || seen.contains(&new_stack)
+					{
+						continue;
+					}
+
+					seen.insert(new_stack.clone());
+					configs.push(Config { stack : new_stack, parent : Some(cur_idx),
+						term_idx, covered : new_covered });
+				}
+			}
+		}
+
+		// does the configuration cover new entries?
+		let mut end_covered : Vec<Coverage> = Vec::new();
+		step(&mut configs[cur_idx].stack.clone(), end_idx, &mut end_covered);
+
+		let has_new = configs[cur_idx].covered.iter().chain(end_covered.iter())
+			.any(|item| !covered.contains(item));
+		if has_new
+		{
+			let stack = configs[cur_idx].stack.clone();
+			if !completions.contains_key(&stack)
+			{
+				let completion = find_completion(&stack, end_idx, max_depth);
+				completions.insert(stack.clone(), completion);
+			}
+
+			if let Some(completion) = &completions[&stack]
+			{
+				// get the prefix leading to this configuration
+				let mut sentence : Vec<TIndex> = Vec::new();
+				let mut idx : Option<usize> = Some(cur_idx);
+				while let Some(cfg_idx) = idx
+				{
+					if configs[cfg_idx].parent.is_some()
+					{
+						sentence.insert(0, configs[cfg_idx].term_idx);
+					}
+					idx = configs[cfg_idx].parent;
+				}
+				sentence.extend(completion.iter());
+
+				// mark everything this sentence covers
+				let mut stack_sentence = vec![lalr1_tables::START];
+				let mut sentence_covered : Vec<Coverage> = Vec::new();
+				for term_idx in sentence.iter().chain([end_idx].iter())
+				{
+					step(&mut stack_sentence, *term_idx, &mut sentence_covered);
+				}
+				covered.extend(sentence_covered);
+
+				sentences.push(sentence);
+			}
+		}
+
+		cur_idx += 1;
+	}
+
+	(sentences, covered)
+}
+
+
+fn main()
+{
+	// optional test module and the paths it uses
+	let mut test_module : Option<String> = None;
+	let mut parser : String = "generated_parser::Parser".to_string();
+	let mut types_module : String = "types".to_string();
+	let mut common_module : String = "common".to_string();
+
+	let args : Vec<String> = env::args().collect();
+	let mut iter = args.iter().skip(1);
+	while let Some(arg) = iter.next()
+	{
+		let value : &mut String = match arg.as_str()
+		{
+			"--test-module" => test_module.get_or_insert_with(String::new),
+			"--parser" => &mut parser,
+			"--types-module" => &mut types_module,
+			"--common-module" => &mut common_module,
+			_ =>
+			{
+				println!("Usage: {} [--test-module <file>] [--parser <path>] [--types-module <path>] [--common-module <path>]", args[0]);
+
+				// only an explicitly requested usage is no error
+				process::exit(if arg == "-h" || arg == "--help" { 0 } else { 1 });
+			},
+		};
+
+		match iter.next()
+		{
+			Some(val) => *value = val.clone(),
+			None =>
+			{
+				println!("Error: Missing value for option \"{arg}\".");
+				process::exit(1);
+			},
+		}
+	}
+
+	let max_depth : usize = 2 * lalr1_tables::SHIFT.len();
+	let (sentences, covered) = create_sentences(max_depth);
+	let all = get_all_coverage();
+
+	let mut corpus : String = String::new();
+	corpus += "# test sentences covering every shift, jump and reduction at least once\n";
+	for sentence in &sentences
+	{
+		let line : String = sentence.iter().map(|term_idx| get_term_str(*term_idx))
+			.collect::<Vec<String>>().join(" ");
+		corpus += &line;
+		corpus += "\n";
+	}
+
+	let mut uncovered : Vec<&Coverage> = all.iter().filter(|item| !covered.contains(item)).collect();
+	uncovered.sort();
+	for item in &uncovered
+	{
+		println!("Warning: Table entry {item:?} is not covered by any test sentence.");
+	}
+
+	let outfilename : &str = "generated_tests.txt";
+	let mut outfile = File::create(outfilename).expect("Cannot create file.");
+	match outfile.write_all(corpus.as_bytes())
+	{
+		Ok(_) => println!("Successfully wrote {} test sentences covering {} of {} table entries to \"{outfilename}\".",
+			sentences.len(), all.len() - uncovered.len(), all.len()),
+		Err(res) =>
+		{
+			println!("Failed to write test sentences \"{outfilename}\": {res:?}.");
+			process::exit(1);
+		},
+	}
+
+	// test module with the sentences and derived rejected inputs
+	if let Some(test_module) = test_module
+	{
+		let rejected : Vec<Vec<TIndex>> = create_rejected(&sentences);
+		let code : String = create_test_module(&sentences, &rejected,
+			&parser, &types_module, &common_module);
+
+		let mut outfile = File::create(&test_module).expect("Cannot create file.");
+		match outfile.write_all(code.as_bytes())
+		{
+			Ok(_) => println!("Successfully wrote {} accepted and {} rejected test cases to \"{test_module}\".",
+				sentences.len(), rejected.len()),
+			Err(res) =>
+			{
+				println!("Failed to write test module \"{test_module}\": {res:?}.");
+				process::exit(1);
+			},
+		}
+	}
+}
@@ -0,0 +1,911 @@
+/**
+ * lalr1 command-line tool
+ * @author Tobias Weber (orcid: 0000-0002-7230-1932)
+ * @date 15-oct-2026
+ * @license see 'LICENSE' file
+ *
+ * usage: lalr1 new <name>
+ *   creates a grammar project skeleton in the directory <name>,
+ *   consisting of a table generator with an example grammar,
+ *   a lexer, a driver using the rust runtime, tests and build files
+ *
+ * usage: lalr1 import <spec file>
+ *   imports a lexertl/parsertl (gram_grep) specification, creates the
+ *   parsing tables for its grammar and writes its token regexes
+ */
+
+#include <iostream>
+#include <fstream>
+#include <sstream>
+#include <filesystem>
+#include <string>
+#include <vector>
+#include <algorithm>
+#include <cctype>
+
+#include "core/collection.h"
+#include "core/tablegen.h"
+#include "core/specimport.h"
+
+#include <boost/algorithm/string.hpp>
+#include <boost/program_options.hpp>
+namespace args = boost::program_options;
+namespace fs = std::filesystem;
+
+
+#ifndef LALR1_RS_DIR
+	#define LALR1_RS_DIR "/usr/local/share/lalr1/rs"
+#endif
+
+
+// ----------------------------------------------------------------------------
+// templates
+// ----------------------------------------------------------------------------
+
+/**
+ * table generator with the grammar definition
+ */
+static const std::string g_tmpl_parsergen = R"RAW(/**
+ * %%NAME%% parser generator
+ * @license see 'LICENSE' file
+ */
+
+#include <lalr1/collection.h>
+#include <lalr1/tablegen.h>
+#include <lalr1/options.h>
+
+#include <iostream>
+
+using namespace lalr1;
+
+
+/**
+ * non-terminal identifiers, see idents.rs
+ */
+enum : t_symbol_id
+{
+	START    = 10,
+	EXPR     = 20,
+};
+
+
+/**
+ * terminal identifiers, see idents.rs
+ */
+enum : t_symbol_id
+{
+	TOK_REAL = 1000,
+};
+
+
+/**
+ * semantic rule identifiers, see idents.rs
+ */
+enum : t_semantic_id
+{
+	SEM_START    = 100,
+	SEM_BRACKETS = 101,
+	SEM_ADD      = 200,
+	SEM_SUB      = 201,
+	SEM_MUL      = 202,
+	SEM_DIV      = 203,
+	SEM_USUB     = 211,
+	SEM_REAL     = 400,
+};
+
+
+static bool create_tables(const std::string& outfile)
+{
+	// non-terminals
+	NonTerminalPtr start = std::make_shared<NonTerminal>(START, "start");
+	NonTerminalPtr expr = std::make_shared<NonTerminal>(EXPR, "expr");
+
+	// terminals
+	TerminalPtr op_plus = std::make_shared<Terminal>('+', "+");
+	TerminalPtr op_minus = std::make_shared<Terminal>('-', "-");
+	TerminalPtr op_mult = std::make_shared<Terminal>('*', "*");
+	TerminalPtr op_div = std::make_shared<Terminal>('/', "/");
+	TerminalPtr bracket_open = std::make_shared<Terminal>('(', "(");
+	TerminalPtr bracket_close = std::make_shared<Terminal>(')', ")");
+	TerminalPtr sym_real = std::make_shared<Terminal>(TOK_REAL, "real");
+
+	// precedences and associativities
+	op_plus->SetPrecedence(70, 'l');
+	op_minus->SetPrecedence(70, 'l');
+	op_mult->SetPrecedence(80, 'l');
+	op_div->SetPrecedence(80, 'l');
+
+	// rules
+	start->AddRule({ expr }, SEM_START);
+	expr->AddRule({ bracket_open, expr, bracket_close }, SEM_BRACKETS);
+	expr->AddRule({ expr, op_plus, expr }, SEM_ADD);
+	expr->AddRule({ expr, op_minus, expr }, SEM_SUB);
+	expr->AddRule({ expr, op_mult, expr }, SEM_MUL);
+	expr->AddRule({ expr, op_div, expr }, SEM_DIV);
+	expr->AddRule({ op_minus, expr }, SEM_USUB);
+	expr->AddRule({ sym_real }, SEM_REAL);
+
+	// lalr(1) collection
+	ElementPtr elem = std::make_shared<Element>(
+		start, 0, 0, Terminal::t_terminalset{{ g_end }});
+	ClosurePtr closure = std::make_shared<Closure>();
+	closure->AddElement(elem);
+
+	CollectionPtr coll = std::make_shared<Collection>(closure);
+	coll->DoTransitions();
+
+	// tables
+	TableGen exporter{coll};
+	exporter.SetAcceptingRule(SEM_START);
+
+	if(!exporter.CreateParseTables())
+		return false;
+	return exporter.SaveParseTablesRS(outfile);
+}
+
+
+int main(int argc, char** argv)
+{
+	std::string outfile = argc > 1 ? argv[1] : "%%NAME%%.rs";
+
+	try
+	{
+		if(!create_tables(outfile))
+		{
+			std::cerr << "Error: Parsing tables could not be created." << std::endl;
+			return -1;
+		}
+	}
+	catch(const std::exception& err)
+	{
+		std::cerr << "Error: " << err.what() << std::endl;
+		return -1;
+	}
+
+	return 0;
+}
+)RAW";
+
+
+/**
+ * symbol and semantic rule identifiers for the rust runtime
+ */
+static const std::string g_tmpl_idents = R"RAW(/*
+ * %%NAME%% parser ids, keep in sync with %%NAME%%_parsergen.cpp
+ * @license see 'LICENSE' file
+ */
+
+#![allow(unused)]
+
+use types;
+
+
+type TSymbolId = types::TSymbolId;
+type TSemanticId = types::TSemanticId;
+
+
+// semantic ids
+pub const SEM_START_ID : TSemanticId    = 100;
+pub const SEM_BRACKETS_ID : TSemanticId = 101;
+pub const SEM_ADD_ID : TSemanticId      = 200;
+pub const SEM_SUB_ID : TSemanticId      = 201;
+pub const SEM_MUL_ID : TSemanticId      = 202;
+pub const SEM_DIV_ID : TSemanticId      = 203;
+pub const SEM_USUB_ID : TSemanticId     = 211;
+pub const SEM_REAL_ID : TSemanticId     = 400;
+
+// token ids
+pub const TOK_REAL_ID : TSymbolId       = 1000;
+
+// nonterminals
+pub const NONTERM_START : TSymbolId     = 10;
+pub const NONTERM_EXPR : TSymbolId      = 20;
+)RAW";
+
+
+/**
+ * types for the rust runtime
+ */
+static const std::string g_tmpl_types = R"RAW(/*
+ * %%NAME%% parser types
+ * @license see 'LICENSE' file
+ */
+
+#![allow(unused)]
+
+// a parser generated with parsergen's --narrow-types embeds its own tables,
+// the runtime has to use them, so its driver is built with the narrow_types feature
+#[cfg(feature = "narrow_types")]
+pub use generated_parser::lalr1_types::*;
+
+#[cfg(not(feature = "narrow_types"))]
+pub use %%NAME%%::lalr1_tables;
+#[cfg(not(feature = "narrow_types"))]
+pub type TIndex = lalr1_tables::TIndex;
+#[cfg(not(feature = "narrow_types"))]
+pub type TSymbolId = lalr1_tables::TSymbolId;
+#[cfg(not(feature = "narrow_types"))]
+pub type TSemanticId = lalr1_tables::TSemanticId;
+
+
+pub type TLVal = f64;
+)RAW";
+
+
+/**
+ * lexer specification
+ */
+static const std::string g_tmpl_lexer = R"RAW(/*
+ * %%NAME%% lexer
+ * @license see 'LICENSE' file
+ */
+
+#![allow(unused)]
+
+use types::*;
+use idents::*;
+use common::*;
+
+
+/*
+ * single-character tokens, their ids are the characters themselves
+ */
+const SINGLE_CHAR_TOKENS : &str = "+-*/()";
+
+
+/*
+ * get the length of the real number at the start of the string
+ */
+fn match_real(chars : &[char]) -> usize
+{
+	let mut len : usize = 0;
+	let mut had_point : bool = false;
+	let mut had_exp : bool = false;
+
+	while len < chars.len()
+	{
+		let ch : char = chars[len];
+
+		if ch.is_digit(10)
+		{
+			len += 1;
+		}
+		else if ch == '.' && !had_point && !had_exp
+		{
+			had_point = true;
+			len += 1;
+		}
+		else if (ch == 'e' || ch == 'E') && !had_exp && len > 0
+		{
+			had_exp = true;
+			len += 1;
+
+			if len < chars.len() && (chars[len] == '+' || chars[len] == '-')
+			{
+				len += 1;
+			}
+		}
+		else
+		{
+			break;
+		}
+	}
+
+	len
+}
+
+
+/*
+ * split the input string into tokens
+ */
+pub fn get_tokens(str : &str) -> Result<Vec<Symbol>, String>
+{
+	let chars : Vec<char> = str.chars().collect();
+	let mut syms : Vec<Symbol> = Vec::<Symbol>::new();
+	let mut pos : usize = 0;
+
+	while pos < chars.len()
+	{
+		let ch : char = chars[pos];
+
+		// whitespace
+		if ch.is_whitespace()
+		{
+			pos += 1;
+			continue;
+		}
+
+		// single-character tokens
+		if SINGLE_CHAR_TOKENS.contains(ch)
+		{
+			syms.push(Symbol{
+				is_term : true,
+				id : ch as TSymbolId,
+				val : 0 as TLVal,
+				strval : Some(ch.to_string()),
+				span : Some((pos, pos + 1)),
+			});
+			pos += 1;
+			continue;
+		}
+
+		// real numbers
+		let len : usize = match_real(&chars[pos..]);
+		if len > 0
+		{
+			let numstr : String = chars[pos .. pos+len].iter().collect();
+			let val : TLVal = parse_real_literal(&numstr)
+				.map_err(|err| format!("{}", err.offset(pos)))?;
+
+			syms.push(Symbol{
+				is_term : true,
+				id : TOK_REAL_ID,
+				val : val,
+				strval : Some(numstr),
+				span : Some((pos, pos + len)),
+			});
+			pos += len;
+			continue;
+		}
+
+		return Err(format!("Invalid character '{}' at position {}", ch, pos));
+	}
+
+	Ok(syms)
+}
+)RAW";
+
+
+/**
+ * driver
+ */
+static const std::string g_tmpl_driver = R"RAW(/*
+ * %%NAME%% parser driver
+ * @license see 'LICENSE' file
+ *
+ * usage: run_%%NAME%% [--test <file>]
+ */
+
+use std::io::stdin;
+use std::fs::read_to_string;
+use std::env::args;
+use std::process::exit;
+
+#[cfg(feature = "log")]
+extern crate log;
+
+mod %%NAME%%;
+mod idents;
+mod types;
+mod common;
+mod lexer;
+mod parser;
+
+use common::{Parsable, Symbol, TSemantics, ParseError};
+use types::*;
+use idents::*;
+use parser::Parser;
+
+
+fn set_semantics(parser : &mut dyn Parsable)
+{
+	const SEMANTICS : [(TSemanticId, TSemantics); 8] =
+	[
+		( SEM_START_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		{
+			if !_done { return 0 as TLVal; }
+			args[0].val
+		} ),
+
+		( SEM_BRACKETS_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		{
+			if !_done { return 0 as TLVal; }
+			args[1].val
+		} ),
+
+		( SEM_ADD_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		{
+			if !_done { return 0 as TLVal; }
+			args[0].val + args[2].val
+		} ),
+
+		( SEM_SUB_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		{
+			if !_done { return 0 as TLVal; }
+			args[0].val - args[2].val
+		} ),
+
+		( SEM_MUL_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		{
+			if !_done { return 0 as TLVal; }
+			args[0].val * args[2].val
+		} ),
+
+		( SEM_DIV_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		{
+			if !_done { return 0 as TLVal; }
+			args[0].val / args[2].val
+		} ),
+
+		( SEM_USUB_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		{
+			if !_done { return 0 as TLVal; }
+			-args[1].val
+		} ),
+
+		( SEM_REAL_ID, |args : Vec<Symbol>, _done : bool, _retval : TLVal| -> TLVal
+		{
+			if !_done { return 0 as TLVal; }
+			args[0].val
+		} ),
+	];
+
+	parser.set_semantics(&SEMANTICS);
+}
+
+
+/*
+ * tokenise and parse a line of input
+ */
+fn parse_line(parser : &mut dyn Parsable, line : &str) -> Result<TLVal, String>
+{
+	let mut tokens = lexer::get_tokens(line)?;
+	tokens.push(Symbol{
+		is_term : true,
+		id : parser.get_end_id(),
+		val : 0 as TLVal,
+		strval : Some("<end>".to_string()),
+		span : Some((line.len(), line.len())),
+	});
+
+	parser.set_input(&tokens);
+	parser.try_parse()
+		.map(|topsym| topsym.val)
+		.map_err(|err : ParseError| format!("{}", err))
+}
+
+
+/*
+ * run the test cases in the given file,
+ * each line has the form "<input> => <expected result or 'error'>"
+ */
+fn run_tests(parser : &mut dyn Parsable, file : &str) -> bool
+{
+	let tests = read_to_string(file).expect("Could not read test file.");
+	let mut num_failed : usize = 0;
+	let mut num_tests : usize = 0;
+
+	for (line_nr, line) in tests.lines().enumerate()
+	{
+		let line = line.trim();
+		if line.len() == 0 || line.starts_with('#')
+		{
+			continue;
+		}
+
+		let (input, expected) = match line.split_once("=>")
+		{
+			Some((input, expected)) => (input.trim(), expected.trim()),
+			None =>
+			{
+				println!("Line {}: Invalid test case \"{}\".", line_nr + 1, line);
+				num_failed += 1;
+				continue;
+			}
+		};
+
+		num_tests += 1;
+		let result = parse_line(parser, input);
+		let ok : bool = match (&result, expected)
+		{
+			(Err(_), "error") => true,
+			(Ok(val), _) => match expected.parse::<TLVal>()
+			{
+				Ok(expected_val) => (val - expected_val).abs() <= 1e-6 * (1. + expected_val.abs()),
+				Err(_) => false,
+			},
+			_ => false,
+		};
+
+		if !ok
+		{
+			println!("Line {}: \"{}\" gave {:?}, expected {}.", line_nr + 1, input, result, expected);
+			num_failed += 1;
+		}
+	}
+
+	println!("{} of {} tests passed.", num_tests - num_failed.min(num_tests), num_tests);
+	num_failed == 0
+}
+
+
+fn main()
+{
+	let mut parser = Parser::new();
+	set_semantics(&mut parser);
+
+	let argv : Vec<String> = args().collect();
+	if argv.len() > 2 && argv[1] == "--test"
+	{
+		exit(if run_tests(&mut parser, &argv[2]) { 0 } else { -1 });
+	}
+
+	loop
+	{
+		let mut line : String = String::new();
+		if stdin().read_line(&mut line).expect("Could not read input.") == 0
+		{
+			break;
+		}
+
+		line = line.trim().to_string();
+		if line.len() == 0
+		{
+			continue;
+		}
+
+		match parse_line(&mut parser, &line)
+		{
+			Ok(val) => println!("{}", val),
+			Err(err) => println!("Error: {}.", err),
+		}
+	}
+}
+)RAW";
+
+
+/**
+ * test cases
+ */
+static const std::string g_tmpl_tests = R"RAW(# %%NAME%% test cases: <input> => <expected result or 'error'>
+1 + 2 * 3 => 7
+(1 + 2) * 3 => 9
+1 - 2 - 3 => -4
+-(2 + 3) * 2 => -10
+8 / 4 / 2 => 1
+1.5e2 + .5 => 150.5
+2 * (3 + => error
+)RAW";
+
+
+/**
+ * build integration
+ */
+static const std::string g_tmpl_cmake = R"RAW(#
+# %%NAME%% grammar project
+# @license see 'LICENSE' file
+#
+# build:
+#   mkdir build && cd build
+#   cmake .. && make && ctest
+#
+
+cmake_minimum_required(VERSION 3.12)
+
+project(%%NAME%%)
+enable_language(CXX)
+
+set(CMAKE_CXX_STANDARD 20)
+list(APPEND CMAKE_MODULE_PATH "${CMAKE_INSTALL_PREFIX}/lib/cmake" "/usr/local/lib/cmake")
+
+find_package(LibLalr1 REQUIRED)
+find_package(Boost REQUIRED)
+find_package(Threads REQUIRED)
+find_program(RUSTC rustc REQUIRED)
+
+include_directories(${LibLalr1_INCLUDE_DIRS} ${Boost_INCLUDE_DIRS})
+
+enable_testing()
+
+
+# table generator
+add_executable(%%NAME%%_parsergen %%NAME%%_parsergen.cpp)
+target_link_libraries(%%NAME%%_parsergen ${LibLalr1_LIBRARIES} Threads::Threads)
+
+# parsing tables
+add_custom_command(
+	OUTPUT ${CMAKE_BINARY_DIR}/%%NAME%%.rs
+	COMMAND %%NAME%%_parsergen ${CMAKE_BINARY_DIR}/%%NAME%%.rs
+	DEPENDS %%NAME%%_parsergen
+)
+
+# driver
+set(RS_SOURCES
+	${CMAKE_SOURCE_DIR}/src/run_%%NAME%%.rs
+	${CMAKE_SOURCE_DIR}/src/idents.rs
+	${CMAKE_SOURCE_DIR}/src/types.rs
+	${CMAKE_SOURCE_DIR}/src/lexer.rs
+	${CMAKE_SOURCE_DIR}/src/common.rs
+	${CMAKE_SOURCE_DIR}/src/parser.rs
+)
+
+add_custom_command(
+	OUTPUT ${CMAKE_BINARY_DIR}/run_%%NAME%%
+	COMMAND ${CMAKE_COMMAND} -E make_directory ${CMAKE_BINARY_DIR}/rs
+	COMMAND ${CMAKE_COMMAND} -E copy ${RS_SOURCES} ${CMAKE_BINARY_DIR}/rs/
+	COMMAND ${CMAKE_COMMAND} -E copy ${CMAKE_BINARY_DIR}/%%NAME%%.rs ${CMAKE_BINARY_DIR}/rs/
+	COMMAND ${RUSTC} --edition 2015 -A dead_code
+		-o ${CMAKE_BINARY_DIR}/run_%%NAME%% ${CMAKE_BINARY_DIR}/rs/run_%%NAME%%.rs
+	DEPENDS ${RS_SOURCES} ${CMAKE_BINARY_DIR}/%%NAME%%.rs
+)
+
+add_custom_target(run_%%NAME%%_bin ALL DEPENDS ${CMAKE_BINARY_DIR}/run_%%NAME%%)
+
+# tests
+add_test(NAME %%NAME%%_tests
+	COMMAND ${CMAKE_BINARY_DIR}/run_%%NAME%% --test ${CMAKE_SOURCE_DIR}/tests/%%NAME%%.tests
+)
+)RAW";
+
+
+/**
+ * files of the rust runtime which are copied into the project
+ */
+static const std::vector<std::string> g_runtime_files{ "common.rs", "parser.rs" };
+// ----------------------------------------------------------------------------
+
+
+
+/**
+ * the project name is used for file, module and target names
+ */
+static bool is_valid_name(const std::string& name)
+{
+	if(name.empty() || std::isdigit(name[0]))
+		return false;
+
+	return std::all_of(name.begin(), name.end(), [](char c) -> bool
+	{
+		return std::isalnum(c) || c == '_';
+	});
+}
+
+
+static bool write_file(const fs::path& file, const std::string& tmpl,
+	const std::string& name, bool overwrite)
+{
+	if(fs::exists(file) && !overwrite)
+	{
+		std::cerr << "Error: File " << file << " already exists." << std::endl;
+		return false;
+	}
+
+	std::string content = tmpl;
+	boost::replace_all(content, "%%NAME%%", name);
+
+	std::ofstream ofstr{file};
+	if(!ofstr)
+	{
+		std::cerr << "Error: Cannot open " << file << "." << std::endl;
+		return false;
+	}
+
+	ofstr << content;
+	std::cout << "Created " << file << "." << std::endl;
+	return true;
+}
+
+
+/**
+ * create a new grammar project
+ */
+static bool create_project(const std::string& name, const fs::path& dir,
+	const fs::path& runtime_dir, bool overwrite)
+{
+	if(!is_valid_name(name))
+	{
+		std::cerr << "Error: Invalid project name \"" << name << "\","
+			<< " it has to be an identifier consisting of alphanumeric characters and '_'."
+			<< std::endl;
+		return false;
+	}
+
+	for(const std::string& file : g_runtime_files)
+	{
+		if(!fs::exists(runtime_dir / file))
+		{
+			std::cerr << "Error: Rust runtime file " << (runtime_dir / file)
+				<< " was not found, use --runtime to set its directory."
+				<< std::endl;
+			return false;
+		}
+	}
+
+	fs::create_directories(dir / "src");
+	fs::create_directories(dir / "tests");
+
+	std::vector<std::pair<fs::path, const std::string*>> files
+	{
+		{ dir / (name + "_parsergen.cpp"), &g_tmpl_parsergen },
+		{ dir / "src" / "idents.rs", &g_tmpl_idents },
+		{ dir / "src" / "types.rs", &g_tmpl_types },
+		{ dir / "src" / "lexer.rs", &g_tmpl_lexer },
+		{ dir / "src" / ("run_" + name + ".rs"), &g_tmpl_driver },
+		{ dir / "tests" / (name + ".tests"), &g_tmpl_tests },
+		{ dir / "CMakeLists.txt", &g_tmpl_cmake },
+	};
+
+	for(const auto& [file, tmpl] : files)
+	{
+		if(!write_file(file, *tmpl, name, overwrite))
+			return false;
+	}
+
+	for(const std::string& file : g_runtime_files)
+	{
+		fs::copy_file(runtime_dir / file, dir / "src" / file,
+			overwrite ? fs::copy_options::overwrite_existing
+				: fs::copy_options::skip_existing);
+		std::cout << "Created " << (dir / "src" / file) << "." << std::endl;
+	}
+
+	return true;
+}
+
+
+
+/**
+ * import a lexertl/parsertl specification and create the parsing tables
+ */
+static bool import_spec(const fs::path& spec_file, fs::path tables_file)
+{
+	lalr1::SpecImporter importer;
+	if(!importer.LoadFile(spec_file.string()))
+	{
+		std::cerr << "Error: Cannot open " << spec_file << "." << std::endl;
+		return false;
+	}
+
+	for(const std::string& warning : importer.GetWarnings())
+		std::cerr << "Warning: " << warning << std::endl;
+
+	if(tables_file.empty())
+		tables_file = spec_file.stem().string() + ".json";
+	fs::path tokens_file = tables_file.parent_path() / (tables_file.stem().string() + "_tokens.json");
+
+	if(!importer.SaveTokensJSON(tokens_file.string()))
+	{
+		std::cerr << "Error: Cannot write " << tokens_file << "." << std::endl;
+		return false;
+	}
+	std::cout << "Created " << tokens_file << " with "
+		<< importer.GetTokens().size() << " token(s)." << std::endl;
+
+	if(!importer.HasGrammar())
+		return true;
+
+	std::cout << "Imported " << importer.GetNonTerminals().size() << " non-terminal(s) and "
+		<< importer.GetTerminals().size() << " terminal(s)." << std::endl;
+
+	lalr1::ElementPtr elem = std::make_shared<lalr1::Element>(
+		importer.GetStartNonTerminal(), 0, 0,
+		lalr1::Terminal::t_terminalset{{ lalr1::g_end }});
+	lalr1::ClosurePtr closure = std::make_shared<lalr1::Closure>();
+	closure->AddElement(elem);
+
+	lalr1::CollectionPtr coll = std::make_shared<lalr1::Collection>(closure);
+	coll->DoTransitions();
+
+	lalr1::TableGen exporter{coll};
+	exporter.SetAcceptingRule(importer.GetAcceptingRule());
+	if(!exporter.CreateParseTables())
+	{
+		std::cerr << "Error: Parsing tables could not be created." << std::endl;
+		return false;
+	}
+
+	// the output format is given by the file extension
+	std::string ext = tables_file.extension().string();
+	bool ok = false;
+	if(ext == ".rs")
+		ok = exporter.SaveParseTablesRS(tables_file.string());
+	else if(ext == ".java")
+		ok = exporter.SaveParseTablesJava(tables_file.string());
+	else if(ext == ".tab" || ext == ".h")
+		ok = exporter.SaveParseTablesCXX(tables_file.string());
+	else if(ext == ".json")
+		ok = exporter.SaveParseTablesJSON(tables_file.string());
+	else
+	{
+		std::cerr << "Error: Unknown table format \"" << ext << "\","
+			<< " use .rs, .java, .tab or .json." << std::endl;
+		return false;
+	}
+
+	if(!ok)
+	{
+		std::cerr << "Error: Cannot write " << tables_file << "." << std::endl;
+		return false;
+	}
+
+	std::cout << "Created " << tables_file << " with "
+		<< exporter.GetShiftTable().size1() << " state(s)." << std::endl;
+	return true;
+}
+
+
+int main(int argc, char** argv)
+{
+	try
+	{
+		std::ios_base::sync_with_stdio(false);
+
+		// --------------------------------------------------------------------
+		// get program arguments
+		// --------------------------------------------------------------------
+		std::string command;
+		std::string name;
+		std::string dir;
+		std::string runtime_dir = LALR1_RS_DIR;
+		std::string out_file;
+		bool overwrite = false;
+		bool show_help = false;
+
+		args::options_description arg_descr("lalr1 arguments");
+		arg_descr.add_options()
+			("command", args::value<decltype(command)>(&command), "command, \"new\" creates a grammar project, \"import\" imports a lexertl/parsertl specification")
+			("name", args::value<decltype(name)>(&name), "project name or specification file")
+			("dir,d", args::value<decltype(dir)>(&dir), "project directory [default: name]")
+			("runtime,r", args::value<decltype(runtime_dir)>(&runtime_dir), "directory of the rust runtime files")
+			("out,o", args::value<decltype(out_file)>(&out_file), "output tables file of the import, e.g. grammar.rs [default: <spec>.json]")
+			("force,f", args::bool_switch(&overwrite), "overwrite existing files")
+			("help,h", args::bool_switch(&show_help), "show help");
+
+		args::positional_options_description posarg_descr;
+		posarg_descr.add("command", 1);
+		posarg_descr.add("name", 1);
+
+		auto argparser = args::command_line_parser{argc, argv};
+		argparser.style(args::command_line_style::default_style);
+		argparser.options(arg_descr);
+		argparser.positional(posarg_descr);
+
+		args::variables_map mapArgs;
+		auto parsedArgs = argparser.run();
+		args::store(parsedArgs, mapArgs);
+		args::notify(mapArgs);
+
+		if(show_help || command.empty())
+		{
+			std::cout << "Usage: lalr1 new <name> [options]\n"
+				<< "       lalr1 import <spec file> [options]\n\n" << arg_descr << std::endl;
+			return show_help ? 0 : -1;
+		}
+		// --------------------------------------------------------------------
+
+		if(command == "new")
+		{
+			if(name.empty())
+			{
+				std::cerr << "Error: No project name given." << std::endl;
+				return -1;
+			}
+
+			if(!create_project(name, dir.empty() ? name : dir, runtime_dir, overwrite))
+				return -1;
+		}
+		else if(command == "import")
+		{
+			if(name.empty())
+			{
+				std::cerr << "Error: No specification file given." << std::endl;
+				return -1;
+			}
+
+			if(!import_spec(name, out_file))
+				return -1;
+		}
+		else
+		{
+			std::cerr << "Error: Unknown command \"" << command << "\"." << std::endl;
+			return -1;
+		}
+	}
+	catch(const std::exception& ex)
+	{
+		std::cerr << "Error: " << ex.what() << std::endl;
+		return -1;
+	}
+
+	return 0;
+}
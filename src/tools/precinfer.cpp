@@ -0,0 +1,618 @@
+/**
+ * experimental tool: infers operator precedences and associativities
+ * for an ambiguous expression grammar from a corpus of example inputs
+ * @author Tobias Weber (orcid: 0000-0002-7230-1932)
+ * @date 15-oct-2026
+ * @license see 'LICENSE' file
+ *
+ * usage: lalr1-precinfer [--ops "+-*\/^"] <corpus file>
+ *
+ * corpus format, one example per line ('#' starts a comment):
+ *   <input> => <expected value>
+ *   <input> => <expected fully bracketed parse>
+ * e.g.:
+ *   1 + 2*3   => 7
+ *   1 - 2 - 3 => ((1-2)-3)
+ *
+ * the grammar is: expr -> expr op expr | ( expr ) | real, for all operators op;
+ * every assignment of operators to ordered precedence levels with a left or
+ * right associativity per level is tried, assignments whose parse tables still
+ * have conflicts or which do not reproduce the whole corpus are rejected
+ */
+
+#include "core/collection.h"
+#include "core/tablegen.h"
+#include "core/parser.h"
+
+#include <iostream>
+#include <fstream>
+#include <sstream>
+#include <string>
+#include <vector>
+#include <optional>
+#include <functional>
+#include <algorithm>
+#include <cmath>
+#include <cctype>
+
+#include <boost/algorithm/string.hpp>
+#include <boost/program_options.hpp>
+namespace args = boost::program_options;
+
+using namespace lalr1;
+
+
+/**
+ * symbol and semantic rule identifiers
+ */
+enum : t_symbol_id
+{
+	START    = 10,
+	EXPR     = 20,
+	TOK_REAL = 1000,
+};
+
+
+enum : t_semantic_id
+{
+	SEM_START    = 100,
+	SEM_BRACKETS = 101,
+	SEM_REAL     = 102,
+	SEM_OP_BEGIN = 200,  // one rule per operator
+};
+
+
+/**
+ * expression node holding the value and the bracketed parse
+ */
+class PrecNode : public ASTBase
+{
+public:
+	PrecNode(t_symbol_id id, std::optional<t_index> tableidx,
+		double val, const std::string& str)
+		: ASTBase{id, tableidx}, m_val{val}, m_str{str}
+	{}
+
+	virtual ~PrecNode() = default;
+
+	virtual bool IsTerminal() const override { return GetId() != EXPR && GetId() != START; }
+
+	double GetValue() const { return m_val; }
+	const std::string& GetString() const { return m_str; }
+
+
+private:
+	double m_val{};
+	std::string m_str{};
+};
+
+
+/**
+ * corpus entry
+ */
+struct CorpusEntry
+{
+	std::size_t line{};
+	std::string input{};
+	std::string expected{};
+	std::optional<double> expected_val{};
+};
+
+
+/**
+ * precedence level and associativity for each operator
+ */
+struct Assignment
+{
+	std::vector<std::size_t> levels{};  // level index per operator, 0 = lowest
+	std::vector<char> assocs{};         // associativity per level
+	std::size_t num_levels{};
+};
+
+
+/**
+ * the operators to infer the precedences for
+ */
+static std::string g_ops = "+-*/^";
+
+
+/**
+ * applies an operator
+ */
+static double apply_op(char op, double lhs, double rhs)
+{
+	switch(op)
+	{
+		case '+': return lhs + rhs;
+		case '-': return lhs - rhs;
+		case '*': return lhs * rhs;
+		case '/': return lhs / rhs;
+		case '%': return std::fmod(lhs, rhs);
+		case '^': return std::pow(lhs, rhs);
+	}
+
+	throw std::runtime_error(std::string("Unknown operator '") + op + "'.");
+}
+
+
+/**
+ * removes all white spaces
+ */
+static std::string strip_spaces(const std::string& str)
+{
+	std::string stripped;
+	for(char c : str)
+	{
+		if(!std::isspace(c))
+			stripped += c;
+	}
+	return stripped;
+}
+
+
+/**
+ * gets a number if the string only consists of one
+ */
+static std::optional<double> get_number(const std::string& str)
+{
+	try
+	{
+		std::size_t len = 0;
+		double val = std::stod(str, &len);
+		if(len == str.length())
+			return val;
+	}
+	catch(const std::exception&)
+	{
+	}
+
+	return std::nullopt;
+}
+
+
+/**
+ * loads the corpus file
+ */
+static std::vector<CorpusEntry> load_corpus(const std::string& filename)
+{
+	std::ifstream ifstr{filename};
+	if(!ifstr)
+		throw std::runtime_error("Cannot open corpus file \"" + filename + "\".");
+
+	std::vector<CorpusEntry> corpus;
+	std::string line;
+	std::size_t linenr = 0;
+
+	while(std::getline(ifstr, line))
+	{
+		++linenr;
+
+		if(std::size_t comment = line.find('#'); comment != std::string::npos)
+			line.erase(comment);
+		boost::trim(line);
+		if(line == "")
+			continue;
+
+		std::size_t sep = line.find("=>");
+		if(sep == std::string::npos)
+		{
+			std::ostringstream ostrErr;
+			ostrErr << "Missing \"=>\" in corpus line " << linenr << ".";
+			throw std::runtime_error(ostrErr.str());
+		}
+
+		CorpusEntry entry;
+		entry.line = linenr;
+		entry.input = boost::trim_copy(line.substr(0, sep));
+		entry.expected = strip_spaces(line.substr(sep + 2));
+		entry.expected_val = get_number(entry.expected);
+		corpus.emplace_back(std::move(entry));
+	}
+
+	return corpus;
+}
+
+
+/**
+ * splits an input into tokens
+ */
+static t_toknodes get_tokens(const std::string& input, const t_mapIdIdx& term_idx)
+{
+	auto get_idx = [&term_idx](t_symbol_id id) -> t_index
+	{
+		auto iter = term_idx.find(id);
+		if(iter == term_idx.end())
+			throw std::runtime_error("No table index for terminal " + std::to_string(id) + ".");
+		return iter->second;
+	};
+
+	t_toknodes tokens;
+
+	for(std::size_t pos = 0; pos < input.length();)
+	{
+		char c = input[pos];
+
+		if(std::isspace(c))
+		{
+			++pos;
+		}
+		else if(std::isdigit(c) || c == '.')
+		{
+			std::size_t len = 0;
+			double val = std::stod(input.substr(pos), &len);
+			std::string str = input.substr(pos, len);
+			tokens.emplace_back(std::make_shared<PrecNode>(TOK_REAL, get_idx(TOK_REAL), val, str));
+			pos += len;
+		}
+		else if(c == '(' || c == ')' || g_ops.find(c) != std::string::npos)
+		{
+			tokens.emplace_back(std::make_shared<PrecNode>(t_symbol_id(c), get_idx(t_symbol_id(c)), 0., std::string{c}));
+			++pos;
+		}
+		else
+		{
+			throw std::runtime_error(std::string("Invalid input character '") + c + "'.");
+		}
+	}
+
+	tokens.emplace_back(std::make_shared<PrecNode>(END_IDENT, get_idx(END_IDENT), 0., ""));
+	return tokens;
+}
+
+
+/**
+ * creates the expression grammar with the given operator precedences
+ */
+static NonTerminalPtr create_grammar(const Assignment& assign, t_semanticrules& rules)
+{
+	NonTerminalPtr start = std::make_shared<NonTerminal>(START, "start");
+	NonTerminalPtr expr = std::make_shared<NonTerminal>(EXPR, "expr");
+
+	TerminalPtr bracket_open = std::make_shared<Terminal>('(', "(");
+	TerminalPtr bracket_close = std::make_shared<Terminal>(')', ")");
+	TerminalPtr sym_real = std::make_shared<Terminal>(TOK_REAL, "real");
+
+	// start -> expr
+	start->AddRule({ expr }, SEM_START);
+	rules.emplace(std::make_pair(SEM_START,
+	[](bool full_match, const t_semanticargs& args, [[maybe_unused]] t_astbaseptr retval) -> t_astbaseptr
+	{
+		if(!full_match) return nullptr;
+		return args[0];
+	}));
+
+	// expr -> ( expr )
+	expr->AddRule({ bracket_open, expr, bracket_close }, SEM_BRACKETS);
+	rules.emplace(std::make_pair(SEM_BRACKETS,
+	[](bool full_match, const t_semanticargs& args, [[maybe_unused]] t_astbaseptr retval) -> t_astbaseptr
+	{
+		if(!full_match) return nullptr;
+		return args[1];
+	}));
+
+	// expr -> real
+	expr->AddRule({ sym_real }, SEM_REAL);
+	rules.emplace(std::make_pair(SEM_REAL,
+	[](bool full_match, const t_semanticargs& args, [[maybe_unused]] t_astbaseptr retval) -> t_astbaseptr
+	{
+		if(!full_match) return nullptr;
+		auto arg = std::dynamic_pointer_cast<PrecNode>(args[0]);
+		return std::make_shared<PrecNode>(EXPR, std::nullopt, arg->GetValue(), arg->GetString());
+	}));
+
+	// expr -> expr op expr
+	for(std::size_t op_idx = 0; op_idx < g_ops.length(); ++op_idx)
+	{
+		char op = g_ops[op_idx];
+		std::size_t level = assign.levels[op_idx];
+
+		TerminalPtr op_term = std::make_shared<Terminal>(op, std::string{op});
+		op_term->SetPrecedence((level + 1) * 10, assign.assocs[level]);
+
+		t_semantic_id sem_id = SEM_OP_BEGIN + op_idx;
+		expr->AddRule({ expr, op_term, expr }, sem_id);
+		rules.emplace(std::make_pair(sem_id,
+		[op](bool full_match, const t_semanticargs& args, [[maybe_unused]] t_astbaseptr retval) -> t_astbaseptr
+		{
+			if(!full_match) return nullptr;
+			auto lhs = std::dynamic_pointer_cast<PrecNode>(args[0]);
+			auto rhs = std::dynamic_pointer_cast<PrecNode>(args[2]);
+			return std::make_shared<PrecNode>(EXPR, std::nullopt,
+				apply_op(op, lhs->GetValue(), rhs->GetValue()),
+				"(" + lhs->GetString() + op + rhs->GetString() + ")");
+		}));
+	}
+
+	return start;
+}
+
+
+/**
+ * checks if an assignment resolves all conflicts and reproduces the corpus
+ * @return index of the first corpus entry that fails, if any
+ */
+static std::optional<std::size_t> check_assignment(const Assignment& assign,
+	const std::vector<CorpusEntry>& corpus, bool& conflicts, double eps)
+{
+	t_semanticrules rules;
+	NonTerminalPtr start = create_grammar(assign, rules);
+
+	ElementPtr elem = std::make_shared<Element>(
+		start, 0, 0, Terminal::t_terminalset{{ g_end }});
+	ClosurePtr closure = std::make_shared<Closure>();
+	closure->AddElement(elem);
+
+	CollectionPtr coll = std::make_shared<Collection>(closure);
+	TableGen tables{coll};
+
+	// unresolved conflicts throw
+	conflicts = false;
+	try
+	{
+		coll->SetStopOnConflicts(true);
+		coll->DoTransitions();
+
+		tables.SetAcceptingRule(SEM_START);
+		if(!tables.CreateParseTables())
+			conflicts = true;
+	}
+	catch(const std::exception&)
+	{
+		conflicts = true;
+	}
+
+	if(conflicts)
+		return std::nullopt;
+
+	Parser parser;
+	parser.SetShiftTable(&tables.GetShiftTable());
+	parser.SetReduceTable(&tables.GetReduceTable());
+	parser.SetJumpTable(&tables.GetJumpTable());
+	parser.SetSemanticIdxMap(&tables.GetSemanticIndexMap());
+	parser.SetNumRhsSymsPerRule(&tables.GetNumRhsSymbolsPerRule());
+	parser.SetLhsIndices(&tables.GetRuleLhsIndices());
+	parser.SetSemanticRules(&rules);
+	parser.SetEndId(END_IDENT);
+	parser.SetStartingState(tables.GetStartingState());
+	parser.SetAcceptingRule(tables.GetTableIndex(SEM_START, IndexTableKind::SEMANTIC));
+
+	for(std::size_t entry_idx = 0; entry_idx < corpus.size(); ++entry_idx)
+	{
+		const CorpusEntry& entry = corpus[entry_idx];
+
+		try
+		{
+			t_toknodes tokens = get_tokens(entry.input, tables.GetTermIndexMap());
+			auto result = std::dynamic_pointer_cast<PrecNode>(parser.Parse(tokens));
+			if(!result)
+				return entry_idx;
+
+			if(entry.expected_val)
+			{
+				if(std::abs(result->GetValue() - *entry.expected_val) > eps)
+					return entry_idx;
+			}
+			else if(result->GetString() != entry.expected)
+			{
+				return entry_idx;
+			}
+		}
+		catch(const std::exception&)
+		{
+			return entry_idx;
+		}
+	}
+
+	return std::nullopt;
+}
+
+
+/**
+ * calls the function for all operator precedence assignments
+ */
+static void for_all_assignments(const std::function<bool(const Assignment&)>& func)
+{
+	const std::size_t num_ops = g_ops.length();
+
+	Assignment assign;
+	assign.levels.resize(num_ops, 0);
+
+	while(true)
+	{
+		// only use assignments whose levels are contiguous
+		std::vector<bool> used(num_ops, false);
+		for(std::size_t level : assign.levels)
+			used[level] = true;
+
+		std::size_t num_levels = 0;
+		while(num_levels < num_ops && used[num_levels])
+			++num_levels;
+		bool contiguous = std::find(used.begin() + num_levels, used.end(), true) == used.end();
+
+		if(contiguous)
+		{
+			assign.num_levels = num_levels;
+
+			// try all associativities per level
+			for(std::size_t assoc_bits = 0; assoc_bits < (std::size_t(1) << num_levels); ++assoc_bits)
+			{
+				assign.assocs.clear();
+				for(std::size_t level = 0; level < num_levels; ++level)
+					assign.assocs.push_back((assoc_bits & (std::size_t(1) << level)) ? 'r' : 'l');
+
+				if(!func(assign))
+					return;
+			}
+		}
+
+		// next level combination
+		std::size_t op_idx = 0;
+		for(; op_idx < num_ops; ++op_idx)
+		{
+			if(++assign.levels[op_idx] < num_ops)
+				break;
+			assign.levels[op_idx] = 0;
+		}
+
+		if(op_idx == num_ops)
+			break;
+	}
+}
+
+
+/**
+ * prints an assignment, from the lowest to the highest precedence
+ */
+static void print_assignment(std::ostream& ostr, const Assignment& assign)
+{
+	for(std::size_t level = 0; level < assign.num_levels; ++level)
+	{
+		ostr << "\tprecedence " << (level + 1) * 10 << ", "
+			<< (assign.assocs[level] == 'r' ? "right" : "left") << ":";
+		for(std::size_t op_idx = 0; op_idx < g_ops.length(); ++op_idx)
+		{
+			if(assign.levels[op_idx] == level)
+				ostr << " " << g_ops[op_idx];
+		}
+		ostr << "\n";
+	}
+}
+
+
+int main(int argc, char** argv)
+{
+	try
+	{
+		std::ios_base::sync_with_stdio(false);
+
+		// --------------------------------------------------------------------
+		// get program arguments
+		// --------------------------------------------------------------------
+		std::string corpus_file;
+		std::size_t max_results = 16;
+		double eps = 1e-6;
+		bool verbose = false;
+		bool show_help = false;
+
+		args::options_description arg_descr("lalr1-precinfer arguments");
+		arg_descr.add_options()
+			("corpus", args::value<decltype(corpus_file)>(&corpus_file), "corpus file")
+			("ops,o", args::value<decltype(g_ops)>(&g_ops), "operators, e.g. \"+-*/^\"")
+			("max,m", args::value<decltype(max_results)>(&max_results), "maximum number of results to show")
+			("eps,e", args::value<decltype(eps)>(&eps), "tolerance for comparing values")
+			("verbose,v", args::bool_switch(&verbose), "show rejected assignments")
+			("help,h", args::bool_switch(&show_help), "show help");
+
+		args::positional_options_description posarg_descr;
+		posarg_descr.add("corpus", 1);
+
+		auto argparser = args::command_line_parser{argc, argv};
+		argparser.style(args::command_line_style::default_style);
+		argparser.options(arg_descr);
+		argparser.positional(posarg_descr);
+
+		args::variables_map mapArgs;
+		auto parsedArgs = argparser.run();
+		args::store(parsedArgs, mapArgs);
+		args::notify(mapArgs);
+
+		if(show_help || corpus_file == "")
+		{
+			std::cout << "Usage: " << argv[0] << " [options] <corpus file>\n"
+				<< arg_descr << std::endl;
+			return show_help ? 0 : -1;
+		}
+		// --------------------------------------------------------------------
+
+		for(std::size_t op_idx = 0; op_idx < g_ops.length(); ++op_idx)
+		{
+			if(std::string{"+-*/%^"}.find(g_ops[op_idx]) == std::string::npos
+				|| g_ops.find(g_ops[op_idx], op_idx + 1) != std::string::npos)
+			{
+				std::cerr << "Error: Invalid or duplicate operator '"
+					<< g_ops[op_idx] << "'." << std::endl;
+				return -1;
+			}
+		}
+
+		std::vector<CorpusEntry> corpus = load_corpus(corpus_file);
+		if(!corpus.size())
+		{
+			std::cerr << "Error: The corpus is empty." << std::endl;
+			return -1;
+		}
+
+		std::size_t num_tried = 0, num_conflicts = 0, num_consistent = 0;
+		std::vector<std::size_t> failures(corpus.size(), 0);
+
+		for_all_assignments([&](const Assignment& assign) -> bool
+		{
+			++num_tried;
+
+			bool conflicts = false;
+			std::optional<std::size_t> failed = check_assignment(assign, corpus, conflicts, eps);
+
+			if(conflicts)
+			{
+				++num_conflicts;
+				if(verbose)
+				{
+					std::cout << "Rejected, unresolved conflicts:\n";
+					print_assignment(std::cout, assign);
+				}
+			}
+			else if(failed)
+			{
+				++failures[*failed];
+				if(verbose)
+				{
+					std::cout << "Rejected, corpus line " << corpus[*failed].line << " fails:\n";
+					print_assignment(std::cout, assign);
+				}
+			}
+			else
+			{
+				++num_consistent;
+				if(num_consistent <= max_results)
+				{
+					std::cout << "Consistent assignment #" << num_consistent << ":\n";
+					print_assignment(std::cout, assign);
+				}
+			}
+
+			return true;
+		});
+
+		std::cout << "\nTried " << num_tried << " assignments, "
+			<< num_conflicts << " with conflicts, "
+			<< num_consistent << " consistent with the corpus." << std::endl;
+
+		if(num_consistent == 0)
+		{
+			// show the corpus entries that ruled out the most assignments
+			std::cout << "No assignment reproduces the corpus. Rejections per corpus line:\n";
+			for(std::size_t entry_idx = 0; entry_idx < corpus.size(); ++entry_idx)
+			{
+				if(!failures[entry_idx])
+					continue;
+				std::cout << "\tline " << corpus[entry_idx].line << " (\""
+					<< corpus[entry_idx].input << "\"): "
+					<< failures[entry_idx] << "\n";
+			}
+			std::cout.flush();
+			return -1;
+		}
+		else if(num_consistent > 1)
+		{
+			std::cout << "The corpus does not determine a unique assignment,"
+				<< " add examples to distinguish them." << std::endl;
+		}
+	}
+	catch(const std::exception& err)
+	{
+		std::cerr << "Error: " << err.what() << std::endl;
+		return -1;
+	}
+
+	return 0;
+}